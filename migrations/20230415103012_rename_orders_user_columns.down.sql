@@ -0,0 +1,2 @@
+ALTER TABLE orders RENAME COLUMN student_id TO student_uuid;
+ALTER TABLE orders RENAME COLUMN mentor_id TO mentor_uuid;
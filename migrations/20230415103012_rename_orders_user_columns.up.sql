@@ -0,0 +1,2 @@
+ALTER TABLE orders RENAME COLUMN student_uuid TO student_id;
+ALTER TABLE orders RENAME COLUMN mentor_uuid TO mentor_id;
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS messages (
+    id UUID NOT NULL,
+    order_id UUID NOT NULL REFERENCES orders (id) ON DELETE CASCADE,
+    sender_id UUID NOT NULL,
+    body VARCHAR(4096) NOT NULL,
+    read_at TIMESTAMPTZ,
+    edited_at TIMESTAMPTZ,
+    deleted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX IF NOT EXISTS messages_order_id_created_at_idx ON messages (order_id, created_at, id);
@@ -0,0 +1,28 @@
+CREATE TABLE IF NOT EXISTS notifications (
+    id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    kind VARCHAR(64) NOT NULL,
+    payload JSONB NOT NULL,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX IF NOT EXISTS notifications_user_id_created_at_idx ON notifications (user_id, created_at, id);
+
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id UUID NOT NULL,
+    kind VARCHAR(64) NOT NULL,
+    email BOOLEAN NOT NULL,
+    PRIMARY KEY (user_id, kind)
+);
+
+CREATE TABLE IF NOT EXISTS emails (
+    id UUID NOT NULL,
+    address VARCHAR NOT NULL,
+    subject VARCHAR NOT NULL,
+    body VARCHAR NOT NULL,
+    sent_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (id)
+);
@@ -0,0 +1,32 @@
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS status VARCHAR(16) NOT NULL DEFAULT 'in_progress'
+    CHECK (status IN ('in_progress', 'delivered', 'disputed', 'completed', 'refunded'));
+
+CREATE TABLE IF NOT EXISTS order_history (
+    id UUID NOT NULL,
+    order_id UUID NOT NULL REFERENCES orders (id) ON DELETE CASCADE,
+    status VARCHAR(16) NOT NULL,
+    changed_by UUID NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX IF NOT EXISTS order_history_order_id_idx ON order_history (order_id, created_at);
+
+CREATE TABLE IF NOT EXISTS disputes (
+    id UUID NOT NULL,
+    order_id UUID NOT NULL REFERENCES orders (id) ON DELETE CASCADE,
+    opened_by UUID NOT NULL,
+    reason VARCHAR(2048) NOT NULL,
+    evidence VARCHAR[] NOT NULL,
+    status VARCHAR(16) NOT NULL CHECK (status IN ('open', 'resolved')),
+    outcome VARCHAR(16) CHECK (outcome IN ('release', 'refund', 'split')),
+    student_amount INTEGER,
+    mentor_amount INTEGER,
+    resolution_note VARCHAR(2048),
+    resolved_by UUID,
+    created_at TIMESTAMPTZ NOT NULL,
+    resolved_at TIMESTAMPTZ,
+    PRIMARY KEY (id)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS disputes_open_order_id_idx ON disputes (order_id) WHERE status = 'open';
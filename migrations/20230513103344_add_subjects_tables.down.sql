@@ -0,0 +1,4 @@
+DROP FUNCTION IF EXISTS subject_subtree;
+DROP TABLE IF EXISTS order_subjects;
+DROP TABLE IF EXISTS mentor_subjects;
+DROP TABLE IF EXISTS subjects;
@@ -0,0 +1,61 @@
+CREATE TABLE IF NOT EXISTS subjects (
+    id UUID NOT NULL,
+    parent_id UUID REFERENCES subjects (id),
+    slug VARCHAR(64) NOT NULL UNIQUE,
+    name VARCHAR(128) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (id)
+);
+
+CREATE TABLE IF NOT EXISTS mentor_subjects (
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    subject_id UUID NOT NULL REFERENCES subjects (id) ON DELETE CASCADE,
+    PRIMARY KEY (user_id, subject_id)
+);
+
+CREATE TABLE IF NOT EXISTS order_subjects (
+    order_id UUID NOT NULL REFERENCES orders (id) ON DELETE CASCADE,
+    subject_id UUID NOT NULL REFERENCES subjects (id) ON DELETE CASCADE,
+    PRIMARY KEY (order_id, subject_id)
+);
+
+CREATE OR REPLACE FUNCTION subject_subtree(root_slug VARCHAR) RETURNS TABLE (id UUID) AS $$
+    WITH RECURSIVE subtree AS (
+        SELECT subjects.id FROM subjects WHERE subjects.slug = root_slug
+        UNION
+        SELECT subjects.id FROM subjects JOIN subtree ON subjects.parent_id = subtree.id
+    )
+    SELECT subtree.id FROM subtree;
+$$ LANGUAGE SQL STABLE;
+
+INSERT INTO subjects (id, parent_id, slug, name, created_at)
+VALUES
+    (gen_random_uuid(), NULL, 'mathematics', 'Mathematics', NOW()),
+    (gen_random_uuid(), NULL, 'computer-science', 'Computer Science', NOW()),
+    (gen_random_uuid(), NULL, 'natural-sciences', 'Natural Sciences', NOW()),
+    (gen_random_uuid(), NULL, 'languages', 'Languages', NOW()),
+    (gen_random_uuid(), NULL, 'humanities', 'Humanities', NOW());
+
+INSERT INTO subjects (id, parent_id, slug, name, created_at)
+SELECT gen_random_uuid(), parent.id, child.slug, child.name, NOW()
+FROM (
+    VALUES
+        ('mathematics', 'algebra', 'Algebra'),
+        ('mathematics', 'calculus', 'Calculus'),
+        ('mathematics', 'statistics', 'Statistics'),
+        ('mathematics', 'discrete-mathematics', 'Discrete Mathematics'),
+        ('computer-science', 'programming', 'Programming'),
+        ('computer-science', 'algorithms', 'Algorithms and Data Structures'),
+        ('computer-science', 'databases', 'Databases'),
+        ('computer-science', 'networking', 'Computer Networks'),
+        ('natural-sciences', 'physics', 'Physics'),
+        ('natural-sciences', 'chemistry', 'Chemistry'),
+        ('natural-sciences', 'biology', 'Biology'),
+        ('languages', 'english', 'English'),
+        ('languages', 'german', 'German'),
+        ('languages', 'ukrainian', 'Ukrainian'),
+        ('humanities', 'history', 'History'),
+        ('humanities', 'philosophy', 'Philosophy'),
+        ('humanities', 'economics', 'Economics')
+) AS child (parent_slug, slug, name)
+JOIN subjects AS parent ON parent.slug = child.parent_slug;
@@ -0,0 +1,4 @@
+DROP FUNCTION IF EXISTS mentor_is_available;
+DROP TABLE IF EXISTS mentor_availability_exceptions;
+DROP TABLE IF EXISTS mentor_availability;
+DROP TABLE IF EXISTS mentor_profiles;
@@ -0,0 +1,60 @@
+CREATE TABLE IF NOT EXISTS mentor_profiles (
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    headline VARCHAR(128) NOT NULL,
+    hourly_rate INTEGER NOT NULL CHECK (hourly_rate >= 0),
+    languages VARCHAR(32)[] NOT NULL DEFAULT '{}',
+    education VARCHAR(1024),
+    time_zone VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (user_id)
+);
+
+CREATE TABLE IF NOT EXISTS mentor_availability (
+    id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES mentor_profiles (user_id) ON DELETE CASCADE,
+    weekday SMALLINT NOT NULL CHECK (weekday BETWEEN 0 AND 6),
+    start_time TIME NOT NULL,
+    end_time TIME NOT NULL,
+    CHECK (start_time < end_time),
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX IF NOT EXISTS mentor_availability_user_id_weekday_idx
+    ON mentor_availability (user_id, weekday);
+
+CREATE TABLE IF NOT EXISTS mentor_availability_exceptions (
+    id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES mentor_profiles (user_id) ON DELETE CASCADE,
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ NOT NULL,
+    reason VARCHAR(256),
+    CHECK (starts_at < ends_at),
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX IF NOT EXISTS mentor_availability_exceptions_user_id_idx
+    ON mentor_availability_exceptions (user_id);
+
+CREATE OR REPLACE FUNCTION mentor_is_available(
+    mentor_id UUID,
+    window_start TIMESTAMPTZ,
+    window_end TIMESTAMPTZ
+) RETURNS BOOLEAN AS $$
+    SELECT EXISTS (
+        SELECT 1
+        FROM mentor_profiles
+        JOIN mentor_availability ON mentor_availability.user_id = mentor_profiles.user_id
+        WHERE mentor_profiles.user_id = mentor_id
+          AND mentor_availability.weekday = EXTRACT(ISODOW FROM window_start AT TIME ZONE mentor_profiles.time_zone) - 1
+          AND (window_start AT TIME ZONE mentor_profiles.time_zone)::DATE = (window_end AT TIME ZONE mentor_profiles.time_zone)::DATE
+          AND (window_start AT TIME ZONE mentor_profiles.time_zone)::TIME >= mentor_availability.start_time
+          AND (window_end AT TIME ZONE mentor_profiles.time_zone)::TIME <= mentor_availability.end_time
+    ) AND NOT EXISTS (
+        SELECT 1
+        FROM mentor_availability_exceptions
+        WHERE mentor_availability_exceptions.user_id = mentor_id
+          AND TSTZRANGE(mentor_availability_exceptions.starts_at, mentor_availability_exceptions.ends_at)
+              && TSTZRANGE(window_start, window_end)
+    );
+$$ LANGUAGE SQL STABLE;
@@ -0,0 +1,27 @@
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+CREATE TABLE IF NOT EXISTS sessions (
+    id UUID NOT NULL,
+    order_id UUID NOT NULL REFERENCES orders (id) ON DELETE CASCADE,
+    mentor_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    student_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    starts_at TIMESTAMPTZ NOT NULL,
+    ends_at TIMESTAMPTZ NOT NULL,
+    status VARCHAR(16) NOT NULL DEFAULT 'booked',
+    cancelled_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    cancellation_reason VARCHAR(512),
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    CHECK (starts_at < ends_at),
+    CONSTRAINT sessions_mentor_overlap EXCLUDE USING gist (
+        mentor_id WITH =,
+        TSTZRANGE(starts_at, ends_at) WITH &&
+    ) WHERE (status = 'booked'),
+    CONSTRAINT sessions_student_overlap EXCLUDE USING gist (
+        student_id WITH =,
+        TSTZRANGE(starts_at, ends_at) WITH &&
+    ) WHERE (status = 'booked'),
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX IF NOT EXISTS sessions_order_id_idx ON sessions (order_id);
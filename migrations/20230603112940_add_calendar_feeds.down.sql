@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS calendar_tokens;
+ALTER TABLE sessions DROP COLUMN IF EXISTS sequence;
+ALTER TABLE orders DROP COLUMN IF EXISTS deadline;
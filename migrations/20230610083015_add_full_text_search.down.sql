@@ -0,0 +1,13 @@
+DROP TRIGGER IF EXISTS users_search ON users;
+DROP TRIGGER IF EXISTS mentor_profiles_search ON mentor_profiles;
+DROP TRIGGER IF EXISTS orders_search ON orders;
+DROP FUNCTION IF EXISTS users_search_trigger;
+DROP FUNCTION IF EXISTS mentors_search_trigger;
+DROP FUNCTION IF EXISTS orders_search_trigger;
+DROP FUNCTION IF EXISTS index_mentor;
+DROP FUNCTION IF EXISTS index_order;
+DROP INDEX IF EXISTS users_username_trgm_idx;
+DROP TABLE IF EXISTS mentor_search;
+DROP TABLE IF EXISTS order_search;
+DROP FUNCTION IF EXISTS search_config;
+ALTER TABLE orders DROP COLUMN IF EXISTS language;
@@ -0,0 +1,96 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+ALTER TABLE orders ADD COLUMN IF NOT EXISTS language VARCHAR(32);
+
+CREATE OR REPLACE FUNCTION search_config(language VARCHAR) RETURNS REGCONFIG AS $$
+    SELECT COALESCE(
+        (SELECT pg_ts_config.oid::REGCONFIG FROM pg_ts_config WHERE pg_ts_config.cfgname = LOWER(language)),
+        'simple'::REGCONFIG
+    );
+$$ LANGUAGE SQL STABLE;
+
+CREATE TABLE IF NOT EXISTS order_search (
+    order_id UUID NOT NULL REFERENCES orders (id) ON DELETE CASCADE,
+    config REGCONFIG NOT NULL,
+    document TSVECTOR NOT NULL,
+    PRIMARY KEY (order_id)
+);
+
+CREATE INDEX IF NOT EXISTS order_search_document_idx ON order_search USING GIN (document);
+
+CREATE TABLE IF NOT EXISTS mentor_search (
+    user_id UUID NOT NULL REFERENCES mentor_profiles (user_id) ON DELETE CASCADE,
+    config REGCONFIG NOT NULL,
+    document TSVECTOR NOT NULL,
+    PRIMARY KEY (user_id)
+);
+
+CREATE INDEX IF NOT EXISTS mentor_search_document_idx ON mentor_search USING GIN (document);
+
+CREATE INDEX IF NOT EXISTS users_username_trgm_idx ON users USING GIN (username gin_trgm_ops);
+
+CREATE OR REPLACE FUNCTION index_order(target_id UUID) RETURNS VOID AS $$
+    INSERT INTO order_search (order_id, config, document)
+    SELECT
+        orders.id,
+        search_config(COALESCE(orders.language, 'english')),
+        SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(orders.language, 'english')), orders.title), 'A')
+            || SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(orders.language, 'english')), orders.description), 'B')
+            || SETWEIGHT(TO_TSVECTOR('simple', orders.title), 'C')
+    FROM orders
+    WHERE orders.id = target_id
+    ON CONFLICT (order_id) DO UPDATE
+    SET config = EXCLUDED.config, document = EXCLUDED.document;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION index_mentor(target_id UUID) RETURNS VOID AS $$
+    INSERT INTO mentor_search (user_id, config, document)
+    SELECT
+        users.id,
+        search_config(COALESCE(mentor_profiles.languages[1], 'english')),
+        SETWEIGHT(TO_TSVECTOR('simple', CONCAT_WS(' ', users.username, users.first_name, users.last_name)), 'A')
+            || SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(mentor_profiles.languages[1], 'english')), mentor_profiles.headline), 'A')
+            || SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(mentor_profiles.languages[1], 'english')), COALESCE(users.about, '')), 'B')
+            || SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(mentor_profiles.languages[1], 'english')), COALESCE(mentor_profiles.education, '')), 'C')
+    FROM users
+    JOIN mentor_profiles ON mentor_profiles.user_id = users.id
+    WHERE users.id = target_id
+    ON CONFLICT (user_id) DO UPDATE
+    SET config = EXCLUDED.config, document = EXCLUDED.document;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION orders_search_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM index_order(NEW.id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION mentors_search_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM index_mentor(NEW.user_id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION users_search_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM index_mentor(NEW.id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER orders_search
+AFTER INSERT OR UPDATE OF title, description, language ON orders
+FOR EACH ROW EXECUTE FUNCTION orders_search_trigger();
+
+CREATE TRIGGER mentor_profiles_search
+AFTER INSERT OR UPDATE ON mentor_profiles
+FOR EACH ROW EXECUTE FUNCTION mentors_search_trigger();
+
+CREATE TRIGGER users_search
+AFTER UPDATE OF username, first_name, last_name, about ON users
+FOR EACH ROW EXECUTE FUNCTION users_search_trigger();
+
+SELECT index_order(orders.id) FROM orders;
+SELECT index_mentor(mentor_profiles.user_id) FROM mentor_profiles;
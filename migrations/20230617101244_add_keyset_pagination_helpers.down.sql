@@ -0,0 +1,6 @@
+DROP FUNCTION IF EXISTS mentor_sort_key;
+DROP FUNCTION IF EXISTS order_sort_key;
+DROP FUNCTION IF EXISTS user_sort_key;
+DROP FUNCTION IF EXISTS keyset_after;
+DROP FUNCTION IF EXISTS sort_integer;
+DROP FUNCTION IF EXISTS sort_timestamp;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS privacy_settings (
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    name VARCHAR(16) NOT NULL CHECK (name IN ('public', 'members', 'private')),
+    email VARCHAR(16) NOT NULL CHECK (email IN ('public', 'members', 'private')),
+    age VARCHAR(16) NOT NULL CHECK (age IN ('public', 'members', 'private')),
+    about VARCHAR(16) NOT NULL CHECK (about IN ('public', 'members', 'private')),
+    updated_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (user_id)
+);
@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS uploads (
+    id UUID NOT NULL,
+    owner_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    order_id UUID REFERENCES orders (id) ON DELETE CASCADE,
+    kind VARCHAR(16) NOT NULL CHECK (kind IN ('avatar', 'attachment')),
+    file_name VARCHAR(255) NOT NULL,
+    content_type VARCHAR(128) NOT NULL,
+    size BIGINT NOT NULL CHECK (size >= 0),
+    storage_key VARCHAR(255) NOT NULL,
+    thumbnail_key VARCHAR(255),
+    scan_status VARCHAR(16) NOT NULL CHECK (scan_status IN ('clean', 'unscanned')),
+    created_at TIMESTAMPTZ NOT NULL,
+    CHECK ((kind = 'attachment') = (order_id IS NOT NULL)),
+    PRIMARY KEY (id)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uploads_avatar_owner_id_idx
+    ON uploads (owner_id) WHERE kind = 'avatar';
+
+CREATE INDEX IF NOT EXISTS uploads_order_id_created_at_idx
+    ON uploads (order_id, created_at) WHERE order_id IS NOT NULL;
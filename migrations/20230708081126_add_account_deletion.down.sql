@@ -0,0 +1,4 @@
+DROP FUNCTION IF EXISTS purge_user(UUID, TIMESTAMPTZ);
+DROP INDEX IF EXISTS users_pending_deletion_idx;
+ALTER TABLE users DROP COLUMN IF EXISTS purged_at;
+ALTER TABLE users DROP COLUMN IF EXISTS deleted_at;
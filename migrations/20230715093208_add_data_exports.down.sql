@@ -0,0 +1,2 @@
+DROP FUNCTION IF EXISTS export_user_data(UUID);
+DROP TABLE IF EXISTS data_exports;
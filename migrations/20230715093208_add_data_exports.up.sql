@@ -0,0 +1,107 @@
+CREATE TABLE IF NOT EXISTS data_exports (
+    id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    status VARCHAR(16) NOT NULL CHECK (status IN ('pending', 'ready', 'failed', 'expired')),
+    storage_key VARCHAR(255),
+    size BIGINT CHECK (size >= 0),
+    created_at TIMESTAMPTZ NOT NULL,
+    completed_at TIMESTAMPTZ,
+    expires_at TIMESTAMPTZ,
+    CHECK ((status = 'ready') = (storage_key IS NOT NULL)),
+    PRIMARY KEY (id)
+);
+
+CREATE INDEX IF NOT EXISTS data_exports_user_id_created_at_idx
+    ON data_exports (user_id, created_at);
+
+CREATE INDEX IF NOT EXISTS data_exports_expires_at_idx
+    ON data_exports (expires_at) WHERE storage_key IS NOT NULL;
+
+-- One key per file in the archive. Secrets such as password hashes, calendar tokens and storage
+-- keys are left out, and so are other users' deleted messages.
+CREATE OR REPLACE FUNCTION export_user_data(target UUID) RETURNS JSONB AS $$
+    WITH user_orders AS (
+        SELECT orders.*
+        FROM orders
+        WHERE target IN (orders.student_id, orders.mentor_id)
+    )
+    SELECT JSONB_BUILD_OBJECT(
+        'profile', (
+            SELECT TO_JSONB(users) - 'pwd_hash'
+            FROM users
+            WHERE users.id = target
+        ),
+        'privacy', (
+            SELECT TO_JSONB(privacy_settings)
+            FROM privacy_settings
+            WHERE privacy_settings.user_id = target
+        ),
+        'mentor_profile', (
+            SELECT TO_JSONB(mentor_profiles) || JSONB_BUILD_OBJECT(
+                'subjects', COALESCE((
+                    SELECT JSONB_AGG(subjects.slug ORDER BY subjects.slug)
+                    FROM mentor_subjects
+                    JOIN subjects ON subjects.id = mentor_subjects.subject_id
+                    WHERE mentor_subjects.user_id = target
+                ), '[]'),
+                'availability', COALESCE((
+                    SELECT JSONB_AGG(TO_JSONB(mentor_availability) ORDER BY weekday, start_time)
+                    FROM mentor_availability
+                    WHERE mentor_availability.user_id = target
+                ), '[]'),
+                'availability_exceptions', COALESCE((
+                    SELECT JSONB_AGG(TO_JSONB(mentor_availability_exceptions) ORDER BY starts_at)
+                    FROM mentor_availability_exceptions
+                    WHERE mentor_availability_exceptions.user_id = target
+                ), '[]')
+            )
+            FROM mentor_profiles
+            WHERE mentor_profiles.user_id = target
+        ),
+        'orders', COALESCE((
+            SELECT JSONB_AGG(TO_JSONB(user_orders) ORDER BY user_orders.created_at)
+            FROM user_orders
+        ), '[]'),
+        'order_history', COALESCE((
+            SELECT JSONB_AGG(TO_JSONB(order_history) ORDER BY order_history.created_at)
+            FROM order_history
+            WHERE order_history.order_id IN (SELECT id FROM user_orders)
+        ), '[]'),
+        'messages', COALESCE((
+            SELECT JSONB_AGG(TO_JSONB(messages) ORDER BY messages.created_at)
+            FROM messages
+            WHERE messages.order_id IN (SELECT id FROM user_orders)
+              AND (messages.deleted_at IS NULL OR messages.sender_id = target)
+        ), '[]'),
+        'disputes', COALESCE((
+            SELECT JSONB_AGG(TO_JSONB(disputes) ORDER BY disputes.created_at)
+            FROM disputes
+            WHERE disputes.order_id IN (SELECT id FROM user_orders)
+        ), '[]'),
+        'sessions', COALESCE((
+            SELECT JSONB_AGG(TO_JSONB(sessions) ORDER BY sessions.starts_at)
+            FROM sessions
+            WHERE target IN (sessions.mentor_id, sessions.student_id)
+        ), '[]'),
+        'notifications', COALESCE((
+            SELECT JSONB_AGG(TO_JSONB(notifications) ORDER BY notifications.created_at)
+            FROM notifications
+            WHERE notifications.user_id = target
+        ), '[]'),
+        'notification_preferences', COALESCE((
+            SELECT JSONB_AGG(TO_JSONB(notification_preferences) ORDER BY notification_preferences.kind)
+            FROM notification_preferences
+            WHERE notification_preferences.user_id = target
+        ), '[]'),
+        'events', COALESCE((
+            SELECT JSONB_AGG(TO_JSONB(events) ORDER BY events.id)
+            FROM events
+            WHERE events.user_id = target
+        ), '[]'),
+        'uploads', COALESCE((
+            SELECT JSONB_AGG(TO_JSONB(uploads) - 'storage_key' - 'thumbnail_key' ORDER BY uploads.created_at)
+            FROM uploads
+            WHERE uploads.owner_id = target
+        ), '[]')
+    );
+$$ LANGUAGE sql STABLE;
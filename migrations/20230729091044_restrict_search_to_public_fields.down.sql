@@ -0,0 +1,22 @@
+DROP TRIGGER IF EXISTS privacy_settings_search ON privacy_settings;
+DROP FUNCTION IF EXISTS privacy_search_trigger;
+
+CREATE OR REPLACE FUNCTION index_mentor(target_id UUID) RETURNS VOID AS $$
+    INSERT INTO mentor_search (user_id, config, document)
+    SELECT
+        users.id,
+        search_config(COALESCE(mentor_profiles.languages[1], 'english')),
+        SETWEIGHT(TO_TSVECTOR('simple', CONCAT_WS(' ', users.username, users.first_name, users.last_name)), 'A')
+            || SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(mentor_profiles.languages[1], 'english')), mentor_profiles.headline), 'A')
+            || SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(mentor_profiles.languages[1], 'english')), COALESCE(users.about, '')), 'B')
+            || SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(mentor_profiles.languages[1], 'english')), COALESCE(mentor_profiles.education, '')), 'C')
+    FROM users
+    JOIN mentor_profiles ON mentor_profiles.user_id = users.id
+    WHERE users.id = target_id
+    ON CONFLICT (user_id) DO UPDATE
+    SET config = EXCLUDED.config, document = EXCLUDED.document;
+$$ LANGUAGE SQL;
+
+SELECT index_mentor(mentor_profiles.user_id) FROM mentor_profiles;
+
+DROP FUNCTION IF EXISTS html_escape;
@@ -0,0 +1,39 @@
+CREATE OR REPLACE FUNCTION html_escape(source TEXT) RETURNS TEXT AS $$
+    SELECT REPLACE(REPLACE(REPLACE(REPLACE(REPLACE(source,
+        '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '"', '&quot;'), '''', '&#39;');
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION index_mentor(target_id UUID) RETURNS VOID AS $$
+    INSERT INTO mentor_search (user_id, config, document)
+    SELECT
+        users.id,
+        search_config(COALESCE(mentor_profiles.languages[1], 'english')),
+        SETWEIGHT(TO_TSVECTOR('simple', CONCAT_WS(' ',
+                users.username,
+                CASE WHEN COALESCE(privacy_settings.name, 'public') = 'public' THEN users.first_name END,
+                CASE WHEN COALESCE(privacy_settings.name, 'public') = 'public' THEN users.last_name END
+            )), 'A')
+            || SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(mentor_profiles.languages[1], 'english')), mentor_profiles.headline), 'A')
+            || SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(mentor_profiles.languages[1], 'english')),
+                CASE WHEN COALESCE(privacy_settings.about, 'public') = 'public' THEN COALESCE(users.about, '') ELSE '' END), 'B')
+            || SETWEIGHT(TO_TSVECTOR(search_config(COALESCE(mentor_profiles.languages[1], 'english')), COALESCE(mentor_profiles.education, '')), 'C')
+    FROM users
+    JOIN mentor_profiles ON mentor_profiles.user_id = users.id
+    LEFT JOIN privacy_settings ON privacy_settings.user_id = users.id
+    WHERE users.id = target_id
+    ON CONFLICT (user_id) DO UPDATE
+    SET config = EXCLUDED.config, document = EXCLUDED.document;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION privacy_search_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM index_mentor(NEW.user_id);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER privacy_settings_search
+AFTER INSERT OR UPDATE OF name, about ON privacy_settings
+FOR EACH ROW EXECUTE FUNCTION privacy_search_trigger();
+
+SELECT index_mentor(mentor_profiles.user_id) FROM mentor_profiles;
@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS events_lock ON events;
+DROP FUNCTION IF EXISTS lock_events;
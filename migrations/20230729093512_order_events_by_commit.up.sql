@@ -0,0 +1,13 @@
+-- Event ids double as resume cursors, so they have to become visible in the order they were
+-- handed out. Holding this lock from drawing ids until commit makes sure a transaction
+-- that drew a lower id can't commit after one that drew a higher id.
+CREATE OR REPLACE FUNCTION lock_events() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM pg_advisory_xact_lock('events'::REGCLASS::OID::BIGINT);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER events_lock
+    BEFORE INSERT ON events
+    FOR EACH STATEMENT EXECUTE FUNCTION lock_events();
@@ -0,0 +1,31 @@
+CREATE OR REPLACE FUNCTION purge_user(target UUID, at TIMESTAMPTZ) RETURNS BOOLEAN AS $$
+BEGIN
+    DELETE FROM notifications WHERE user_id = target;
+    DELETE FROM notification_preferences WHERE user_id = target;
+    DELETE FROM events WHERE user_id = target;
+
+    IF NOT EXISTS (SELECT 1 FROM orders WHERE target IN (orders.student_id, orders.mentor_id)) THEN
+        DELETE FROM users WHERE id = target;
+        RETURN TRUE;
+    END IF;
+
+    DELETE FROM mentor_profiles WHERE user_id = target;
+    DELETE FROM mentor_subjects WHERE user_id = target;
+    DELETE FROM privacy_settings WHERE user_id = target;
+    DELETE FROM calendar_tokens WHERE user_id = target;
+    DELETE FROM uploads WHERE owner_id = target AND kind = 'avatar';
+    UPDATE users
+    SET username = 'deleted' || LEFT(REPLACE(id::TEXT, '-', ''), 24),
+        email = REPLACE(id::TEXT, '-', '') || '@deleted.invalid',
+        first_name = NULL,
+        last_name = NULL,
+        pwd_hash = '',
+        age = NULL,
+        about = NULL,
+        verified = FALSE,
+        purged_at = at,
+        updated_at = at
+    WHERE id = target;
+    RETURN FALSE;
+END;
+$$ LANGUAGE plpgsql;
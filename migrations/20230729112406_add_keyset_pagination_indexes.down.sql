@@ -0,0 +1,52 @@
+CREATE OR REPLACE FUNCTION sort_timestamp(value TIMESTAMPTZ) RETURNS TEXT AS $$
+    SELECT TO_CHAR(value AT TIME ZONE 'UTC', 'YYYY-MM-DD"T"HH24:MI:SS.US');
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION sort_integer(value BIGINT) RETURNS TEXT AS $$
+    SELECT LPAD(value::TEXT, 20, '0');
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION keyset_after(
+    sort_key TEXT,
+    id UUID,
+    cursor_key TEXT,
+    cursor_id UUID,
+    descending BOOLEAN
+) RETURNS BOOLEAN AS $$
+    SELECT cursor_id IS NULL OR CASE
+        WHEN descending THEN (sort_key, id) < (cursor_key, cursor_id)
+        ELSE (sort_key, id) > (cursor_key, cursor_id)
+    END;
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION user_sort_key(target users, sort VARCHAR) RETURNS TEXT AS $$
+    SELECT CASE sort
+        WHEN 'username' THEN target.username
+        ELSE sort_timestamp(target.created_at)
+    END;
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION order_sort_key(target orders, sort VARCHAR) RETURNS TEXT AS $$
+    SELECT CASE sort
+        WHEN 'price' THEN sort_integer(target.price)
+        WHEN 'updated_at' THEN sort_timestamp(target.updated_at)
+        ELSE sort_timestamp(target.created_at)
+    END;
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION mentor_sort_key(profile mentor_profiles, target users, sort VARCHAR) RETURNS TEXT AS $$
+    SELECT CASE sort
+        WHEN 'username' THEN target.username
+        WHEN 'created_at' THEN sort_timestamp(profile.created_at)
+        ELSE sort_integer(profile.hourly_rate)
+    END;
+$$ LANGUAGE SQL IMMUTABLE;
+
+DROP INDEX IF EXISTS disputes_status_created_at_id_idx;
+DROP INDEX IF EXISTS mentor_profiles_hourly_rate_user_id_idx;
+DROP INDEX IF EXISTS mentor_profiles_created_at_user_id_idx;
+DROP INDEX IF EXISTS orders_price_id_idx;
+DROP INDEX IF EXISTS orders_updated_at_id_idx;
+DROP INDEX IF EXISTS orders_created_at_id_idx;
+DROP INDEX IF EXISTS users_username_id_idx;
+DROP INDEX IF EXISTS users_created_at_id_idx;
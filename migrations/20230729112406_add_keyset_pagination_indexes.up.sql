@@ -0,0 +1,15 @@
+CREATE INDEX IF NOT EXISTS users_created_at_id_idx ON users (created_at, id);
+CREATE INDEX IF NOT EXISTS users_username_id_idx ON users (username, id);
+CREATE INDEX IF NOT EXISTS orders_created_at_id_idx ON orders (created_at, id);
+CREATE INDEX IF NOT EXISTS orders_updated_at_id_idx ON orders (updated_at, id);
+CREATE INDEX IF NOT EXISTS orders_price_id_idx ON orders (price, id);
+CREATE INDEX IF NOT EXISTS mentor_profiles_created_at_user_id_idx ON mentor_profiles (created_at, user_id);
+CREATE INDEX IF NOT EXISTS mentor_profiles_hourly_rate_user_id_idx ON mentor_profiles (hourly_rate, user_id);
+CREATE INDEX IF NOT EXISTS disputes_status_created_at_id_idx ON disputes (status, created_at, id);
+
+DROP FUNCTION IF EXISTS mentor_sort_key;
+DROP FUNCTION IF EXISTS order_sort_key;
+DROP FUNCTION IF EXISTS user_sort_key;
+DROP FUNCTION IF EXISTS keyset_after;
+DROP FUNCTION IF EXISTS sort_integer;
+DROP FUNCTION IF EXISTS sort_timestamp;
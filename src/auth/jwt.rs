@@ -5,7 +5,10 @@ use jsonwebtoken::{DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::{config::env::JWT_SECRET, error::Result};
+use crate::{
+    config::env::JWT_SECRET,
+    error::{Error, Result},
+};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
@@ -34,11 +37,12 @@ impl Claims {
     }
 
     pub fn sign(self) -> Result<String> {
-        Ok(jsonwebtoken::encode(
+        jsonwebtoken::encode(
             &Header::default(),
             &self,
             &EncodingKey::from_secret(JWT_SECRET.as_bytes()),
-        )?)
+        )
+        .map_err(Error::Jwt)
     }
 
     pub fn verify(token: &str) -> Result<Claims> {
@@ -8,12 +8,8 @@ use axum::{
     http::request::Parts,
     RequestPartsExt, TypedHeader,
 };
-use jsonwebtoken::{decode, DecodingKey, Validation};
 
-use crate::{
-    config::env::JWT_SECRET,
-    error::{ApiError, Error},
-};
+use crate::error::{ApiError, Error};
 
 use self::jwt::Claims;
 
@@ -29,13 +25,6 @@ where
             .extract::<TypedHeader<Authorization<Bearer>>>()
             .await
             .map_err(Error::from)?;
-        let token_data = decode::<Claims>(
-            bearer.token(),
-            &DecodingKey::from_secret(JWT_SECRET.as_bytes()),
-            &Validation::default(),
-        )
-        .map_err(Error::from)?;
-
-        Ok(token_data.claims)
+        Ok(Claims::verify(bearer.token())?)
     }
 }
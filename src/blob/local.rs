@@ -0,0 +1,47 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use tokio::fs;
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone)]
+pub struct LocalBackend {
+    root: PathBuf,
+}
+
+impl LocalBackend {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    pub async fn put(&self, key: &str, bytes: Vec<u8>) -> Result<()> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(path, bytes).await?;
+        Ok(())
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        match fs::read(self.root.join(key)).await {
+            Ok(bytes) => Ok(bytes),
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                Err(Error::NotFound(sqlx::Error::RowNotFound))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<()> {
+        match fs::remove_file(self.root.join(key)).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn probe(&self) -> Result<()> {
+        fs::create_dir_all(&self.root).await?;
+        Ok(())
+    }
+}
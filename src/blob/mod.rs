@@ -0,0 +1,141 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::{
+    config::{
+        env::DOWNLOAD_SECRET,
+        upload::{BackendConfig, UploadConfig},
+    },
+    error::{Error, Result},
+    versioning::PREFIX,
+};
+
+use self::{local::LocalBackend, s3::S3Backend, scan::Scanner};
+
+mod local;
+mod s3;
+mod scan;
+
+pub use self::scan::ScanStatus;
+
+#[derive(Debug, Clone)]
+enum Backend {
+    Local(LocalBackend),
+    S3(S3Backend),
+}
+
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    backend: Backend,
+    scanner: Scanner,
+    config: Arc<UploadConfig>,
+}
+
+impl FileStore {
+    #[must_use]
+    pub fn new(config: UploadConfig) -> Self {
+        let backend = match &config.backend {
+            BackendConfig::Local { root } => Backend::Local(LocalBackend::new(root.clone())),
+            BackendConfig::S3 {
+                endpoint,
+                bucket,
+                region,
+                access_key,
+                secret_key,
+            } => Backend::S3(S3Backend::new(
+                endpoint,
+                bucket.clone(),
+                region.clone(),
+                access_key.clone(),
+                secret_key.clone(),
+            )),
+        };
+        let scanner = Scanner::new(&config.scanner);
+        Self {
+            backend,
+            scanner,
+            config: Arc::new(config),
+        }
+    }
+
+    pub fn config(&self) -> &UploadConfig {
+        &self.config
+    }
+
+    pub async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> Result<()> {
+        match &self.backend {
+            Backend::Local(backend) => backend.put(key, bytes).await,
+            Backend::S3(backend) => backend.put(key, content_type, bytes).await,
+        }
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        match &self.backend {
+            Backend::Local(backend) => backend.get(key).await,
+            Backend::S3(backend) => backend.get(key).await,
+        }
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<()> {
+        match &self.backend {
+            Backend::Local(backend) => backend.delete(key).await,
+            Backend::S3(backend) => backend.delete(key).await,
+        }
+    }
+
+    /// Checks that the backend is reachable without touching any stored file.
+    pub async fn probe(&self) -> Result<()> {
+        match &self.backend {
+            Backend::Local(backend) => backend.probe().await,
+            Backend::S3(backend) => backend.probe().await,
+        }
+    }
+
+    pub async fn scan(&self, bytes: &[u8]) -> Result<ScanStatus> {
+        self.scanner.scan(bytes).await
+    }
+
+    pub fn sign(&self, id: Uuid, variant: &str, now: DateTime<Utc>) -> (String, DateTime<Utc>) {
+        let expires_at = now + self.config.link_ttl();
+        let expires = expires_at.timestamp();
+        let signature = hex::encode(Self::mac(id, variant, expires).finalize().into_bytes());
+        let url = format!(
+            "{PREFIX}/files/{id}?variant={variant}&expires={expires}&signature={signature}"
+        );
+        (url, expires_at)
+    }
+
+    pub fn verify(
+        id: Uuid,
+        variant: &str,
+        expires: i64,
+        signature: &str,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let signature = hex::decode(signature).map_err(|_| Error::InvalidDownloadLink)?;
+        Self::mac(id, variant, expires)
+            .verify_slice(&signature)
+            .map_err(|_| Error::InvalidDownloadLink)?;
+        if expires < now.timestamp() {
+            return Err(Error::InvalidDownloadLink);
+        }
+        Ok(())
+    }
+
+    fn mac(id: Uuid, variant: &str, expires: i64) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(DOWNLOAD_SECRET.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(format!("download:{id}:{variant}:{expires}").as_bytes());
+        mac
+    }
+}
+
+impl Default for FileStore {
+    fn default() -> Self {
+        Self::new(UploadConfig::default())
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt::Write;
+
+use hmac::{Hmac, Mac};
+use reqwest::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Client, Method, Response, StatusCode, Url,
+};
+use sha2::{Digest, Sha256};
+
+use crate::error::{Error, Result};
+
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+// A minimal path-style S3 client signing requests with AWS Signature Version 4, which
+// is enough for S3 itself as well as compatible stores like MinIO.
+#[derive(Debug, Clone)]
+pub struct S3Backend {
+    client: Client,
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+}
+
+impl S3Backend {
+    pub fn new(
+        endpoint: &str,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    ) -> Self {
+        Self {
+            client: Client::new(),
+            endpoint: endpoint.trim_end_matches('/').to_owned(),
+            bucket,
+            region,
+            access_key,
+            secret_key,
+        }
+    }
+
+    pub async fn put(&self, key: &str, content_type: &str, bytes: Vec<u8>) -> Result<()> {
+        let response = self
+            .send(Method::PUT, key, bytes, Some(content_type))
+            .await?;
+        Self::check(response)?;
+        Ok(())
+    }
+
+    pub async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let response = self.send(Method::GET, key, Vec::new(), None).await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(Error::NotFound(sqlx::Error::RowNotFound));
+        }
+        Ok(Self::check(response)?.bytes().await?.to_vec())
+    }
+
+    pub async fn delete(&self, key: &str) -> Result<()> {
+        let response = self.send(Method::DELETE, key, Vec::new(), None).await?;
+        if response.status() != StatusCode::NOT_FOUND {
+            Self::check(response)?;
+        }
+        Ok(())
+    }
+
+    pub async fn probe(&self) -> Result<()> {
+        let response = self.send(Method::HEAD, "", Vec::new(), None).await?;
+        Self::check(response)?;
+        Ok(())
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        body: Vec<u8>,
+        content_type: Option<&str>,
+    ) -> Result<Response> {
+        let path = format!("/{}/{}", encode(&self.bucket), encode(key));
+        let url = Url::parse(&format!("{}{path}", self.endpoint))
+            .map_err(|_| Error::InvalidStorageEndpoint)?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_owned(),
+            (None, _) => return Err(Error::InvalidStorageEndpoint),
+        };
+
+        let now = chrono::offset::Utc::now();
+        let date = now.format("%Y%m%d").to_string();
+        let timestamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let canonical_request = format!(
+            "{method}\n{path}\n\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{timestamp}\n\n{SIGNED_HEADERS}\n{payload_hash}"
+        );
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{timestamp}\n{scope}\n{}",
+            hex::encode(Sha256::digest(canonical_request.as_bytes()))
+        );
+        let signing_key = [self.region.as_str(), "s3", "aws4_request"].iter().fold(
+            hmac(
+                format!("AWS4{}", self.secret_key).as_bytes(),
+                date.as_bytes(),
+            ),
+            |key, part| hmac(&key, part.as_bytes()),
+        );
+        let signature = hex::encode(hmac(&signing_key, string_to_sign.as_bytes()));
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={SIGNED_HEADERS}, Signature={signature}",
+            self.access_key
+        );
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("x-amz-date", timestamp)
+            .header("x-amz-content-sha256", payload_hash)
+            .header(AUTHORIZATION, authorization)
+            .body(body);
+        if let Some(content_type) = content_type {
+            request = request.header(CONTENT_TYPE, content_type);
+        }
+        Ok(request.send().await?)
+    }
+
+    fn check(response: Response) -> Result<Response> {
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(Error::FileStorage(response.status()))
+        }
+    }
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn encode(value: &str) -> String {
+    value.bytes().fold(String::new(), |mut encoded, byte| {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~' | b'/') {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+        encoded
+    })
+}
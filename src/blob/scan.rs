@@ -0,0 +1,67 @@
+use std::process::Stdio;
+
+use tokio::{io::AsyncWriteExt, process::Command};
+use tracing::warn;
+
+use crate::{
+    config::upload::ScannerConfig,
+    error::{Error, Result},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanStatus {
+    Clean,
+    Unscanned,
+}
+
+impl ScanStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Clean => "clean",
+            Self::Unscanned => "unscanned",
+        }
+    }
+}
+
+// The command receives the file on stdin and is expected to exit with 0 when it is clean,
+// 1 when it is infected and anything else when the scan itself failed (like `clamdscan -`).
+#[derive(Debug, Clone)]
+pub enum Scanner {
+    Disabled,
+    Command { program: String, args: Vec<String> },
+}
+
+impl Scanner {
+    pub fn new(config: &ScannerConfig) -> Self {
+        match config {
+            ScannerConfig::Disabled => Self::Disabled,
+            ScannerConfig::Command { program, args } => Self::Command {
+                program: program.clone(),
+                args: args.clone(),
+            },
+        }
+    }
+
+    pub async fn scan(&self, bytes: &[u8]) -> Result<ScanStatus> {
+        let Self::Command { program, args } = self else {
+            return Ok(ScanStatus::Unscanned);
+        };
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            // The scanner may stop reading as soon as it has made up its mind.
+            if let Err(err) = stdin.write_all(bytes).await {
+                warn!("failed to stream upload to the virus scanner: {err}");
+            }
+        }
+        match child.wait().await?.code() {
+            Some(0) => Ok(ScanStatus::Clean),
+            Some(1) => Err(Error::InfectedFile),
+            code => Err(Error::ScanFailed(code)),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use chrono::Duration;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct AccountPolicy {
+    deletion_grace_days: i64,
+    purge_interval_minutes: u64,
+}
+
+impl AccountPolicy {
+    #[must_use]
+    pub fn grace_period(&self) -> Duration {
+        Duration::days(self.deletion_grace_days)
+    }
+
+    #[must_use]
+    pub fn purge_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.purge_interval_minutes * 60)
+    }
+}
+
+impl Default for AccountPolicy {
+    fn default() -> Self {
+        Self {
+            deletion_grace_days: 30,
+            purge_interval_minutes: 60,
+        }
+    }
+}
@@ -2,3 +2,6 @@ use once_cell::sync::Lazy;
 
 pub static JWT_SECRET: Lazy<String> =
     Lazy::new(|| dotenvy::var("JWT_SECRET").expect("JWT_SECRET must be set"));
+
+pub static DOWNLOAD_SECRET: Lazy<String> =
+    Lazy::new(|| dotenvy::var("DOWNLOAD_SECRET").expect("DOWNLOAD_SECRET must be set"));
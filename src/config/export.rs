@@ -0,0 +1,51 @@
+use chrono::Duration;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct ExportPolicy {
+    retention_hours: i64,
+    max_requests: i64,
+    window_hours: i64,
+    cleanup_interval_minutes: u64,
+    build_timeout_minutes: i64,
+}
+
+impl ExportPolicy {
+    #[must_use]
+    pub fn retention(&self) -> Duration {
+        Duration::hours(self.retention_hours)
+    }
+
+    #[must_use]
+    pub fn max_requests(&self) -> i64 {
+        self.max_requests
+    }
+
+    #[must_use]
+    pub fn window(&self) -> Duration {
+        Duration::hours(self.window_hours)
+    }
+
+    #[must_use]
+    pub fn cleanup_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.cleanup_interval_minutes * 60)
+    }
+
+    #[must_use]
+    pub fn build_timeout(&self) -> Duration {
+        Duration::minutes(self.build_timeout_minutes)
+    }
+}
+
+impl Default for ExportPolicy {
+    fn default() -> Self {
+        Self {
+            retention_hours: 72,
+            max_requests: 3,
+            window_hours: 24,
+            cleanup_interval_minutes: 60,
+            build_timeout_minutes: 30,
+        }
+    }
+}
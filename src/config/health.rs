@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct HealthPolicy {
+    check_timeout_ms: u64,
+    drain_seconds: u64,
+}
+
+impl HealthPolicy {
+    /// How long a single readiness check may take before it counts as failed.
+    #[must_use]
+    pub fn check_timeout(&self) -> Duration {
+        Duration::from_millis(self.check_timeout_ms)
+    }
+
+    /// How long the server keeps serving after reporting not ready on shutdown, giving
+    /// load balancers time to stop routing new requests to it.
+    #[must_use]
+    pub fn drain_period(&self) -> Duration {
+        Duration::from_secs(self.drain_seconds)
+    }
+}
+
+impl Default for HealthPolicy {
+    fn default() -> Self {
+        Self {
+            check_timeout_ms: 2000,
+            drain_seconds: 5,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::{
+    net::{AddrParseError, IpAddr, SocketAddr},
+    str::FromStr,
+};
+
+use serde::Deserialize;
+
+/// Where the admin listener serving `/metrics` binds, apart from the public API.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MetricsConfig {
+    host: String,
+    port: u16,
+}
+
+impl MetricsConfig {
+    pub fn address(&self) -> Result<SocketAddr, AddrParseError> {
+        let ip_addr = IpAddr::from_str(&self.host)?;
+        Ok(SocketAddr::from((ip_addr, self.port)))
+    }
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_owned(),
+            port: 9000,
+        }
+    }
+}
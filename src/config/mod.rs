@@ -1,19 +1,49 @@
 use serde::Deserialize;
 
-use self::{app::AppConfig, storage::StorageConfig};
+use self::{
+    account::AccountPolicy, app::AppConfig, export::ExportPolicy, health::HealthPolicy,
+    metrics::MetricsConfig, session::SessionPolicy, storage::StorageConfig,
+    telemetry::TelemetryConfig, upload::UploadConfig, versioning::VersioningPolicy,
+};
 
+pub mod account;
 mod app;
 pub mod env;
+pub mod export;
+pub mod health;
+pub mod metrics;
 pub mod routes;
+pub mod session;
 mod storage;
+pub mod telemetry;
+pub mod upload;
+pub mod versioning;
 
 #[derive(Deserialize)]
 pub struct Config {
     pub app: AppConfig,
     pub storage: StorageConfig,
+    #[serde(default)]
+    pub sessions: SessionPolicy,
+    #[serde(default)]
+    pub uploads: UploadConfig,
+    #[serde(default)]
+    pub accounts: AccountPolicy,
+    #[serde(default)]
+    pub exports: ExportPolicy,
+    #[serde(default)]
+    pub versioning: VersioningPolicy,
+    #[serde(default)]
+    pub health: HealthPolicy,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub telemetry: TelemetryConfig,
 }
 
 impl Config {
+    /// Reads `config.yaml`, overridden by `APP__`-prefixed environment variables such as
+    /// `APP__TELEMETRY__LOG_FORMAT=json`.
     pub fn new() -> Result<Self, config::ConfigError> {
         let config = config::Config::builder()
             .add_source(config::File::from(
@@ -21,6 +51,11 @@ impl Config {
                     .expect("Failed to determine the current directory.")
                     .join("config.yaml"),
             ))
+            .add_source(
+                config::Environment::with_prefix("APP")
+                    .prefix_separator("__")
+                    .separator("__"),
+            )
             .build()?;
 
         config.try_deserialize::<Config>()
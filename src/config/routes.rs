@@ -1,28 +1,192 @@
 use axum::{
-    routing::{get, post, put},
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{delete, get, post, put, MethodRouter},
     Router,
 };
 
 use crate::{
-    routes::{auth, index, user},
-    storage::DbPool,
+    correlation, i18n, metrics,
+    routes::{
+        auth, calendar, dispute, export, health, index, mentor_profile, message,
+        metrics as export_metrics, notification, openapi, order, realtime, search, session,
+        subject, upload, user,
+    },
+    state::AppState,
+    versioning,
 };
 
-pub fn routes() -> Router<DbPool> {
-    let user_routes = Router::new()
+pub fn routes(state: AppState) -> Router {
+    let api = table().router;
+    let legacy = api.clone().layer(middleware::from_fn_with_state(
+        state.clone(),
+        versioning::deprecate,
+    ));
+
+    Router::new()
+        .route("/", get(index))
+        .route("/health/live", get(health::live))
+        .route("/health/ready", get(health::ready))
+        .route("/openapi.json", get(openapi::document))
+        .route("/docs", get(openapi::docs))
+        // Only reaches the routes above; `Routes::route` already covers the API.
+        .route_layer(middleware::from_fn(metrics::remember_route))
+        .nest(versioning::PREFIX, api)
+        .merge(legacy)
+        .layer(middleware::from_fn(i18n::negotiate))
+        .layer(middleware::from_fn(correlation::propagate))
+        .layer(middleware::from_fn(metrics::track))
+        .with_state(state)
+}
+
+/// Operational endpoints served on a separate listener that is not exposed publicly.
+pub fn admin(state: AppState) -> Router {
+    Router::new()
+        .route("/metrics", get(export_metrics::export))
+        .with_state(state)
+}
+
+/// Every versioned path the router serves, which the `OpenAPI` document is checked against.
+#[must_use]
+pub fn paths() -> Vec<String> {
+    table().paths
+}
+
+fn table() -> Routes {
+    let user_routes = Routes::new()
         .route("/", get(user::get_all))
         .route("/me", get(user::me).delete(user::delete))
         .route("/me/edit", put(user::edit))
         .route("/me/edit/email", put(user::edit_email))
         .route("/me/edit/password", put(user::edit_password))
-        .route("/:username", get(user::get_by_username));
+        .route(
+            "/me/privacy",
+            get(user::get_privacy).put(user::edit_privacy),
+        )
+        .route(
+            "/me/avatar",
+            put(upload::set_avatar)
+                .delete(upload::delete_avatar)
+                .layer(DefaultBodyLimit::disable()),
+        )
+        .route("/me/export", post(export::request))
+        .route("/me/export/:id", get(export::download))
+        .route("/me/subjects", put(subject::set_for_me))
+        .route("/me/sessions", get(session::get_mine))
+        .route("/me/calendar.ics", get(calendar::feed))
+        .route(
+            "/me/calendar-token",
+            get(calendar::get_token).post(calendar::rotate_token),
+        )
+        .route(
+            "/me/mentor-profile",
+            get(mentor_profile::get_own)
+                .put(mentor_profile::edit)
+                .delete(mentor_profile::delete),
+        )
+        .route("/me/notifications", get(notification::get_page))
+        .route("/me/notifications/read", post(notification::mark_all_read))
+        .route("/me/notifications/:id/read", post(notification::mark_read))
+        .route(
+            "/me/notification-preferences",
+            get(notification::get_preferences).put(notification::edit_preferences),
+        )
+        .route("/:username", get(user::get_by_username))
+        .route("/:username/avatar", get(upload::get_avatar))
+        .route("/:username/subjects", get(subject::get_for_user));
 
-    let auth_routes = Router::new()
+    let order_routes = Routes::new()
+        .route("/", get(order::get_all).post(order::create))
+        .route("/:id", get(order::get_by_id))
+        .route(
+            "/:id/messages",
+            get(message::get_page).post(message::create),
+        )
+        .route("/:id/messages/read", post(message::mark_read))
+        .route(
+            "/:id/messages/:message_id",
+            put(message::edit).delete(message::delete),
+        )
+        .route(
+            "/:id/disputes",
+            get(dispute::get_for_order).post(dispute::open),
+        )
+        .route(
+            "/:id/sessions",
+            get(session::get_for_order).post(session::book),
+        )
+        .route(
+            "/:id/attachments",
+            get(upload::get_attachments)
+                .post(upload::attach)
+                .layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/:id/attachments/:attachment_id",
+            delete(upload::delete_attachment),
+        )
+        .route("/:id/sessions/:session_id", put(session::reschedule))
+        .route("/:id/sessions/:session_id/cancel", post(session::cancel));
+
+    let admin_routes = Routes::new()
+        .route("/users", get(user::get_all_as_admin))
+        .route("/users/:username", get(user::get_by_username_as_admin))
+        .route("/disputes", get(dispute::get_queue))
+        .route("/disputes/:id", get(dispute::get_detail))
+        .route("/disputes/:id/resolve", post(dispute::resolve))
+        .route("/subjects", post(subject::create))
+        .route("/subjects/:id", put(subject::edit).delete(subject::delete));
+
+    let auth_routes = Routes::new()
         .route("/signup", post(auth::signup))
-        .route("/login", post(auth::login));
+        .route("/login", post(auth::login))
+        .route("/restore", post(auth::restore));
 
-    Router::new()
-        .route("/", get(index))
+    Routes::new()
+        .route("/ws", get(realtime::connect))
+        .route("/events", get(realtime::events))
+        .route("/subjects", get(subject::get_tree))
+        .route("/mentors", get(mentor_profile::search))
+        .route("/search", get(search::search))
+        .route("/search/users", get(search::find_usernames))
+        .route("/files/:id", get(upload::download))
         .nest("/users", user_routes)
+        .nest("/orders", order_routes)
+        .nest("/admin", admin_routes)
         .nest("/auth", auth_routes)
 }
+
+/// A router that remembers the paths it was given, as axum cannot list them.
+struct Routes {
+    router: Router<AppState>,
+    paths: Vec<String>,
+}
+
+impl Routes {
+    fn new() -> Self {
+        Self {
+            router: Router::new(),
+            paths: Vec::new(),
+        }
+    }
+
+    fn route(mut self, path: &str, method_router: MethodRouter<AppState>) -> Self {
+        self.paths.push(path.to_owned());
+        // Only the innermost router knows the full matched path of a nested route.
+        self.router = self.router.route(
+            path,
+            method_router.layer(middleware::from_fn(metrics::remember_route)),
+        );
+        self
+    }
+
+    fn nest(mut self, prefix: &str, routes: Self) -> Self {
+        self.paths
+            .extend(routes.paths.into_iter().map(|path| match path.as_str() {
+                "/" => prefix.to_owned(),
+                _ => format!("{prefix}{path}"),
+            }));
+        self.router = self.router.nest(prefix, routes.router);
+        self
+    }
+}
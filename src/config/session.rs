@@ -0,0 +1,38 @@
+use chrono::Duration;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(default)]
+pub struct SessionPolicy {
+    notice_hours: i64,
+    min_duration_minutes: i64,
+    max_duration_minutes: i64,
+}
+
+impl SessionPolicy {
+    #[must_use]
+    pub fn notice(&self) -> Duration {
+        Duration::hours(self.notice_hours)
+    }
+
+    #[must_use]
+    pub fn notice_hours(&self) -> i64 {
+        self.notice_hours
+    }
+
+    #[must_use]
+    pub fn allows_duration(&self, duration: Duration) -> bool {
+        duration >= Duration::minutes(self.min_duration_minutes)
+            && duration <= Duration::minutes(self.max_duration_minutes)
+    }
+}
+
+impl Default for SessionPolicy {
+    fn default() -> Self {
+        Self {
+            notice_hours: 24,
+            min_duration_minutes: 30,
+            max_duration_minutes: 240,
+        }
+    }
+}
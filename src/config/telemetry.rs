@@ -0,0 +1,64 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Compact,
+    Json,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    log_format: LogFormat,
+    log_filter: String,
+    service_name: String,
+    otlp_endpoint: Option<String>,
+    sample_ratio: f64,
+}
+
+impl TelemetryConfig {
+    #[must_use]
+    pub fn log_format(&self) -> LogFormat {
+        self.log_format
+    }
+
+    /// Directives used when `RUST_LOG` is not set, in the same syntax.
+    #[must_use]
+    pub fn log_filter(&self) -> &str {
+        &self.log_filter
+    }
+
+    #[must_use]
+    pub fn service_name(&self) -> &str {
+        &self.service_name
+    }
+
+    /// Base URL of an OTLP/HTTP collector, such as `http://collector:4318`. Spans are only
+    /// exported when this is set.
+    #[must_use]
+    pub fn otlp_endpoint(&self) -> Option<&str> {
+        self.otlp_endpoint.as_deref()
+    }
+
+    /// Share of new traces that are recorded. Requests continuing a remote trace follow the
+    /// caller's sampling decision instead.
+    #[must_use]
+    pub fn sample_ratio(&self) -> f64 {
+        self.sample_ratio
+    }
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            log_format: LogFormat::default(),
+            log_filter: "info,sqlx::query=warn".to_owned(),
+            service_name: "s4s".to_owned(),
+            otlp_endpoint: None,
+            sample_ratio: 1.0,
+        }
+    }
+}
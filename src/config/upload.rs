@@ -0,0 +1,113 @@
+use std::path::PathBuf;
+
+use chrono::Duration;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BackendConfig {
+    Local {
+        root: PathBuf,
+    },
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+impl Default for BackendConfig {
+    fn default() -> Self {
+        Self::Local {
+            root: std::env::temp_dir().join("s4s-uploads"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScannerConfig {
+    #[default]
+    Disabled,
+    Command {
+        program: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct UploadConfig {
+    pub backend: BackendConfig,
+    pub scanner: ScannerConfig,
+    max_avatar_bytes: usize,
+    max_attachment_bytes: usize,
+    avatar_content_types: Vec<String>,
+    attachment_content_types: Vec<String>,
+    thumbnail_size: u32,
+    link_ttl_seconds: i64,
+}
+
+impl UploadConfig {
+    #[must_use]
+    pub fn max_avatar_bytes(&self) -> usize {
+        self.max_avatar_bytes
+    }
+
+    #[must_use]
+    pub fn max_attachment_bytes(&self) -> usize {
+        self.max_attachment_bytes
+    }
+
+    #[must_use]
+    pub fn allows_avatar(&self, content_type: &str) -> bool {
+        self.avatar_content_types.iter().any(|t| t == content_type)
+    }
+
+    #[must_use]
+    pub fn allows_attachment(&self, content_type: &str) -> bool {
+        self.attachment_content_types
+            .iter()
+            .any(|t| t == content_type)
+    }
+
+    #[must_use]
+    pub fn thumbnail_size(&self) -> u32 {
+        self.thumbnail_size
+    }
+
+    #[must_use]
+    pub fn link_ttl(&self) -> Duration {
+        Duration::seconds(self.link_ttl_seconds)
+    }
+}
+
+impl Default for UploadConfig {
+    fn default() -> Self {
+        Self {
+            backend: BackendConfig::default(),
+            scanner: ScannerConfig::default(),
+            max_avatar_bytes: 5 * 1024 * 1024,
+            max_attachment_bytes: 20 * 1024 * 1024,
+            avatar_content_types: ["image/png", "image/jpeg", "image/webp"]
+                .map(String::from)
+                .to_vec(),
+            attachment_content_types: [
+                "application/pdf",
+                "application/zip",
+                "application/msword",
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+                "image/png",
+                "image/jpeg",
+                "text/plain",
+            ]
+            .map(String::from)
+            .to_vec(),
+            thumbnail_size: 128,
+            link_ttl_seconds: 15 * 60,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+pub struct VersioningPolicy {
+    legacy_sunset: Option<DateTime<Utc>>,
+}
+
+impl VersioningPolicy {
+    /// When the unversioned aliases stop being served, formatted as an HTTP date.
+    #[must_use]
+    pub fn sunset(&self) -> Option<String> {
+        self.legacy_sunset
+            .map(|at| at.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+    }
+}
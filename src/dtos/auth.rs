@@ -1,28 +1,29 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use crate::validators::is_lowercase_alphanumeric;
+use crate::{secret::Secret, validators::is_lowercase_alphanumeric};
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
 pub struct SignupForm {
     #[validate(length(min = 4, max = 32), custom = "is_lowercase_alphanumeric")]
     pub username: String,
     #[validate(email)]
     pub email: String,
     #[validate(length(min = 8), must_match(other = "repeat_password"))]
-    pub password: String,
-    repeat_password: String,
+    pub password: Secret<String>,
+    repeat_password: Secret<String>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
 pub struct LoginForm {
     #[validate(length(min = 4, max = 32), custom = "is_lowercase_alphanumeric")]
     pub username: String,
     #[validate(length(min = 8))]
-    pub password: String,
+    pub password: Secret<String>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, JsonSchema)]
 pub struct AuthBody {
     access_token: String,
     token_type: String,
@@ -0,0 +1,26 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{secret::Secret, versioning::PREFIX};
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct CalendarQuery {
+    #[validate(length(equal = 64))]
+    pub token: Secret<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CalendarTokenBody {
+    pub token: String,
+    pub url: String,
+}
+
+impl CalendarTokenBody {
+    pub fn new(token: String) -> Self {
+        Self {
+            url: format!("{PREFIX}/users/me/calendar.ics?token={token}"),
+            token,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::{
+        dispute::{Dispute, DisputeOutcome},
+        message::Message,
+        order::{Order, OrderHistoryEntry},
+    },
+    pagination::{timestamp_key, KeyKind, Sort},
+};
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct OpenDisputeForm {
+    #[validate(length(min = 1, max = 2048))]
+    pub reason: String,
+    #[serde(default)]
+    #[validate(length(max = 16))]
+    pub evidence: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct ResolveDisputeForm {
+    pub outcome: DisputeOutcome,
+    #[validate(range(min = 0))]
+    pub student_amount: Option<i32>,
+    #[validate(length(max = 2048))]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DisputesQuery {
+    #[serde(default)]
+    pub resolved: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeSort {
+    #[default]
+    CreatedAt,
+}
+
+impl Sort for DisputeSort {
+    type Item = Dispute;
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+        }
+    }
+
+    fn key(self, dispute: &Dispute) -> (String, Uuid) {
+        match self {
+            Self::CreatedAt => (timestamp_key(dispute.created_at), dispute.id),
+        }
+    }
+
+    fn key_kind(self) -> KeyKind {
+        match self {
+            Self::CreatedAt => KeyKind::Timestamp,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DisputeDetail {
+    pub dispute: Dispute,
+    pub order: Order,
+    pub history: Vec<OrderHistoryEntry>,
+    pub disputes: Vec<Dispute>,
+    pub messages: Vec<Message>,
+}
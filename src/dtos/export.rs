@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    models::export::{DataExport, ExportStatus},
+    versioning::PREFIX,
+};
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ExportBody {
+    pub id: Uuid,
+    pub status: String,
+    pub size: Option<i64>,
+    pub url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<DataExport> for ExportBody {
+    fn from(export: DataExport) -> Self {
+        let url = export
+            .has_status(ExportStatus::Ready)
+            .then(|| format!("{PREFIX}/users/me/export/{}", export.id));
+        Self {
+            id: export.id,
+            status: export.status,
+            size: export.size,
+            url,
+            created_at: export.created_at,
+            completed_at: export.completed_at,
+            expires_at: export.expires_at,
+        }
+    }
+}
+
+pub enum ExportDownload {
+    Pending(ExportBody),
+    Ready { file_name: String, bytes: Vec<u8> },
+}
@@ -0,0 +1,39 @@
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    Ok,
+    Unavailable,
+    Draining,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    Failed,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CheckBody {
+    pub status: CheckStatus,
+    pub duration_ms: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HealthBody {
+    pub status: HealthStatus,
+    pub checks: BTreeMap<&'static str, CheckBody>,
+}
+
+impl HealthBody {
+    pub fn is_ok(&self) -> bool {
+        self.status == HealthStatus::Ok
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::{
+        mentor_profile::{AvailabilityException, AvailabilitySlot, MentorListing, MentorProfile},
+        subject::Subject,
+    },
+    pagination::{integer_key, timestamp_key, KeyKind, Sort},
+    validators::{are_languages, is_time_zone},
+};
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct MentorProfileForm {
+    #[validate(length(min = 1, max = 128))]
+    pub headline: String,
+    #[validate(range(min = 0))]
+    pub hourly_rate: i32,
+    #[serde(default)]
+    #[validate(length(max = 16), custom = "are_languages")]
+    pub languages: Vec<String>,
+    #[validate(length(max = 1024))]
+    pub education: Option<String>,
+    #[validate(length(max = 64), custom = "is_time_zone")]
+    pub time_zone: String,
+    #[serde(default)]
+    #[validate(length(max = 32))]
+    pub subject_ids: Vec<Uuid>,
+    #[serde(default)]
+    #[validate(length(max = 64))]
+    #[validate]
+    pub availability: Vec<AvailabilitySlotForm>,
+    #[serde(default)]
+    #[validate(length(max = 64))]
+    #[validate]
+    pub exceptions: Vec<AvailabilityExceptionForm>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, JsonSchema)]
+pub struct AvailabilitySlotForm {
+    #[validate(range(min = 0, max = 6))]
+    pub weekday: i16,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, JsonSchema)]
+pub struct AvailabilityExceptionForm {
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    #[validate(length(max = 256))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MentorsQuery {
+    pub subject: Option<String>,
+    pub available_from: Option<DateTime<Utc>>,
+    pub available_to: Option<DateTime<Utc>>,
+    #[validate(range(min = 0))]
+    pub max_rate: Option<i32>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MentorProfileBody {
+    #[serde(flatten)]
+    pub profile: MentorProfile,
+    pub subjects: Vec<Subject>,
+    pub availability: Vec<AvailabilitySlot>,
+    pub exceptions: Vec<AvailabilityException>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MentorSort {
+    #[default]
+    HourlyRate,
+    Username,
+    CreatedAt,
+}
+
+impl Sort for MentorSort {
+    type Item = MentorListing;
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::HourlyRate => "hourly_rate",
+            Self::Username => "username",
+            Self::CreatedAt => "created_at",
+        }
+    }
+
+    fn key(self, mentor: &MentorListing) -> (String, Uuid) {
+        let key = match self {
+            Self::HourlyRate => integer_key(mentor.hourly_rate.into()),
+            Self::Username => mentor.username.clone(),
+            Self::CreatedAt => timestamp_key(mentor.created_at),
+        };
+        (key, mentor.user_id)
+    }
+
+    fn key_kind(self) -> KeyKind {
+        match self {
+            Self::HourlyRate => KeyKind::Integer,
+            Self::Username => KeyKind::Text,
+            Self::CreatedAt => KeyKind::Timestamp,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::message::{Message, UnreadCount},
+    pagination::{timestamp_key, KeyKind, Sort, SortOrder},
+};
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct MessageForm {
+    #[validate(length(min = 1, max = 4096))]
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageSort {
+    #[default]
+    CreatedAt,
+}
+
+impl Sort for MessageSort {
+    type Item = Message;
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+        }
+    }
+
+    fn key(self, message: &Message) -> (String, Uuid) {
+        match self {
+            Self::CreatedAt => (timestamp_key(message.created_at), message.id),
+        }
+    }
+
+    fn key_kind(self) -> KeyKind {
+        match self {
+            Self::CreatedAt => KeyKind::Timestamp,
+        }
+    }
+
+    fn default_order(self) -> SortOrder {
+        SortOrder::Desc
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct UnreadMessages {
+    total: i64,
+    orders: Vec<UnreadCount>,
+}
+
+impl From<Vec<UnreadCount>> for UnreadMessages {
+    fn from(orders: Vec<UnreadCount>) -> Self {
+        Self {
+            total: orders.iter().map(|o| o.count).sum(),
+            orders,
+        }
+    }
+}
@@ -1,2 +1,15 @@
 pub mod auth;
+pub mod calendar;
+pub mod dispute;
+pub mod export;
+pub mod health;
+pub mod mentor_profile;
+pub mod message;
+pub mod notification;
+pub mod order;
+pub mod realtime;
+pub mod search;
+pub mod session;
+pub mod subject;
+pub mod upload;
 pub mod user;
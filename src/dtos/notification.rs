@@ -0,0 +1,60 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::notification::{Notification, NotificationKind},
+    pagination::{timestamp_key, KeyKind, Sort, SortOrder},
+};
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NotificationsQuery {
+    #[serde(default)]
+    pub unread: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationSort {
+    #[default]
+    CreatedAt,
+}
+
+impl Sort for NotificationSort {
+    type Item = Notification;
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+        }
+    }
+
+    fn key(self, notification: &Notification) -> (String, Uuid) {
+        match self {
+            Self::CreatedAt => (timestamp_key(notification.created_at), notification.id),
+        }
+    }
+
+    fn key_kind(self) -> KeyKind {
+        match self {
+            Self::CreatedAt => KeyKind::Timestamp,
+        }
+    }
+
+    fn default_order(self) -> SortOrder {
+        SortOrder::Desc
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct NotificationPreferenceForm {
+    pub kind: NotificationKind,
+    pub email: bool,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct EditNotificationPreferencesForm {
+    pub preferences: Vec<NotificationPreferenceForm>,
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::{
+        order::{Order, OrderStatus},
+        subject::Subject,
+    },
+    pagination::{integer_key, timestamp_key, KeyKind, Sort, SortOrder},
+    validators::is_lowercase_alphanumeric,
+};
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct CreateOrderForm {
+    #[validate(length(min = 4, max = 32), custom = "is_lowercase_alphanumeric")]
+    pub mentor: String,
+    #[validate(length(min = 1, max = 128))]
+    pub title: String,
+    #[validate(length(min = 1, max = 8192))]
+    pub description: String,
+    #[validate(range(min = 0))]
+    pub price: i32,
+    pub deadline: Option<DateTime<Utc>>,
+    #[validate(length(min = 2, max = 32))]
+    pub language: Option<String>,
+    #[serde(default)]
+    #[validate(length(max = 8))]
+    pub subject_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OrdersQuery {
+    pub subject: Option<String>,
+    pub status: Option<OrderStatus>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderSort {
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+    Price,
+}
+
+impl Sort for OrderSort {
+    type Item = Order;
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+            Self::Price => "price",
+        }
+    }
+
+    fn key(self, order: &Order) -> (String, Uuid) {
+        let key = match self {
+            Self::CreatedAt => timestamp_key(order.created_at),
+            Self::UpdatedAt => timestamp_key(order.updated_at),
+            Self::Price => integer_key(order.price.into()),
+        };
+        (key, order.id)
+    }
+
+    fn key_kind(self) -> KeyKind {
+        match self {
+            Self::CreatedAt | Self::UpdatedAt => KeyKind::Timestamp,
+            Self::Price => KeyKind::Integer,
+        }
+    }
+
+    fn default_order(self) -> SortOrder {
+        SortOrder::Desc
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct OrderBody {
+    #[serde(flatten)]
+    pub order: Order,
+    pub subjects: Vec<Subject>,
+}
@@ -0,0 +1,14 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::secret::Secret;
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ConnectQuery {
+    pub token: Option<Secret<String>>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+pub struct ResumeQuery {
+    pub last_event_id: Option<i64>,
+}
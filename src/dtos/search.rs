@@ -0,0 +1,74 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    models::search::SearchHit,
+    pagination::{float_key, KeyKind, Sort, SortOrder},
+};
+
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Order,
+    Mentor,
+}
+
+impl SearchKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Order => "order",
+            Self::Mentor => "mentor",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SearchQuery {
+    #[validate(length(min = 1, max = 256))]
+    pub q: String,
+    pub kind: Option<SearchKind>,
+    #[validate(length(min = 2, max = 32))]
+    pub lang: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct UsernameQuery {
+    #[validate(length(min = 1, max = 32))]
+    pub q: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSort {
+    #[default]
+    Rank,
+}
+
+impl Sort for SearchSort {
+    type Item = SearchHit;
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Rank => "rank",
+        }
+    }
+
+    fn key(self, hit: &SearchHit) -> (String, Uuid) {
+        match self {
+            Self::Rank => (float_key(hit.rank), hit.id),
+        }
+    }
+
+    fn key_kind(self) -> KeyKind {
+        match self {
+            Self::Rank => KeyKind::Float,
+        }
+    }
+
+    fn default_order(self) -> SortOrder {
+        SortOrder::Desc
+    }
+}
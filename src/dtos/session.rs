@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{models::session::Session, validators::is_time_zone};
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct SessionForm {
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct CancelSessionForm {
+    #[validate(length(max = 512))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct SessionsQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    #[validate(custom = "is_time_zone")]
+    pub time_zone: Option<String>,
+}
+
+impl SessionsQuery {
+    pub fn tz(&self) -> Option<Tz> {
+        self.time_zone.as_deref().and_then(|tz| tz.parse().ok())
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SessionBody {
+    #[serde(flatten)]
+    pub session: Session,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starts_at_local: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_at_local: Option<String>,
+}
+
+impl SessionBody {
+    pub fn new(session: Session, tz: Option<Tz>) -> Self {
+        let local = |at: DateTime<Utc>| tz.map(|tz| at.with_timezone(&tz).to_rfc3339());
+        Self {
+            starts_at_local: local(session.starts_at),
+            ends_at_local: local(session.ends_at),
+            session,
+        }
+    }
+}
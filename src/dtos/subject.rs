@@ -0,0 +1,48 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{models::subject::Subject, validators::is_slug};
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct SubjectForm {
+    pub parent_id: Option<Uuid>,
+    #[validate(length(min = 2, max = 64), custom = "is_slug")]
+    pub slug: String,
+    #[validate(length(min = 1, max = 128))]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct SubjectIdsForm {
+    #[validate(length(max = 32))]
+    pub subject_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SubjectNode {
+    id: Uuid,
+    slug: String,
+    name: String,
+    children: Vec<SubjectNode>,
+}
+
+impl SubjectNode {
+    pub fn tree(subjects: &[Subject]) -> Vec<Self> {
+        Self::children_of(subjects, None)
+    }
+
+    fn children_of(subjects: &[Subject], parent_id: Option<Uuid>) -> Vec<Self> {
+        subjects
+            .iter()
+            .filter(|subject| subject.parent_id == parent_id)
+            .map(|subject| Self {
+                id: subject.id,
+                slug: subject.slug.clone(),
+                name: subject.name.clone(),
+                children: Self::children_of(subjects, Some(subject.id)),
+            })
+            .collect()
+    }
+}
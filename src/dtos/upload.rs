@@ -0,0 +1,135 @@
+use std::fmt;
+
+use axum::extract::Multipart;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    error::{Error, Result},
+    models::upload::Upload,
+};
+
+const FILE_FIELD: &str = "file";
+const MAX_FILE_NAME_CHARS: usize = 255;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FileVariant {
+    #[default]
+    Original,
+    Thumbnail,
+}
+
+impl FileVariant {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Original => "original",
+            Self::Thumbnail => "thumbnail",
+        }
+    }
+}
+
+pub struct UploadForm {
+    pub file_name: String,
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+impl UploadForm {
+    // Reads the `file` part chunk by chunk so oversized uploads are rejected before they are
+    // buffered in full.
+    pub async fn read(mut multipart: Multipart, max_bytes: usize) -> Result<Self> {
+        while let Some(mut field) = multipart.next_field().await? {
+            if field.name() != Some(FILE_FIELD) {
+                continue;
+            }
+            let file_name = sanitize_file_name(field.file_name().unwrap_or_default());
+            let content_type = field
+                .content_type()
+                .unwrap_or("application/octet-stream")
+                .to_owned();
+            let mut bytes = Vec::new();
+            while let Some(chunk) = field.chunk().await? {
+                if bytes.len() + chunk.len() > max_bytes {
+                    return Err(Error::FileTooLarge(max_bytes));
+                }
+                bytes.extend_from_slice(&chunk);
+            }
+            return Ok(Self {
+                file_name,
+                content_type,
+                bytes,
+            });
+        }
+        Err(Error::MissingFile)
+    }
+}
+
+impl fmt::Debug for UploadForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadForm")
+            .field("file_name", &self.file_name)
+            .field("content_type", &self.content_type)
+            .field("size", &self.bytes.len())
+            .finish()
+    }
+}
+
+fn sanitize_file_name(file_name: &str) -> String {
+    let file_name: String = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| !c.is_control() && *c != '"')
+        .take(MAX_FILE_NAME_CHARS)
+        .collect();
+    if file_name.trim().is_empty() {
+        String::from("upload")
+    } else {
+        file_name
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct DownloadQuery {
+    #[serde(default)]
+    pub variant: FileVariant,
+    pub expires: i64,
+    #[validate(length(equal = 64))]
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct UploadBody {
+    pub id: Uuid,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub url: String,
+    pub thumbnail_url: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct Download {
+    pub upload: Upload,
+    pub variant: FileVariant,
+    pub bytes: Vec<u8>,
+}
+
+impl Download {
+    pub fn content_type(&self) -> &str {
+        match self.variant {
+            FileVariant::Original => &self.upload.content_type,
+            FileVariant::Thumbnail => "image/png",
+        }
+    }
+
+    pub fn content_disposition(&self) -> String {
+        format!("attachment; filename=\"{}\"", self.upload.file_name)
+    }
+}
@@ -1,9 +1,23 @@
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use crate::validators::is_lowercase_alphanumeric;
+use uuid::Uuid;
 
-#[derive(Debug, Deserialize, Validate)]
+use crate::{
+    models::{
+        privacy::{Audience, PrivacySettings, Visibility},
+        user::User,
+    },
+    pagination::{timestamp_key, KeyKind, Sort},
+    secret::Secret,
+    validators::{is_lowercase_alphanumeric, is_supported_language},
+};
+
+use super::{mentor_profile::MentorProfileBody, message::UnreadMessages};
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
 pub struct EditUserForm {
     #[validate(length(min = 4, max = 32), custom = "is_lowercase_alphanumeric")]
     pub username: String,
@@ -13,17 +27,207 @@ pub struct EditUserForm {
     pub age: Option<i32>,
     #[validate(length(max = 512))]
     pub about: Option<String>,
+    #[validate(custom = "is_supported_language")]
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UsersQuery {
+    pub subject: Option<String>,
+    pub verified: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Clone, Copy, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UserSort {
+    #[default]
+    CreatedAt,
+    Username,
+}
+
+impl Sort for UserSort {
+    type Item = User;
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "created_at",
+            Self::Username => "username",
+        }
+    }
+
+    fn key(self, user: &User) -> (String, Uuid) {
+        let key = match self {
+            Self::CreatedAt => timestamp_key(user.created_at),
+            Self::Username => user.username.clone(),
+        };
+        (key, user.id)
+    }
+
+    fn key_kind(self) -> KeyKind {
+        match self {
+            Self::CreatedAt => KeyKind::Timestamp,
+            Self::Username => KeyKind::Text,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
 pub struct EditUserEmailForm {
     #[validate(email)]
     pub email: String,
 }
 
-#[derive(Debug, Deserialize, Validate)]
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
 pub struct EditUserPasswordForm {
     #[validate(length(min = 8), must_match(other = "repeat_password"))]
-    pub password: String,
-    repeat_password: String,
+    pub password: Secret<String>,
+    repeat_password: Secret<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, JsonSchema)]
+pub struct PrivacyForm {
+    pub name: Visibility,
+    pub email: Visibility,
+    pub age: Visibility,
+    pub about: Visibility,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PrivacyBody {
+    pub name: String,
+    pub email: String,
+    pub age: String,
+    pub about: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<PrivacySettings> for PrivacyBody {
+    fn from(settings: PrivacySettings) -> Self {
+        Self {
+            name: settings.name,
+            email: settings.email,
+            age: settings.age,
+            about: settings.about,
+            updated_at: settings.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PublicProfile {
+    pub username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub about: Option<String>,
+    pub verified: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PublicProfile {
+    pub fn new(user: User, privacy: &PrivacySettings, audience: Audience) -> Self {
+        let show_name = audience.can_see(&privacy.name);
+        Self {
+            username: user.username,
+            first_name: user.first_name.filter(|_| show_name),
+            last_name: user.last_name.filter(|_| show_name),
+            email: audience.can_see(&privacy.email).then_some(user.email),
+            age: user.age.filter(|_| audience.can_see(&privacy.age)),
+            about: user.about.filter(|_| audience.can_see(&privacy.about)),
+            verified: user.verified,
+            created_at: user.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MyProfile {
+    pub username: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub email: String,
+    pub age: Option<i32>,
+    pub about: Option<String>,
+    pub language: Option<String>,
+    pub verified: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<User> for MyProfile {
+    fn from(user: User) -> Self {
+        Self {
+            username: user.username,
+            first_name: user.first_name,
+            last_name: user.last_name,
+            email: user.email,
+            age: user.age,
+            about: user.about,
+            language: user.language,
+            verified: user.verified,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AdminUserBody {
+    pub id: Uuid,
+    pub username: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub email: String,
+    pub age: Option<i32>,
+    pub about: Option<String>,
+    pub language: Option<String>,
+    pub verified: bool,
+    pub is_admin: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub purged_at: Option<DateTime<Utc>>,
+}
+
+impl From<User> for AdminUserBody {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            first_name: user.first_name,
+            last_name: user.last_name,
+            email: user.email,
+            age: user.age,
+            about: user.about,
+            language: user.language,
+            verified: user.verified,
+            is_admin: user.is_admin,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            deleted_at: user.deleted_at,
+            purged_at: user.purged_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MeBody {
+    #[serde(flatten)]
+    pub profile: MyProfile,
+    pub privacy: PrivacyBody,
+    pub unread_messages: UnreadMessages,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct UserBody {
+    #[serde(flatten)]
+    pub profile: PublicProfile,
+    pub mentor_profile: Option<MentorProfileBody>,
 }
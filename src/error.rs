@@ -1,6 +1,35 @@
-use axum::{http::StatusCode, Json};
-use serde_json::{json, Value};
+use std::borrow::Cow;
+
+use axum::{
+    extract::rejection::{TypedHeaderRejection, TypedHeaderRejectionReason},
+    http::{
+        header::{AUTHORIZATION, CONTENT_LANGUAGE, CONTENT_TYPE, WWW_AUTHENTICATE},
+        HeaderValue, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use jsonwebtoken::errors::ErrorKind;
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use sqlx::{error::DatabaseError, postgres::PgDatabaseError};
 use thiserror::Error;
+use tracing::error;
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use crate::{
+    correlation,
+    i18n::{self, Locale},
+};
+
+const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+const REALM: &str = "s4s";
+const UNIQUE_VIOLATION: &str = "23505";
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+const CHECK_VIOLATION: &str = "23514";
+const SERIALIZATION_FAILURE: &str = "40001";
+const DEADLOCK_DETECTED: &str = "40P01";
 
 #[derive(Debug, Error)]
 pub enum Error {
@@ -9,41 +38,525 @@ pub enum Error {
     #[error("The requested resource was not found.")]
     NotFound(sqlx::Error),
     #[error(transparent)]
-    Jwt(#[from] jsonwebtoken::errors::Error),
+    Jwt(jsonwebtoken::errors::Error),
     #[error(transparent)]
     Argon2(#[from] argon2::password_hash::Error),
     #[error(transparent)]
     AxumJson(#[from] axum::extract::rejection::JsonRejection),
     #[error(transparent)]
-    AxumTypedHeader(#[from] axum::extract::rejection::TypedHeaderRejection),
+    AxumQuery(#[from] axum::extract::rejection::QueryRejection),
     #[error(transparent)]
+    AxumTypedHeader(axum::extract::rejection::TypedHeaderRejection),
+    #[error("The request failed validation.")]
     Validation(#[from] validator::ValidationErrors),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    WebSocket(#[from] axum::Error),
+    #[error(transparent)]
+    AxumMultipart(#[from] axum::extract::multipart::MultipartRejection),
+    #[error(transparent)]
+    MultipartField(#[from] axum::extract::multipart::MultipartError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+    #[error("The {0} is already taken.")]
+    UniqueViolation(String),
+    #[error("The request refers to a resource that does not exist or is still in use.")]
+    ForeignKeyViolation,
+    #[error("The request violates a data constraint.")]
+    CheckViolation,
+    #[error("The request conflicted with a concurrent one. Please try again.")]
+    SerializationFailure,
     #[error("Wrong credentials.")]
     WrongCredentials,
+    #[error("Missing access token.")]
+    MissingToken,
+    #[error("The access token is malformed or has an invalid signature.")]
+    InvalidToken,
+    #[error("The access token has expired.")]
+    ExpiredToken,
+    #[error("You are not allowed to access this resource.")]
+    Forbidden,
+    #[error("The message can no longer be modified.")]
+    MessageLocked,
+    #[error("The order cannot be disputed in its current state.")]
+    OrderNotDisputable,
+    #[error("The dispute has already been resolved.")]
+    DisputeResolved,
+    #[error("The split amount must be between zero and the order price.")]
+    InvalidSplit,
+    #[error("One or more subjects do not exist.")]
+    UnknownSubject,
+    #[error("A subject cannot be nested under itself or its descendants.")]
+    SubjectCycle,
+    #[error("The subject still has nested subjects.")]
+    SubjectHasChildren,
+    #[error("You cannot place an order with yourself.")]
+    SelfOrder,
+    #[error("The start of a time range must be before its end.")]
+    InvalidTimeRange,
+    #[error("An availability window needs both a start and an end.")]
+    IncompleteTimeRange,
+    #[error("The session duration is outside of the allowed range.")]
+    InvalidSessionDuration,
+    #[error("Sessions cannot be scheduled in the past.")]
+    SessionInPast,
+    #[error("Sessions can only be booked for orders in progress.")]
+    OrderNotBookable,
+    #[error("The mentor is not available at the requested time.")]
+    MentorUnavailable,
+    #[error("The requested time overlaps another session.")]
+    SessionConflict,
+    #[error("The session has already been cancelled.")]
+    SessionCancelled,
+    #[error("Sessions cannot be changed while the order is disputed.")]
+    OrderDisputed,
+    #[error("The deadline must be in the future.")]
+    DeadlineInPast,
+    #[error("The pagination cursor is invalid.")]
+    InvalidCursor,
+    #[error("Sessions can only be changed at least {0} hours before they start.")]
+    NoticePeriod(i64),
+    #[error("The request does not contain a file.")]
+    MissingFile,
+    #[error("Files must not be larger than {0} bytes.")]
+    FileTooLarge(usize),
+    #[error("Files of type {0} are not accepted here.")]
+    UnsupportedContentType(String),
+    #[error("The file is not a valid image.")]
+    InvalidImage,
+    #[error("The file content does not match its content type.")]
+    ContentMismatch,
+    #[error("The file was rejected by the virus scanner.")]
+    InfectedFile,
+    #[error("The virus scanner failed with exit code {0:?}.")]
+    ScanFailed(Option<i32>),
+    #[error("The download link is invalid or has expired.")]
+    InvalidDownloadLink,
+    #[error("The file storage endpoint is invalid.")]
+    InvalidStorageEndpoint,
+    #[error("The account has been deleted.")]
+    AccountDeleted,
+    #[error("The account is not pending deletion.")]
+    AccountNotDeleted,
+    #[error("The account can no longer be restored.")]
+    RestorePeriodExpired,
+    #[error("Too many data exports have been requested recently.")]
+    TooManyExports,
+    #[error("The data export could not be built. Please request a new one.")]
+    ExportFailed,
+    #[error("The data export has expired.")]
+    ExportExpired,
+    #[error("The file storage responded with {0}.")]
+    FileStorage(reqwest::StatusCode),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
-pub type ApiError = (StatusCode, Json<Value>);
 pub type ApiResult<T> = std::result::Result<T, ApiError>;
 
+impl Error {
+    fn status(&self) -> StatusCode {
+        match self {
+            Self::Validation(_)
+            | Self::AxumJson(_)
+            | Self::AxumQuery(_)
+            | Self::InvalidSplit
+            | Self::UnknownSubject
+            | Self::SubjectCycle
+            | Self::SelfOrder
+            | Self::InvalidTimeRange
+            | Self::IncompleteTimeRange
+            | Self::InvalidSessionDuration
+            | Self::SessionInPast
+            | Self::DeadlineInPast
+            | Self::InvalidCursor
+            | Self::AxumMultipart(_)
+            | Self::MultipartField(_)
+            | Self::MissingFile
+            | Self::InvalidImage
+            | Self::ContentMismatch
+            | Self::CheckViolation
+            | Self::AxumTypedHeader(_) => StatusCode::BAD_REQUEST,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::WrongCredentials
+            | Self::MissingToken
+            | Self::InvalidToken
+            | Self::ExpiredToken => StatusCode::UNAUTHORIZED,
+            Self::Forbidden
+            | Self::MessageLocked
+            | Self::InvalidDownloadLink
+            | Self::AccountDeleted => StatusCode::FORBIDDEN,
+            Self::OrderNotDisputable
+            | Self::DisputeResolved
+            | Self::SubjectHasChildren
+            | Self::OrderNotBookable
+            | Self::MentorUnavailable
+            | Self::SessionConflict
+            | Self::SessionCancelled
+            | Self::OrderDisputed
+            | Self::NoticePeriod(_)
+            | Self::AccountNotDeleted
+            | Self::ExportFailed
+            | Self::UniqueViolation(_)
+            | Self::ForeignKeyViolation
+            | Self::SerializationFailure => StatusCode::CONFLICT,
+            Self::RestorePeriodExpired | Self::ExportExpired => StatusCode::GONE,
+            Self::TooManyExports => StatusCode::TOO_MANY_REQUESTS,
+            Self::FileTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::UnsupportedContentType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::InfectedFile => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// A stable identifier clients can match on instead of the human readable detail.
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Sqlx(_)
+            | Self::Jwt(_)
+            | Self::Argon2(_)
+            | Self::SerdeJson(_)
+            | Self::WebSocket(_)
+            | Self::Io(_)
+            | Self::Reqwest(_)
+            | Self::Image(_)
+            | Self::Zip(_)
+            | Self::InvalidStorageEndpoint
+            | Self::FileStorage(_) => "internal_error",
+            Self::NotFound(_) => "not_found",
+            Self::AxumJson(_) => "malformed_json",
+            Self::AxumQuery(_) => "malformed_query",
+            Self::AxumTypedHeader(_) => "malformed_header",
+            Self::Validation(_) => "validation_failed",
+            Self::AxumMultipart(_) | Self::MultipartField(_) => "malformed_multipart",
+            Self::UniqueViolation(_) => "unique_violation",
+            Self::ForeignKeyViolation => "foreign_key_violation",
+            Self::CheckViolation => "check_violation",
+            Self::SerializationFailure => "serialization_failure",
+            Self::WrongCredentials => "wrong_credentials",
+            Self::MissingToken => "missing_token",
+            Self::InvalidToken => "invalid_token",
+            Self::ExpiredToken => "token_expired",
+            Self::Forbidden => "forbidden",
+            Self::MessageLocked => "message_locked",
+            Self::OrderNotDisputable => "order_not_disputable",
+            Self::DisputeResolved => "dispute_resolved",
+            Self::InvalidSplit => "invalid_split",
+            Self::UnknownSubject => "unknown_subject",
+            Self::SubjectCycle => "subject_cycle",
+            Self::SubjectHasChildren => "subject_has_children",
+            Self::SelfOrder => "self_order",
+            Self::InvalidTimeRange => "invalid_time_range",
+            Self::IncompleteTimeRange => "incomplete_time_range",
+            Self::InvalidSessionDuration => "invalid_session_duration",
+            Self::SessionInPast => "session_in_past",
+            Self::OrderNotBookable => "order_not_bookable",
+            Self::MentorUnavailable => "mentor_unavailable",
+            Self::SessionConflict => "session_conflict",
+            Self::SessionCancelled => "session_cancelled",
+            Self::OrderDisputed => "order_disputed",
+            Self::DeadlineInPast => "deadline_in_past",
+            Self::InvalidCursor => "invalid_cursor",
+            Self::NoticePeriod(_) => "notice_period",
+            Self::MissingFile => "missing_file",
+            Self::FileTooLarge(_) => "file_too_large",
+            Self::UnsupportedContentType(_) => "unsupported_content_type",
+            Self::InvalidImage => "invalid_image",
+            Self::ContentMismatch => "content_mismatch",
+            Self::InfectedFile => "infected_file",
+            Self::ScanFailed(_) => "scan_failed",
+            Self::InvalidDownloadLink => "invalid_download_link",
+            Self::AccountDeleted => "account_deleted",
+            Self::AccountNotDeleted => "account_not_deleted",
+            Self::RestorePeriodExpired => "restore_period_expired",
+            Self::TooManyExports => "too_many_exports",
+            Self::ExportFailed => "export_failed",
+            Self::ExportExpired => "export_expired",
+        }
+    }
+
+    /// Values filled into the placeholders of the localised detail.
+    fn args(&self) -> Vec<(&'static str, String)> {
+        match self {
+            Self::AxumJson(err) => vec![("reason", err.body_text())],
+            Self::AxumQuery(err) => vec![("reason", err.body_text())],
+            Self::AxumTypedHeader(err) => vec![("reason", err.to_string())],
+            Self::AxumMultipart(err) => vec![("reason", err.body_text())],
+            Self::MultipartField(err) => vec![("reason", err.to_string())],
+            Self::UniqueViolation(field) => vec![("field", field.clone())],
+            Self::NoticePeriod(hours) => vec![("hours", hours.to_string())],
+            Self::FileTooLarge(limit) => vec![("limit", limit.to_string())],
+            Self::UnsupportedContentType(content_type) => {
+                vec![("content_type", content_type.clone())]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// The `WWW-Authenticate` challenge sent along with a 401, as described in RFC 6750.
+    fn challenge(&self) -> Option<String> {
+        match self {
+            Self::WrongCredentials | Self::MissingToken => {
+                Some(format!("Bearer realm=\"{REALM}\""))
+            }
+            Self::InvalidToken | Self::ExpiredToken => Some(format!(
+                "Bearer realm=\"{REALM}\", error=\"invalid_token\", error_description=\"{self}\""
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// An error rendered as an RFC 7807 problem document.
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    code: &'static str,
+    detail: String,
+    locale: Locale,
+    errors: Vec<FieldError>,
+    challenge: Option<String>,
+    internal: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+struct FieldError {
+    field: String,
+    code: String,
+    message: Option<String>,
+    params: Map<String, Value>,
+}
+
+#[derive(Serialize, JsonSchema)]
+pub(crate) struct Problem<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    title: &'a str,
+    status: u16,
+    detail: &'a str,
+    code: &'static str,
+    correlation_id: Option<String>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    #[schemars(default)]
+    errors: &'a [FieldError],
+}
+
 impl From<Error> for ApiError {
     fn from(err: Error) -> Self {
-        let status = match err {
-            Error::Validation(_) | Error::AxumJson(_) => StatusCode::BAD_REQUEST,
-            Error::NotFound(_) => StatusCode::NOT_FOUND,
-            Error::WrongCredentials => StatusCode::UNAUTHORIZED,
-            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        let status = err.status();
+        let code = err.code();
+        let locale = i18n::current();
+        // Internal details are only of use to us and may leak queries or file paths.
+        let (detail, internal) = if status.is_server_error() {
+            (
+                i18n::translate(locale, "error.internal_error", &[]),
+                Some(format!("{err:?}")),
+            )
+        } else {
+            (
+                i18n::translate(locale, &format!("error.{code}"), &err.args()),
+                None,
+            )
+        };
+        let errors = match &err {
+            Error::Validation(errors) => FieldError::collect(errors, locale),
+            Error::UniqueViolation(field) => vec![FieldError {
+                field: field.clone(),
+                code: String::from("unique"),
+                message: i18n::translate(locale, "validation.unique", &[]),
+                params: Map::new(),
+            }],
+            _ => Vec::new(),
+        };
+        Self {
+            status,
+            code,
+            detail: detail.unwrap_or_else(|| err.to_string()),
+            locale,
+            errors,
+            challenge: err.challenge(),
+            internal,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        if let Some(internal) = &self.internal {
+            error!(code = self.code, "{internal}");
+        }
+        let problem = Problem {
+            kind: "about:blank",
+            title: self.status.canonical_reason().unwrap_or_default(),
+            status: self.status.as_u16(),
+            detail: &self.detail,
+            code: self.code,
+            correlation_id: correlation::current(),
+            errors: &self.errors,
+        };
+        let mut response = (self.status, Json(problem)).into_response();
+        let headers = response.headers_mut();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(PROBLEM_CONTENT_TYPE));
+        headers.insert(
+            CONTENT_LANGUAGE,
+            HeaderValue::from_static(self.locale.as_str()),
+        );
+        if let Some(challenge) = self.challenge.and_then(|c| HeaderValue::from_str(&c).ok()) {
+            headers.insert(WWW_AUTHENTICATE, challenge);
+        }
+        response
+    }
+}
+
+impl FieldError {
+    fn collect(errors: &ValidationErrors, locale: Locale) -> Vec<Self> {
+        let mut fields = Vec::new();
+        Self::flatten(errors, "", locale, &mut fields);
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        fields
+    }
+
+    fn flatten(errors: &ValidationErrors, prefix: &str, locale: Locale, fields: &mut Vec<Self>) {
+        for (field, kind) in errors.errors() {
+            let path = if prefix.is_empty() {
+                (*field).to_owned()
+            } else {
+                format!("{prefix}.{field}")
+            };
+            match kind {
+                ValidationErrorsKind::Field(errors) => {
+                    fields.extend(errors.iter().map(|error| {
+                        Self {
+                            field: path.clone(),
+                            code: error.code.to_string(),
+                            message: error
+                                .message
+                                .as_ref()
+                                .map(Cow::to_string)
+                                .or_else(|| Self::localise(error, locale)),
+                            // Rejected values are left out as they may be passwords.
+                            params: error
+                                .params
+                                .iter()
+                                .filter(|(name, _)| !matches!(name.as_ref(), "value" | "other"))
+                                .map(|(name, value)| (name.to_string(), value.clone()))
+                                .collect(),
+                        }
+                    }));
+                }
+                ValidationErrorsKind::Struct(errors) => {
+                    Self::flatten(errors, &path, locale, fields);
+                }
+                ValidationErrorsKind::List(items) => {
+                    for (index, errors) in items {
+                        Self::flatten(errors, &format!("{path}[{index}]"), locale, fields);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Length and range messages depend on which bounds the rule sets.
+    fn localise(error: &ValidationError, locale: Locale) -> Option<String> {
+        let bound = |name: &str| error.params.contains_key(name);
+        let key = match error.code.as_ref() {
+            code @ ("length" | "range") => {
+                let variant = if bound("equal") {
+                    "equal"
+                } else if bound("min") && bound("max") {
+                    "between"
+                } else if bound("min") {
+                    "min"
+                } else {
+                    "max"
+                };
+                format!("validation.{code}.{variant}")
+            }
+            code => format!("validation.{code}"),
         };
-        let payload = json!({"error": {"message": err.to_string()}});
-        (status, Json(payload))
+        let args = error
+            .params
+            .iter()
+            .map(|(name, value)| {
+                let value = value
+                    .as_str()
+                    .map_or_else(|| value.to_string(), ToOwned::to_owned);
+                (name.as_ref(), value)
+            })
+            .collect::<Vec<_>>();
+        i18n::translate(locale, &key, &args)
+            .or_else(|| i18n::translate(locale, "validation.invalid", &[]))
     }
 }
 
 impl From<sqlx::Error> for Error {
     fn from(err: sqlx::Error) -> Self {
-        match err {
-            sqlx::Error::RowNotFound => Self::NotFound(err),
+        let Some(db_err) = err.as_database_error() else {
+            return match err {
+                sqlx::Error::RowNotFound => Self::NotFound(err),
+                _ => Self::Sqlx(err),
+            };
+        };
+        match db_err.code().as_deref() {
+            Some(UNIQUE_VIOLATION) => Self::UniqueViolation(conflicting_field(db_err)),
+            Some(FOREIGN_KEY_VIOLATION) => Self::ForeignKeyViolation,
+            Some(CHECK_VIOLATION) => Self::CheckViolation,
+            Some(SERIALIZATION_FAILURE | DEADLOCK_DETECTED) => Self::SerializationFailure,
             _ => Self::Sqlx(err),
         }
     }
 }
+
+/// Reads the column list out of a detail like `Key (username)=(ada) already exists.`,
+/// falling back to the constraint name. The conflicting value itself is never exposed.
+fn conflicting_field(db_err: &dyn DatabaseError) -> String {
+    db_err
+        .try_downcast_ref::<PgDatabaseError>()
+        .and_then(PgDatabaseError::detail)
+        .and_then(|detail| detail.strip_prefix("Key ("))
+        .and_then(|detail| detail.split_once(")="))
+        .map(|(columns, _)| columns)
+        .or_else(|| db_err.constraint())
+        .unwrap_or("value")
+        .to_owned()
+}
+
+impl From<TypedHeaderRejection> for Error {
+    fn from(rejection: TypedHeaderRejection) -> Self {
+        if rejection.name() != AUTHORIZATION {
+            return Self::AxumTypedHeader(rejection);
+        }
+        match rejection.reason() {
+            TypedHeaderRejectionReason::Missing => Self::MissingToken,
+            _ => Self::InvalidToken,
+        }
+    }
+}
+
+impl From<jsonwebtoken::errors::Error> for Error {
+    fn from(err: jsonwebtoken::errors::Error) -> Self {
+        match err.kind() {
+            ErrorKind::ExpiredSignature => Self::ExpiredToken,
+            // Anything wrong with the token the client sent.
+            ErrorKind::InvalidToken
+            | ErrorKind::InvalidSignature
+            | ErrorKind::InvalidAlgorithm
+            | ErrorKind::ImmatureSignature
+            | ErrorKind::MissingRequiredClaim(_)
+            | ErrorKind::InvalidIssuer
+            | ErrorKind::InvalidAudience
+            | ErrorKind::InvalidSubject
+            | ErrorKind::Base64(_)
+            | ErrorKind::Json(_)
+            | ErrorKind::Utf8(_) => Self::InvalidToken,
+            // Keys, algorithms and crypto are set up by us, so these are server errors.
+            _ => Self::Jwt(err),
+        }
+    }
+}
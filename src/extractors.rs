@@ -1,8 +1,11 @@
 use axum::{
     async_trait,
-    extract::{rejection::JsonRejection, FromRef, FromRequest, FromRequestParts},
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        FromRef, FromRequest, FromRequestParts, OriginalUri, Query,
+    },
     headers::{authorization::Bearer, Authorization},
-    http::{request::Parts, Request},
+    http::{request::Parts, Request, Uri},
     Json, RequestExt, RequestPartsExt, TypedHeader,
 };
 use uuid::Uuid;
@@ -10,8 +13,11 @@ use validator::Validate;
 
 use crate::{
     auth::jwt::Claims,
+    dtos::realtime::ResumeQuery,
     error::{ApiError, Error},
+    i18n,
     models::user::User,
+    pagination::{is_page_param, PageQuery, Pagination, Sort},
     storage::{user, DbPool},
 };
 
@@ -20,7 +26,17 @@ pub struct LoggedInUser(pub User);
 #[derive(Debug)]
 pub struct LoggedInUserId(pub Uuid);
 #[derive(Debug)]
+pub struct AdminUser(pub User);
+#[derive(Debug)]
 pub struct ValidatedJson<T>(pub T);
+#[derive(Debug)]
+pub struct ValidatedQuery<T>(pub T);
+/// The filters of a paginated endpoint. The page parameters are left to `Pagination`; with
+/// `#[serde(deny_unknown_fields)]` on `T`, anything else it does not know is a 400.
+#[derive(Debug)]
+pub struct PageFilter<T>(pub T);
+#[derive(Debug)]
+pub struct LastEventId(pub i64);
 
 #[async_trait]
 impl<S> FromRequestParts<S> for LoggedInUser
@@ -40,23 +56,55 @@ where
         let user = user::get_by_id(&pool, claims.sub())
             .await
             .map_err(Error::from)?;
+        if user.deleted_at.is_some() {
+            return Err(Error::AccountDeleted.into());
+        }
+        if let Some(locale) = user.language.as_deref().and_then(|l| l.parse().ok()) {
+            i18n::prefer(locale);
+        }
         Ok(LoggedInUser(user))
     }
 }
 
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminUser
+where
+    DbPool: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let LoggedInUser(user) = LoggedInUser::from_request_parts(parts, state).await?;
+        if user.is_admin {
+            Ok(AdminUser(user))
+        } else {
+            Err(Error::Forbidden.into())
+        }
+    }
+}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for LoggedInUserId
 where
+    DbPool: FromRef<S>,
     S: Send + Sync,
 {
     type Rejection = ApiError;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
         let TypedHeader(Authorization(bearer)) = parts
             .extract::<TypedHeader<Authorization<Bearer>>>()
             .await
             .map_err(Error::from)?;
+        let pool = DbPool::from_ref(state);
         let claims = Claims::verify(bearer.token())?;
+        if !user::is_active(&pool, claims.sub())
+            .await
+            .map_err(Error::from)?
+        {
+            return Err(Error::AccountDeleted.into());
+        }
         Ok(LoggedInUserId(claims.sub()))
     }
 }
@@ -77,3 +125,93 @@ where
         Ok(Self(form))
     }
 }
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for ValidatedQuery<T>
+where
+    S: Send + Sync,
+    T: Validate + 'static,
+    Query<T>: FromRequestParts<(), Rejection = QueryRejection>,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Query(query) = parts.extract::<Query<T>>().await.map_err(Error::from)?;
+        query.validate().map_err(Error::from)?;
+        Ok(Self(query))
+    }
+}
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for PageFilter<T>
+where
+    S: Send + Sync,
+    T: Validate + 'static,
+    Query<T>: FromRequestParts<(), Rejection = QueryRejection>,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let filters = parts
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|param| !param.is_empty() && !is_page_param(param))
+            .collect::<Vec<_>>()
+            .join("&");
+        // Dropping whole parameters from a valid query leaves a valid one.
+        let filtered = Uri::builder()
+            .path_and_query(format!("{}?{filters}", parts.uri.path()))
+            .build()
+            .unwrap_or_else(|_| parts.uri.clone());
+        let uri = std::mem::replace(&mut parts.uri, filtered);
+        let query = parts.extract::<Query<T>>().await;
+        parts.uri = uri;
+        let Query(query) = query.map_err(Error::from)?;
+        query.validate().map_err(Error::from)?;
+        Ok(Self(query))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for LastEventId
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let header = parts
+            .headers
+            .get("last-event-id")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok());
+        let Query(query) = parts
+            .extract::<Query<ResumeQuery>>()
+            .await
+            .map_err(Error::from)?;
+        Ok(Self(header.or(query.last_event_id).unwrap_or_default()))
+    }
+}
+
+#[async_trait]
+impl<St, S> FromRequestParts<St> for Pagination<S>
+where
+    St: Send + Sync,
+    S: Sort + 'static,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &St) -> Result<Self, Self::Rejection> {
+        let Query(query) = parts
+            .extract::<Query<PageQuery<S>>>()
+            .await
+            .map_err(Error::from)?;
+        let uri = parts
+            .extensions
+            .get::<OriginalUri>()
+            .map_or_else(|| parts.uri.clone(), |OriginalUri(uri)| uri.clone());
+        Ok(Self::new(query, uri)?)
+    }
+}
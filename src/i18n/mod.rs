@@ -0,0 +1,117 @@
+use std::{cell::Cell, collections::HashMap, str::FromStr};
+
+use axum::{
+    http::{header::ACCEPT_LANGUAGE, Request},
+    middleware::Next,
+    response::Response,
+};
+use once_cell::sync::Lazy;
+use tracing::warn;
+
+type Catalog = HashMap<String, String>;
+
+static CATALOGS: Lazy<HashMap<Locale, Catalog>> = Lazy::new(|| {
+    Locale::ALL
+        .into_iter()
+        .map(|locale| {
+            let catalog = serde_json::from_str(locale.source())
+                .unwrap_or_else(|err| panic!("Invalid {} catalog: {err}", locale.as_str()));
+            (locale, catalog)
+        })
+        .collect()
+});
+
+tokio::task_local! {
+    static LOCALE: Cell<Locale>;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+    Es,
+}
+
+impl Locale {
+    pub const ALL: [Self; 3] = [Self::En, Self::De, Self::Es];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::De => "de",
+            Self::Es => "es",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Self::En => include_str!("en.json"),
+            Self::De => include_str!("de.json"),
+            Self::Es => include_str!("es.json"),
+        }
+    }
+
+    /// Picks the supported language the client weighs highest in an `Accept-Language` header.
+    /// Regional tags such as `de-AT` match their primary language.
+    pub fn negotiate(header: &str) -> Option<Self> {
+        let mut ranges = header
+            .split(',')
+            .enumerate()
+            .filter_map(|(position, range)| {
+                let mut parts = range.split(';').map(str::trim);
+                let tag = parts.next()?;
+                let quality = parts
+                    .find_map(|param| param.strip_prefix("q="))
+                    .map_or(Some(1.0), |q| q.parse::<f32>().ok())?;
+                let locale = tag.split('-').next()?.to_ascii_lowercase().parse().ok()?;
+                (quality > 0.0).then_some((quality, position, locale))
+            })
+            .collect::<Vec<_>>();
+        ranges.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        ranges.first().map(|&(_, _, locale)| locale)
+    }
+}
+
+impl FromStr for Locale {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|locale| locale.as_str() == s)
+            .ok_or(())
+    }
+}
+
+/// Scopes the request to the language negotiated from its `Accept-Language` header.
+pub async fn negotiate<B>(request: Request<B>, next: Next<B>) -> Response {
+    let locale = request
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(Locale::negotiate)
+        .unwrap_or_default();
+    LOCALE.scope(Cell::new(locale), next.run(request)).await
+}
+
+pub fn current() -> Locale {
+    LOCALE.try_with(Cell::get).unwrap_or_default()
+}
+
+/// Overrides the negotiated language for the rest of the request, e.g. with a stored preference.
+pub fn prefer(locale: Locale) {
+    let _ = LOCALE.try_with(|current| current.set(locale));
+}
+
+/// Looks `key` up in the catalog of `locale`, falling back to English, and fills in the
+/// `{name}` placeholders from `args`.
+pub fn translate(locale: Locale, key: &str, args: &[(&str, String)]) -> Option<String> {
+    let message = CATALOGS[&locale].get(key).or_else(|| {
+        warn!("Missing {} translation for {key}", locale.as_str());
+        CATALOGS[&Locale::En].get(key)
+    })?;
+    Some(args.iter().fold(message.clone(), |message, (name, value)| {
+        message.replace(&format!("{{{name}}}"), value)
+    }))
+}
@@ -1,13 +1,23 @@
 #![warn(clippy::pedantic)]
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
 
 mod auth;
+mod blob;
 pub mod config;
+mod correlation;
 mod dtos;
 mod error;
 mod extractors;
+mod i18n;
+mod metrics;
 mod models;
+mod openapi;
+mod pagination;
 mod routes;
+mod secret;
 mod services;
+pub mod state;
 mod storage;
 pub mod telemetry;
 mod validators;
+mod versioning;
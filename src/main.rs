@@ -3,20 +3,25 @@
 use std::time::Duration;
 
 use s4s::{
-    config::{routes::routes, Config},
+    config::{
+        routes::{admin, routes},
+        Config,
+    },
+    state::AppState,
     telemetry::Telemetry,
 };
 use sqlx::postgres::PgPoolOptions;
 use tokio::signal;
+use tracing::info;
 
 #[tokio::main]
 async fn main() {
     dotenvy::dotenv().expect("Failed to load .env!");
 
-    Telemetry::initialize();
-
     let config = Config::new().expect("Failed to read configuration!");
 
+    Telemetry::initialize(&config.telemetry);
+
     let pool = PgPoolOptions::new()
         .acquire_timeout(Duration::from_secs(5))
         .connect(&config.storage.connection_string())
@@ -28,16 +33,38 @@ async fn main() {
         .await
         .expect("Failed to run migrations!");
 
-    let app = routes().with_state(pool);
+    let state = AppState::new(pool)
+        .with_session_policy(config.sessions)
+        .with_upload_config(config.uploads)
+        .with_account_policy(config.accounts)
+        .with_export_policy(config.exports)
+        .with_versioning_policy(config.versioning)
+        .with_health_policy(config.health);
+    state.spawn_account_purge();
+    state.spawn_export_cleanup();
+    let app = routes(state.clone());
+
+    let admin_server = axum::Server::bind(
+        &config
+            .metrics
+            .address()
+            .expect("Failed to parse admin address!"),
+    )
+    .serve(admin(state.clone()).into_make_service());
+    tokio::spawn(async {
+        admin_server.await.expect("Admin server failed!");
+    });
 
     axum::Server::bind(&config.app.address().expect("Failed to parse address!"))
         .serve(app.into_make_service())
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(shutdown_signal(state))
         .await
         .unwrap();
+
+    Telemetry::shutdown();
 }
 
-async fn shutdown_signal() {
+async fn shutdown_signal(state: AppState) {
     let ctrl_c = async {
         signal::ctrl_c()
             .await
@@ -56,9 +83,11 @@ async fn shutdown_signal() {
     let terminate = std::future::pending::<()>();
 
     tokio::select! {
-        _ = ctrl_c => {},
-        _ = terminate => {},
+        () = ctrl_c => {},
+        () = terminate => {},
     }
 
-    println!("signal received, starting graceful shutdown");
+    info!("signal received, draining before graceful shutdown");
+    state.drain().await;
+    info!("starting graceful shutdown");
 }
@@ -0,0 +1,200 @@
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::MatchedPath,
+    http::{Method, Request},
+    middleware::Next,
+    response::Response,
+};
+use once_cell::sync::Lazy;
+use prometheus::{
+    core::Collector, Encoder, Gauge, HistogramOpts, HistogramVec, IntCounter, IntCounterVec,
+    IntGauge, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+use crate::storage::DbPool;
+
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+const UNMATCHED: &str = "unmatched";
+const ACQUIRE_TIMEOUT: Duration = Duration::from_secs(1);
+
+static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+static HTTP_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests served."),
+            &["method", "route", "status"],
+        )
+        .unwrap(),
+    )
+});
+static HTTP_DURATION: Lazy<HistogramVec> = Lazy::new(|| {
+    register(
+        HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Time spent serving HTTP requests.",
+            ),
+            &["method", "route", "status"],
+        )
+        .unwrap(),
+    )
+});
+static HTTP_IN_FLIGHT: Lazy<IntGauge> = Lazy::new(|| {
+    register(
+        IntGauge::new(
+            "http_requests_in_flight",
+            "HTTP requests currently being served.",
+        )
+        .unwrap(),
+    )
+});
+static POOL_CONNECTIONS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register(
+        IntGaugeVec::new(
+            Opts::new("db_pool_connections", "Database connections by state."),
+            &["state"],
+        )
+        .unwrap(),
+    )
+});
+// sqlx has no hook around `acquire`, so this samples one acquire per scrape rather than
+// timing the ones handlers make.
+static POOL_PROBE: Lazy<Gauge> = Lazy::new(|| {
+    register(
+        Gauge::new(
+            "db_pool_probe_acquire_seconds",
+            "Time the last scrape waited for a database connection, infinite if it timed out.",
+        )
+        .unwrap(),
+    )
+});
+static LOGINS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register(
+        IntCounterVec::new(
+            Opts::new("logins_total", "Login attempts by outcome."),
+            &["outcome"],
+        )
+        .unwrap(),
+    )
+});
+static SIGNUPS: Lazy<IntCounter> =
+    Lazy::new(|| register(IntCounter::new("signups_total", "Accounts created.").unwrap()));
+static ORDERS_CREATED: Lazy<IntCounter> =
+    Lazy::new(|| register(IntCounter::new("orders_created_total", "Orders created.").unwrap()));
+
+fn register<T: Collector + Clone + 'static>(metric: T) -> T {
+    REGISTRY
+        .register(Box::new(metric.clone()))
+        .expect("metric names are unique");
+    metric
+}
+
+/// Counts and times every request by the route template it matched, so that paths with
+/// ids in them don't each get their own series.
+pub async fn track<B>(request: Request<B>, next: Next<B>) -> Response {
+    let method = request.method().clone();
+    let started = Instant::now();
+
+    let in_flight = InFlight::start();
+    let response = next.run(request).await;
+    drop(in_flight);
+
+    let route = response
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or(UNMATCHED, MatchedPath::as_str);
+    let status = format!("{}xx", response.status().as_u16() / 100);
+    let labels = [method_label(&method), route, &status];
+    HTTP_REQUESTS.with_label_values(&labels).inc();
+    HTTP_DURATION
+        .with_label_values(&labels)
+        .observe(started.elapsed().as_secs_f64());
+    response
+}
+
+/// Keeps `http_requests_in_flight` right when a client disconnects and the request future
+/// is dropped before it completes.
+struct InFlight;
+
+impl InFlight {
+    fn start() -> Self {
+        HTTP_IN_FLIGHT.inc();
+        Self
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        HTTP_IN_FLIGHT.dec();
+    }
+}
+
+/// The matched path is only known once routing happened, so hand it back out to `track`.
+pub async fn remember_route<B>(request: Request<B>, next: Next<B>) -> Response {
+    let matched = request.extensions().get::<MatchedPath>().cloned();
+    let mut response = next.run(request).await;
+    if let Some(matched) = matched {
+        response.extensions_mut().insert(matched);
+    }
+    response
+}
+
+// Arbitrary methods would let clients create unbounded series.
+fn method_label(method: &Method) -> &'static str {
+    match *method {
+        Method::GET => "GET",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::DELETE => "DELETE",
+        Method::PATCH => "PATCH",
+        Method::HEAD => "HEAD",
+        Method::OPTIONS => "OPTIONS",
+        _ => "OTHER",
+    }
+}
+
+pub fn record_login(succeeded: bool) {
+    let outcome = if succeeded { "success" } else { "failure" };
+    LOGINS.with_label_values(&[outcome]).inc();
+}
+
+pub fn record_signup() {
+    SIGNUPS.inc();
+}
+
+pub fn record_order_created() {
+    ORDERS_CREATED.inc();
+}
+
+/// Samples the database pool and renders every metric in the Prometheus text format.
+pub async fn render(pool: &DbPool) -> String {
+    let size = i64::from(pool.size());
+    let idle = i64::try_from(pool.num_idle()).unwrap_or(i64::MAX);
+    POOL_CONNECTIONS.with_label_values(&["idle"]).set(idle);
+    POOL_CONNECTIONS
+        .with_label_values(&["in_use"])
+        .set((size - idle).max(0));
+
+    let started = Instant::now();
+    let acquired = tokio::time::timeout(ACQUIRE_TIMEOUT, pool.acquire()).await;
+    POOL_PROBE.set(match acquired {
+        Ok(Ok(_)) => started.elapsed().as_secs_f64(),
+        _ => f64::INFINITY,
+    });
+
+    // Make sure every metric shows up, even before it was first touched.
+    Lazy::force(&HTTP_REQUESTS);
+    Lazy::force(&HTTP_DURATION);
+    Lazy::force(&HTTP_IN_FLIGHT);
+    Lazy::force(&LOGINS);
+    Lazy::force(&SIGNUPS);
+    Lazy::force(&ORDERS_CREATED);
+
+    let mut buffer = Vec::new();
+    TextEncoder::new()
+        .encode(&REGISTRY.gather(), &mut buffer)
+        .expect("text encoding cannot fail");
+    String::from_utf8(buffer).expect("text encoding produces UTF-8")
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeStatus {
+    Open,
+    Resolved,
+}
+
+impl DisputeStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Resolved => "resolved",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeOutcome {
+    Release,
+    Refund,
+    Split,
+}
+
+impl DisputeOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Release => "release",
+            Self::Refund => "refund",
+            Self::Split => "split",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Dispute {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub opened_by: Uuid,
+    pub reason: String,
+    pub evidence: Vec<String>,
+    pub status: String,
+    pub outcome: Option<String>,
+    pub student_amount: Option<i32>,
+    pub mentor_amount: Option<i32>,
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, NaiveTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MentorProfile {
+    #[serde(skip_serializing)]
+    pub user_id: Uuid,
+    pub headline: String,
+    pub hourly_rate: i32,
+    pub languages: Vec<String>,
+    pub education: Option<String>,
+    pub time_zone: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AvailabilitySlot {
+    #[serde(skip_serializing)]
+    pub id: Uuid,
+    pub weekday: i16,
+    pub start_time: NaiveTime,
+    pub end_time: NaiveTime,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AvailabilityException {
+    #[serde(skip_serializing)]
+    pub id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MentorListing {
+    #[serde(skip_serializing)]
+    pub user_id: Uuid,
+    pub username: String,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub headline: String,
+    pub hourly_rate: i32,
+    pub languages: Vec<String>,
+    pub time_zone: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Validate, JsonSchema)]
+pub struct Message {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub sender_id: Uuid,
+    #[validate(length(min = 1, max = 4096))]
+    pub body: String,
+    pub read_at: Option<DateTime<Utc>>,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct UnreadCount {
+    pub order_id: Uuid,
+    pub count: i64,
+}
@@ -1,2 +1,14 @@
+pub mod dispute;
+pub mod email;
+pub mod event;
+pub mod export;
+pub mod mentor_profile;
+pub mod message;
+pub mod notification;
 pub mod order;
+pub mod privacy;
+pub mod search;
+pub mod session;
+pub mod subject;
+pub mod upload;
 pub mod user;
@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    OrderCreated,
+    OrderStatusChanged,
+    NewMessage,
+    DisputeOpened,
+    DisputeResolved,
+}
+
+impl NotificationKind {
+    pub const ALL: [Self; 5] = [
+        Self::OrderCreated,
+        Self::OrderStatusChanged,
+        Self::NewMessage,
+        Self::DisputeOpened,
+        Self::DisputeResolved,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OrderCreated => "order_created",
+            Self::OrderStatusChanged => "order_status_changed",
+            Self::NewMessage => "new_message",
+            Self::DisputeOpened => "dispute_opened",
+            Self::DisputeResolved => "dispute_resolved",
+        }
+    }
+
+    /// Whether the notification also goes out by email until the user says otherwise. Chatty
+    /// kinds stay in the app unless users opt in.
+    pub fn emails_by_default(self) -> bool {
+        match self {
+            Self::OrderCreated | Self::DisputeOpened | Self::DisputeResolved => true,
+            Self::OrderStatusChanged | Self::NewMessage => false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Notification {
+    pub id: Uuid,
+    #[serde(skip_serializing)]
+    pub user_id: Uuid,
+    pub kind: String,
+    pub payload: Value,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct NotificationPreference {
+    pub kind: String,
+    pub email: bool,
+}
@@ -1,9 +1,32 @@
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
-#[derive(Debug, Serialize, Validate)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    InProgress,
+    Delivered,
+    Disputed,
+    Completed,
+    Refunded,
+}
+
+impl OrderStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::InProgress => "in_progress",
+            Self::Delivered => "delivered",
+            Self::Disputed => "disputed",
+            Self::Completed => "completed",
+            Self::Refunded => "refunded",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Validate, JsonSchema)]
 pub struct Order {
     pub id: Uuid,
     pub student_id: Uuid,
@@ -12,6 +35,32 @@ pub struct Order {
     pub price: i32,
     pub title: String,
     pub description: String,
+    pub status: String,
+    pub deadline: Option<DateTime<Utc>>,
+    pub language: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
+
+impl Order {
+    pub fn participants(&self) -> [Uuid; 2] {
+        [self.student_id, self.mentor_id]
+    }
+
+    pub fn is_participant(&self, user_id: Uuid) -> bool {
+        self.participants().contains(&user_id)
+    }
+
+    pub fn has_status(&self, status: OrderStatus) -> bool {
+        self.status == status.as_str()
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct OrderHistoryEntry {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub status: String,
+    pub changed_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,72 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::user::User;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    Public,
+    Members,
+    Private,
+}
+
+impl Visibility {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Public => "public",
+            Self::Members => "members",
+            Self::Private => "private",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Audience {
+    Anonymous,
+    Member,
+    Owner,
+}
+
+impl Audience {
+    pub fn of(viewer: Option<&User>, user_id: Uuid) -> Self {
+        match viewer {
+            Some(viewer) if viewer.id == user_id || viewer.is_admin => Self::Owner,
+            Some(_) => Self::Member,
+            None => Self::Anonymous,
+        }
+    }
+
+    pub fn can_see(self, visibility: &str) -> bool {
+        match self {
+            Self::Owner => true,
+            Self::Member => visibility != Visibility::Private.as_str(),
+            Self::Anonymous => visibility == Visibility::Public.as_str(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PrivacySettings {
+    pub user_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub age: String,
+    pub about: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PrivacySettings {
+    pub fn default_for(user_id: Uuid, updated_at: DateTime<Utc>) -> Self {
+        Self {
+            user_id,
+            name: Visibility::Public.as_str().to_owned(),
+            email: Visibility::Private.as_str().to_owned(),
+            age: Visibility::Private.as_str().to_owned(),
+            about: Visibility::Public.as_str().to_owned(),
+            updated_at,
+        }
+    }
+}
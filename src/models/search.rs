@@ -0,0 +1,18 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+use uuid::Uuid;
+
+/// `title` and `snippet` are HTML-escaped, with the matched terms wrapped in `<mark>`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SearchHit {
+    #[serde(skip)]
+    pub id: Uuid,
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    pub title: String,
+    pub snippet: String,
+    pub rank: f32,
+}
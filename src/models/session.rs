@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionStatus {
+    Booked,
+    Cancelled,
+}
+
+impl SessionStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Booked => "booked",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Session {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub mentor_id: Uuid,
+    pub student_id: Uuid,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub status: String,
+    pub cancelled_by: Option<Uuid>,
+    pub cancellation_reason: Option<String>,
+    pub sequence: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Session {
+    pub fn is_participant(&self, user_id: Uuid) -> bool {
+        self.mentor_id == user_id || self.student_id == user_id
+    }
+
+    pub fn has_status(&self, status: SessionStatus) -> bool {
+        self.status == status.as_str()
+    }
+}
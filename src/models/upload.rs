@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadKind {
+    Avatar,
+    Attachment,
+}
+
+impl UploadKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Avatar => "avatar",
+            Self::Attachment => "attachment",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Upload {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub order_id: Option<Uuid>,
+    pub kind: String,
+    pub file_name: String,
+    pub content_type: String,
+    pub size: i64,
+    pub storage_key: String,
+    pub thumbnail_key: Option<String>,
+    pub scan_status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Upload {
+    pub fn has_kind(&self, kind: UploadKind) -> bool {
+        self.kind == kind.as_str()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.storage_key.as_str()).chain(self.thumbnail_key.as_deref())
+    }
+}
@@ -1,13 +1,13 @@
+use std::fmt;
+
 use chrono::{DateTime, Utc};
-use serde::Serialize;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::validators::is_lowercase_alphanumeric;
+use crate::{secret::REDACTED, validators::is_lowercase_alphanumeric};
 
-#[derive(Debug, Serialize, Validate)]
+#[derive(Validate)]
 pub struct User {
-    #[serde(skip_serializing)]
     pub id: Uuid,
     #[validate(length(min = 4, max = 32), custom = "is_lowercase_alphanumeric")]
     pub username: String,
@@ -15,13 +15,39 @@ pub struct User {
     pub last_name: Option<String>,
     #[validate(email)]
     pub email: String,
-    #[serde(skip_serializing)]
     pub pwd_hash: String,
     #[validate(range(min = 0, max = 128))]
     pub age: Option<i32>,
     #[validate(length(max = 512))]
     pub about: Option<String>,
     pub verified: bool,
+    pub is_admin: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
+    pub purged_at: Option<DateTime<Utc>>,
+    pub language: Option<String>,
+}
+
+// Users end up in most spans, which must not carry the password hash.
+impl fmt::Debug for User {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("User")
+            .field("id", &self.id)
+            .field("username", &self.username)
+            .field("first_name", &self.first_name)
+            .field("last_name", &self.last_name)
+            .field("email", &self.email)
+            .field("pwd_hash", &REDACTED)
+            .field("age", &self.age)
+            .field("about", &self.about)
+            .field("verified", &self.verified)
+            .field("is_admin", &self.is_admin)
+            .field("created_at", &self.created_at)
+            .field("updated_at", &self.updated_at)
+            .field("deleted_at", &self.deleted_at)
+            .field("purged_at", &self.purged_at)
+            .field("language", &self.language)
+            .finish()
+    }
 }
@@ -0,0 +1,604 @@
+use once_cell::sync::Lazy;
+use schemars::{
+    gen::{SchemaGenerator, SchemaSettings},
+    JsonSchema,
+};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    dtos::{
+        auth::{AuthBody, LoginForm, SignupForm},
+        calendar::{CalendarQuery, CalendarTokenBody},
+        dispute::{DisputeDetail, DisputeSort, DisputesQuery, OpenDisputeForm, ResolveDisputeForm},
+        export::ExportBody,
+        mentor_profile::{MentorProfileBody, MentorProfileForm, MentorSort, MentorsQuery},
+        message::{MessageForm, MessageSort},
+        notification::{EditNotificationPreferencesForm, NotificationSort, NotificationsQuery},
+        order::{CreateOrderForm, OrderBody, OrderSort, OrdersQuery},
+        realtime::ConnectQuery,
+        search::{SearchQuery, SearchSort, UsernameQuery},
+        session::{CancelSessionForm, SessionBody, SessionForm, SessionsQuery},
+        subject::{SubjectForm, SubjectIdsForm, SubjectNode},
+        upload::{DownloadQuery, UploadBody},
+        user::{
+            AdminUserBody, EditUserEmailForm, EditUserForm, EditUserPasswordForm, MeBody,
+            PrivacyBody, PrivacyForm, PublicProfile, UserBody, UserSort, UsersQuery,
+        },
+    },
+    error::Problem,
+    models::{
+        dispute::Dispute,
+        mentor_profile::MentorListing,
+        message::Message,
+        notification::{Notification, NotificationPreference},
+        order::Order,
+        search::SearchHit,
+        session::Session,
+        subject::Subject,
+    },
+    pagination::{Page, PageQuery, Sort},
+    versioning,
+};
+
+/// The document served at `/openapi.json`, built once from the operations below.
+pub static DOCUMENT: Lazy<Value> = Lazy::new(document);
+
+pub const DOCS_PAGE: &str = include_str!("docs.html");
+
+#[allow(clippy::too_many_lines)]
+fn document() -> Value {
+    let mut spec = Spec::new();
+
+    spec.post("/auth/signup", "auth", "Create an account")
+        .json::<SignupForm>()
+        .responds::<AuthBody>(200);
+    spec.post("/auth/login", "auth", "Log in")
+        .json::<LoginForm>()
+        .responds::<AuthBody>(200);
+    spec.post(
+        "/auth/restore",
+        "auth",
+        "Restore an account pending deletion",
+    )
+    .json::<LoginForm>()
+    .responds::<AuthBody>(200);
+
+    spec.get("/ws", "realtime", "Open a WebSocket event feed")
+        .optional_auth()
+        .query::<ConnectQuery>()
+        .raw(101, "", "Switching to the WebSocket protocol.");
+    spec.get("/events", "realtime", "Subscribe to server-sent events")
+        .auth()
+        .raw(200, "text/event-stream", "A stream of events.");
+
+    spec.get("/subjects", "subjects", "Subject tree")
+        .responds::<Vec<SubjectNode>>(200);
+    spec.get("/mentors", "mentors", "Search mentors")
+        .query::<MentorsQuery>()
+        .page::<MentorListing, MentorSort>();
+    spec.get("/search", "search", "Full-text search")
+        .optional_auth()
+        .query::<SearchQuery>()
+        .page::<SearchHit, SearchSort>();
+    spec.get("/search/users", "search", "Username autocomplete")
+        .optional_auth()
+        .query::<UsernameQuery>()
+        .responds::<Vec<PublicProfile>>(200);
+    spec.get(
+        "/files/:id",
+        "uploads",
+        "Download a file through a signed link",
+    )
+    .query::<DownloadQuery>()
+    .raw(200, "application/octet-stream", "The file contents.");
+
+    spec.get("/users", "users", "List users")
+        .optional_auth()
+        .query::<UsersQuery>()
+        .page::<PublicProfile, UserSort>();
+    spec.get("/users/me", "users", "Own profile")
+        .auth()
+        .responds::<MeBody>(200);
+    spec.delete("/users/me", "users", "Delete own account")
+        .auth()
+        .no_content();
+    spec.put("/users/me/edit", "users", "Edit own profile")
+        .auth()
+        .json::<EditUserForm>()
+        .no_content();
+    spec.put("/users/me/edit/email", "users", "Change email address")
+        .auth()
+        .json::<EditUserEmailForm>()
+        .no_content();
+    spec.put("/users/me/edit/password", "users", "Change password")
+        .auth()
+        .json::<EditUserPasswordForm>()
+        .no_content();
+    spec.get("/users/me/privacy", "users", "Privacy settings")
+        .auth()
+        .responds::<PrivacyBody>(200);
+    spec.put("/users/me/privacy", "users", "Edit privacy settings")
+        .auth()
+        .json::<PrivacyForm>()
+        .responds::<PrivacyBody>(200);
+    spec.put("/users/me/avatar", "uploads", "Upload an avatar")
+        .auth()
+        .multipart()
+        .responds::<UploadBody>(200);
+    spec.delete("/users/me/avatar", "uploads", "Remove the avatar")
+        .auth()
+        .no_content();
+    spec.post("/users/me/export", "users", "Request a data export")
+        .auth()
+        .responds::<ExportBody>(202);
+    spec.get("/users/me/export/:id", "users", "Download a data export")
+        .auth()
+        .also::<ExportBody>(202)
+        .raw(200, "application/zip", "The export archive.");
+    spec.put("/users/me/subjects", "subjects", "Set own subjects")
+        .auth()
+        .json::<SubjectIdsForm>()
+        .responds::<Vec<Subject>>(200);
+    spec.get("/users/me/sessions", "sessions", "Own sessions")
+        .auth()
+        .query::<SessionsQuery>()
+        .responds::<Vec<SessionBody>>(200);
+    spec.get("/users/me/calendar.ics", "calendar", "iCalendar feed")
+        .query::<CalendarQuery>()
+        .raw(200, "text/calendar", "The calendar feed.");
+    spec.get(
+        "/users/me/calendar-token",
+        "calendar",
+        "Calendar feed token",
+    )
+    .auth()
+    .responds::<CalendarTokenBody>(200);
+    spec.post(
+        "/users/me/calendar-token",
+        "calendar",
+        "Rotate the calendar feed token",
+    )
+    .auth()
+    .responds::<CalendarTokenBody>(200);
+    spec.get("/users/me/mentor-profile", "mentors", "Own mentor profile")
+        .auth()
+        .responds::<MentorProfileBody>(200);
+    spec.put(
+        "/users/me/mentor-profile",
+        "mentors",
+        "Edit own mentor profile",
+    )
+    .auth()
+    .json::<MentorProfileForm>()
+    .responds::<MentorProfileBody>(200);
+    spec.delete(
+        "/users/me/mentor-profile",
+        "mentors",
+        "Remove own mentor profile",
+    )
+    .auth()
+    .no_content();
+    spec.get("/users/me/notifications", "notifications", "Notifications")
+        .auth()
+        .query::<NotificationsQuery>()
+        .page::<Notification, NotificationSort>();
+    spec.post(
+        "/users/me/notifications/read",
+        "notifications",
+        "Mark all notifications read",
+    )
+    .auth()
+    .no_content();
+    spec.post(
+        "/users/me/notifications/:id/read",
+        "notifications",
+        "Mark a notification read",
+    )
+    .auth()
+    .no_content();
+    spec.get(
+        "/users/me/notification-preferences",
+        "notifications",
+        "Email preferences",
+    )
+    .auth()
+    .responds::<Vec<NotificationPreference>>(200);
+    spec.put(
+        "/users/me/notification-preferences",
+        "notifications",
+        "Edit email preferences",
+    )
+    .auth()
+    .json::<EditNotificationPreferencesForm>()
+    .responds::<Vec<NotificationPreference>>(200);
+    spec.get("/users/:username", "users", "Public profile")
+        .optional_auth()
+        .responds::<UserBody>(200);
+    spec.get("/users/:username/avatar", "uploads", "Avatar of a user")
+        .responds::<UploadBody>(200);
+    spec.get(
+        "/users/:username/subjects",
+        "subjects",
+        "Subjects of a user",
+    )
+    .responds::<Vec<Subject>>(200);
+
+    spec.get("/orders", "orders", "Own orders")
+        .auth()
+        .query::<OrdersQuery>()
+        .page::<Order, OrderSort>();
+    spec.post("/orders", "orders", "Place an order")
+        .auth()
+        .json::<CreateOrderForm>()
+        .responds::<OrderBody>(201);
+    spec.get("/orders/:id", "orders", "Order")
+        .auth()
+        .responds::<OrderBody>(200);
+    spec.get("/orders/:id/messages", "messages", "Messages of an order")
+        .auth()
+        .page::<Message, MessageSort>();
+    spec.post("/orders/:id/messages", "messages", "Send a message")
+        .auth()
+        .json::<MessageForm>()
+        .responds::<Message>(201);
+    spec.post(
+        "/orders/:id/messages/read",
+        "messages",
+        "Mark messages read",
+    )
+    .auth()
+    .no_content();
+    spec.put(
+        "/orders/:id/messages/:message_id",
+        "messages",
+        "Edit a message",
+    )
+    .auth()
+    .json::<MessageForm>()
+    .responds::<Message>(200);
+    spec.delete(
+        "/orders/:id/messages/:message_id",
+        "messages",
+        "Delete a message",
+    )
+    .auth()
+    .no_content();
+    spec.get("/orders/:id/disputes", "disputes", "Disputes of an order")
+        .auth()
+        .responds::<Vec<Dispute>>(200);
+    spec.post("/orders/:id/disputes", "disputes", "Open a dispute")
+        .auth()
+        .json::<OpenDisputeForm>()
+        .responds::<Dispute>(201);
+    spec.get("/orders/:id/sessions", "sessions", "Sessions of an order")
+        .auth()
+        .responds::<Vec<Session>>(200);
+    spec.post("/orders/:id/sessions", "sessions", "Book a session")
+        .auth()
+        .json::<SessionForm>()
+        .responds::<Session>(201);
+    spec.put(
+        "/orders/:id/sessions/:session_id",
+        "sessions",
+        "Reschedule a session",
+    )
+    .auth()
+    .json::<SessionForm>()
+    .responds::<Session>(200);
+    spec.post(
+        "/orders/:id/sessions/:session_id/cancel",
+        "sessions",
+        "Cancel a session",
+    )
+    .auth()
+    .json::<CancelSessionForm>()
+    .responds::<Session>(200);
+    spec.get(
+        "/orders/:id/attachments",
+        "uploads",
+        "Attachments of an order",
+    )
+    .auth()
+    .responds::<Vec<UploadBody>>(200);
+    spec.post("/orders/:id/attachments", "uploads", "Attach a file")
+        .auth()
+        .multipart()
+        .responds::<UploadBody>(201);
+    spec.delete(
+        "/orders/:id/attachments/:attachment_id",
+        "uploads",
+        "Remove an attachment",
+    )
+    .auth()
+    .no_content();
+
+    spec.get("/admin/users", "admin", "List users with account details")
+        .admin()
+        .query::<UsersQuery>()
+        .page::<AdminUserBody, UserSort>();
+    spec.get(
+        "/admin/users/:username",
+        "admin",
+        "Account details of a user",
+    )
+    .admin()
+    .responds::<AdminUserBody>(200);
+    spec.get("/admin/disputes", "admin", "Dispute queue")
+        .admin()
+        .query::<DisputesQuery>()
+        .page::<Dispute, DisputeSort>();
+    spec.get(
+        "/admin/disputes/:id",
+        "admin",
+        "Dispute with its order and messages",
+    )
+    .admin()
+    .responds::<DisputeDetail>(200);
+    spec.post("/admin/disputes/:id/resolve", "admin", "Resolve a dispute")
+        .admin()
+        .json::<ResolveDisputeForm>()
+        .responds::<Dispute>(200);
+    spec.post("/admin/subjects", "admin", "Create a subject")
+        .admin()
+        .json::<SubjectForm>()
+        .responds::<Subject>(201);
+    spec.put("/admin/subjects/:id", "admin", "Edit a subject")
+        .admin()
+        .json::<SubjectForm>()
+        .responds::<Subject>(200);
+    spec.delete("/admin/subjects/:id", "admin", "Delete a subject")
+        .admin()
+        .no_content();
+
+    spec.finish()
+}
+
+struct Spec {
+    generator: SchemaGenerator,
+    paths: Map<String, Value>,
+}
+
+impl Spec {
+    fn new() -> Self {
+        Self {
+            generator: SchemaSettings::openapi3().into_generator(),
+            paths: Map::new(),
+        }
+    }
+
+    fn get(&mut self, path: &'static str, tag: &str, summary: &str) -> Operation<'_> {
+        Operation::new(self, "get", path, tag, summary)
+    }
+
+    fn post(&mut self, path: &'static str, tag: &str, summary: &str) -> Operation<'_> {
+        Operation::new(self, "post", path, tag, summary)
+    }
+
+    fn put(&mut self, path: &'static str, tag: &str, summary: &str) -> Operation<'_> {
+        Operation::new(self, "put", path, tag, summary)
+    }
+
+    fn delete(&mut self, path: &'static str, tag: &str, summary: &str) -> Operation<'_> {
+        Operation::new(self, "delete", path, tag, summary)
+    }
+
+    fn schema<T: JsonSchema>(&mut self) -> Value {
+        json!(self.generator.subschema_for::<T>())
+    }
+
+    fn finish(mut self) -> Value {
+        let problem = self.schema::<Problem>();
+        json!({
+            "openapi": "3.0.3",
+            "info": {
+                "title": "s4s",
+                "version": env!("CARGO_PKG_VERSION"),
+            },
+            "servers": [{ "url": versioning::PREFIX }],
+            "paths": self.paths,
+            "components": {
+                "schemas": self.generator.definitions(),
+                "responses": {
+                    "Problem": {
+                        "description": "An RFC 7807 problem document.",
+                        "content": { "application/problem+json": { "schema": problem } },
+                    },
+                },
+                "securitySchemes": {
+                    "bearer": { "type": "http", "scheme": "bearer", "bearerFormat": "JWT" },
+                },
+            },
+        })
+    }
+}
+
+struct Operation<'a> {
+    spec: &'a mut Spec,
+    method: &'static str,
+    path: &'static str,
+    fields: Map<String, Value>,
+    parameters: Vec<Value>,
+    responses: Map<String, Value>,
+}
+
+impl<'a> Operation<'a> {
+    fn new(
+        spec: &'a mut Spec,
+        method: &'static str,
+        path: &'static str,
+        tag: &str,
+        summary: &str,
+    ) -> Self {
+        let parameters = path
+            .split('/')
+            .filter_map(|segment| segment.strip_prefix(':'))
+            .map(|name| {
+                // Usernames are the only path parameters that are not ids.
+                let schema = if name == "username" {
+                    json!({ "type": "string" })
+                } else {
+                    json!({ "type": "string", "format": "uuid" })
+                };
+                json!({ "name": name, "in": "path", "required": true, "schema": schema })
+            })
+            .collect();
+        let mut fields = Map::new();
+        fields.insert("tags".into(), json!([tag]));
+        fields.insert("summary".into(), json!(summary));
+        let mut responses = Map::new();
+        responses.insert(
+            "default".into(),
+            json!({ "$ref": "#/components/responses/Problem" }),
+        );
+        Self {
+            spec,
+            method,
+            path,
+            fields,
+            parameters,
+            responses,
+        }
+    }
+
+    fn auth(mut self) -> Self {
+        self.fields
+            .insert("security".into(), json!([{ "bearer": [] }]));
+        self
+    }
+
+    fn optional_auth(mut self) -> Self {
+        self.fields
+            .insert("security".into(), json!([{}, { "bearer": [] }]));
+        self
+    }
+
+    fn admin(self) -> Self {
+        let mut operation = self.auth();
+        operation
+            .fields
+            .insert("description".into(), json!("Requires an administrator."));
+        operation
+    }
+
+    /// Lists the fields of `T` as query parameters.
+    fn query<T: JsonSchema>(mut self) -> Self {
+        let schema = json!(T::json_schema(&mut self.spec.generator));
+        let required = schema["required"].as_array().cloned().unwrap_or_default();
+        if let Some(properties) = schema["properties"].as_object() {
+            self.parameters
+                .extend(properties.iter().map(|(name, schema)| {
+                    json!({
+                        "name": name,
+                        "in": "query",
+                        "required": required.contains(&json!(name)),
+                        "schema": schema,
+                    })
+                }));
+        }
+        self
+    }
+
+    fn json<T: JsonSchema>(mut self) -> Self {
+        let schema = self.spec.schema::<T>();
+        self.fields.insert(
+            "requestBody".into(),
+            json!({
+                "required": true,
+                "content": { "application/json": { "schema": schema } },
+            }),
+        );
+        self
+    }
+
+    fn multipart(mut self) -> Self {
+        self.fields.insert(
+            "requestBody".into(),
+            json!({
+                "required": true,
+                "content": {
+                    "multipart/form-data": {
+                        "schema": {
+                            "type": "object",
+                            "properties": { "file": { "type": "string", "format": "binary" } },
+                            "required": ["file"],
+                        },
+                    },
+                },
+            }),
+        );
+        self
+    }
+
+    /// Documents an additional JSON response besides the one finishing the operation.
+    fn also<T: JsonSchema>(mut self, status: u16) -> Self {
+        let schema = self.spec.schema::<T>();
+        self.responses.insert(
+            status.to_string(),
+            json!({
+                "description": description(status),
+                "content": { "application/json": { "schema": schema } },
+            }),
+        );
+        self
+    }
+
+    fn responds<T: JsonSchema>(self, status: u16) -> &'a mut Spec {
+        self.also::<T>(status).finish()
+    }
+
+    fn page<T: JsonSchema, S: Sort + JsonSchema>(self) -> &'a mut Spec {
+        let mut operation = self.query::<PageQuery<S>>().also::<Page<T>>(200);
+        operation.responses["200"]["headers"] = json!({
+            "Link": {
+                "description": "The URL of the next page, if there is one.",
+                "schema": { "type": "string" },
+            },
+        });
+        operation.finish()
+    }
+
+    fn no_content(mut self) -> &'a mut Spec {
+        self.responses
+            .insert("204".into(), json!({ "description": description(204) }));
+        self.finish()
+    }
+
+    fn raw(mut self, status: u16, content_type: &str, description: &str) -> &'a mut Spec {
+        let mut response = json!({ "description": description });
+        if !content_type.is_empty() {
+            response["content"] = json!({ content_type: { "schema": { "type": "string" } } });
+        }
+        self.responses.insert(status.to_string(), response);
+        self.finish()
+    }
+
+    fn finish(mut self) -> &'a mut Spec {
+        if !self.parameters.is_empty() {
+            self.fields
+                .insert("parameters".into(), Value::Array(self.parameters));
+        }
+        self.fields
+            .insert("responses".into(), Value::Object(self.responses));
+        let path = self
+            .path
+            .split('/')
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => format!("{{{name}}}"),
+                None => segment.to_owned(),
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let item = self.spec.paths.entry(path).or_insert_with(|| json!({}));
+        item[self.method] = Value::Object(self.fields);
+        self.spec
+    }
+}
+
+fn description(status: u16) -> &'static str {
+    match status {
+        201 => "Created.",
+        202 => "Accepted, still being processed.",
+        204 => "No content.",
+        _ => "Success.",
+    }
+}
@@ -0,0 +1,282 @@
+use axum::{
+    http::{header::LINK, HeaderValue, Uri},
+    response::{IntoResponse, Response},
+    Json,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::Error;
+
+pub const DEFAULT_LIMIT: i64 = 20;
+pub const MAX_LIMIT: i64 = 100;
+
+const PARAMS: [&str; 4] = ["cursor", "limit", "sort", "order"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyKind {
+    Text,
+    Timestamp,
+    Integer,
+    Float,
+}
+
+impl KeyKind {
+    fn accepts(self, key: &str) -> bool {
+        match self {
+            Self::Text => true,
+            Self::Timestamp => parse_timestamp_key(key).is_some(),
+            Self::Integer => parse_integer_key(key).is_some(),
+            Self::Float => parse_float_key(key).is_some(),
+        }
+    }
+}
+
+pub trait Sort: Copy + Default + DeserializeOwned + Send {
+    type Item;
+
+    fn as_str(self) -> &'static str;
+
+    fn key(self, item: &Self::Item) -> (String, Uuid);
+
+    fn key_kind(self) -> KeyKind;
+
+    fn default_order(self) -> SortOrder {
+        SortOrder::Asc
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(bound(deserialize = "S: Sort"))]
+#[schemars(bound = "S: Sort + JsonSchema")]
+pub struct PageQuery<S> {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+    #[serde(default)]
+    pub sort: S,
+    pub order: Option<SortOrder>,
+}
+
+/// Whether a raw `name=value` pair of a query string belongs to the `PageQuery`.
+pub fn is_page_param(param: &str) -> bool {
+    let name = param.split_once('=').map_or(param, |(name, _)| name);
+    PARAMS.contains(&name)
+}
+
+#[derive(Debug)]
+pub struct Keyset {
+    pub sort: &'static str,
+    pub descending: bool,
+    pub after_key: Option<String>,
+    pub after_id: Option<Uuid>,
+    pub limit: i64,
+}
+
+// Cursors are checked against the sort's `KeyKind` when they come in, so these only come up
+// empty on the first page.
+impl Keyset {
+    pub fn after_text(&self) -> Option<&str> {
+        self.after_key.as_deref()
+    }
+
+    pub fn after_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.after_key.as_deref().and_then(parse_timestamp_key)
+    }
+
+    pub fn after_integer(&self) -> Option<i64> {
+        self.after_key.as_deref().and_then(parse_integer_key)
+    }
+
+    pub fn after_float(&self) -> Option<f32> {
+        self.after_key.as_deref().and_then(parse_float_key)
+    }
+}
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6f";
+
+pub fn timestamp_key(value: DateTime<Utc>) -> String {
+    value.format(TIMESTAMP_FORMAT).to_string()
+}
+
+pub fn integer_key(value: i64) -> String {
+    format!("{value:020}")
+}
+
+// `Display` prints the shortest string that parses back to the same `f32`.
+pub fn float_key(value: f32) -> String {
+    value.to_string()
+}
+
+fn parse_timestamp_key(key: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(key, TIMESTAMP_FORMAT)
+        .ok()
+        .map(|value| DateTime::from_utc(value, Utc))
+}
+
+fn parse_integer_key(key: &str) -> Option<i64> {
+    key.parse().ok()
+}
+
+fn parse_float_key(key: &str) -> Option<f32> {
+    key.parse().ok().filter(|value: &f32| value.is_finite())
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Cursor {
+    sort: String,
+    order: SortOrder,
+    key: String,
+    id: Uuid,
+}
+
+impl Cursor {
+    fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    fn decode(cursor: &str) -> Result<Self, Error> {
+        URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or(Error::InvalidCursor)
+    }
+}
+
+#[derive(Debug)]
+pub struct Pagination<S> {
+    sort: S,
+    order: SortOrder,
+    limit: i64,
+    after: Option<(String, Uuid)>,
+    uri: Uri,
+}
+
+impl<S: Sort> Pagination<S> {
+    pub fn new(query: PageQuery<S>, uri: Uri) -> Result<Self, Error> {
+        let order = query.order.unwrap_or_else(|| query.sort.default_order());
+        let after = match query.cursor {
+            Some(cursor) => {
+                let cursor = Cursor::decode(&cursor)?;
+                if cursor.sort != query.sort.as_str()
+                    || cursor.order != order
+                    || !query.sort.key_kind().accepts(&cursor.key)
+                {
+                    return Err(Error::InvalidCursor);
+                }
+                Some((cursor.key, cursor.id))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            sort: query.sort,
+            order,
+            limit: query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT),
+            after,
+            uri,
+        })
+    }
+
+    pub fn keyset(&self) -> Keyset {
+        Keyset {
+            sort: self.sort.as_str(),
+            descending: self.order == SortOrder::Desc,
+            after_key: self.after.as_ref().map(|(key, _)| key.clone()),
+            after_id: self.after.as_ref().map(|(_, id)| *id),
+            limit: self.limit + 1,
+        }
+    }
+
+    pub fn page(&self, mut items: Vec<S::Item>) -> Page<S::Item> {
+        let limit = usize::try_from(self.limit).unwrap_or_default();
+        if items.len() <= limit {
+            return Page::last(items);
+        }
+        items.truncate(limit);
+        let next_cursor = items.last().map(|item| {
+            let (key, id) = self.sort.key(item);
+            Cursor {
+                sort: self.sort.as_str().to_owned(),
+                order: self.order,
+                key,
+                id,
+            }
+            .encode()
+        });
+        let next_link = next_cursor.as_deref().map(|cursor| self.link(cursor));
+
+        Page {
+            items,
+            next_cursor,
+            next_link,
+        }
+    }
+
+    fn link(&self, cursor: &str) -> String {
+        let params = self
+            .uri
+            .query()
+            .unwrap_or_default()
+            .split('&')
+            .filter(|param| !param.is_empty() && !param.starts_with("cursor="))
+            .chain(std::iter::once(format!("cursor={cursor}").as_str()))
+            .collect::<Vec<_>>()
+            .join("&");
+        format!("{}?{params}", self.uri.path())
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Page<T> {
+    items: Vec<T>,
+    next_cursor: Option<String>,
+    #[serde(skip)]
+    next_link: Option<String>,
+}
+
+impl<T> Page<T> {
+    fn last(items: Vec<T>) -> Self {
+        Self {
+            items,
+            next_cursor: None,
+            next_link: None,
+        }
+    }
+
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+            next_link: self.next_link,
+        }
+    }
+}
+
+impl<T: Serialize> IntoResponse for Page<T> {
+    fn into_response(self) -> Response {
+        let link = self
+            .next_link
+            .as_deref()
+            .and_then(|link| HeaderValue::from_str(&format!("<{link}>; rel=\"next\"")).ok());
+        let mut response = Json(self).into_response();
+        if let Some(link) = link {
+            response.headers_mut().insert(LINK, link);
+        }
+        response
+    }
+}
@@ -3,10 +3,11 @@ use tracing::instrument;
 
 use crate::{
     auth::jwt,
+    config::account::AccountPolicy,
     dtos::auth::{AuthBody, LoginForm, SignupForm},
     error::ApiResult,
     extractors::ValidatedJson,
-    services::auth::Auth,
+    services::{account::Accounts, auth::Auth},
     storage::DbPool,
 };
 
@@ -33,3 +34,16 @@ pub async fn login(
 
     Ok(Json(AuthBody::new(token)))
 }
+
+#[instrument(skip(pool))]
+pub async fn restore(
+    State(pool): State<DbPool>,
+    State(policy): State<AccountPolicy>,
+    ValidatedJson(form): ValidatedJson<LoginForm>,
+) -> ApiResult<Json<AuthBody>> {
+    let id = Accounts::restore(&pool, policy, form).await?;
+
+    let token = jwt::Claims::new(id).sign()?;
+
+    Ok(Json(AuthBody::new(token)))
+}
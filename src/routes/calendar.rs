@@ -0,0 +1,53 @@
+use axum::{
+    extract::State,
+    http::header::{CACHE_CONTROL, CONTENT_TYPE},
+    response::IntoResponse,
+    Json,
+};
+use tracing::instrument;
+
+use crate::{
+    dtos::calendar::{CalendarQuery, CalendarTokenBody},
+    error::ApiResult,
+    extractors::{LoggedInUserId, ValidatedQuery},
+    services::calendar::Calendars,
+    storage::DbPool,
+};
+
+#[instrument(skip_all)]
+pub async fn feed(
+    State(pool): State<DbPool>,
+    ValidatedQuery(query): ValidatedQuery<CalendarQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let ics = Calendars::feed(&pool, query.token.expose()).await?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (CACHE_CONTROL, "no-cache"),
+        ],
+        ics,
+    ))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_token(
+    State(pool): State<DbPool>,
+    id: ApiResult<LoggedInUserId>,
+) -> ApiResult<Json<CalendarTokenBody>> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    let token = Calendars::get_token(&pool, id).await?;
+
+    Ok(Json(token))
+}
+
+#[instrument(skip(pool))]
+pub async fn rotate_token(
+    State(pool): State<DbPool>,
+    id: ApiResult<LoggedInUserId>,
+) -> ApiResult<Json<CalendarTokenBody>> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    let token = Calendars::rotate_token(&pool, id).await?;
+
+    Ok(Json(token))
+}
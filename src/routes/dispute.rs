@@ -0,0 +1,82 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::dispute::{
+        DisputeDetail, DisputeSort, DisputesQuery, OpenDisputeForm, ResolveDisputeForm,
+    },
+    error::ApiResult,
+    extractors::{AdminUser, LoggedInUser, PageFilter, ValidatedJson},
+    models::dispute::Dispute,
+    pagination::{Page, Pagination},
+    services::dispute::Disputes,
+    storage::DbPool,
+};
+
+#[instrument(skip(pool))]
+pub async fn open(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    Path(order_id): Path<Uuid>,
+    ValidatedJson(form): ValidatedJson<OpenDisputeForm>,
+) -> ApiResult<(StatusCode, Json<Dispute>)> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let dispute = Disputes::open(&pool, &user, order_id, form).await?;
+
+    Ok((StatusCode::CREATED, Json(dispute)))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_for_order(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    Path(order_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<Dispute>>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let disputes = Disputes::get_for_order(&pool, &user, order_id).await?;
+
+    Ok(Json(disputes))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_queue(
+    State(pool): State<DbPool>,
+    admin: ApiResult<AdminUser>,
+    PageFilter(query): PageFilter<DisputesQuery>,
+    pagination: Pagination<DisputeSort>,
+) -> ApiResult<Page<Dispute>> {
+    admin?;
+    let disputes = Disputes::get_queue(&pool, query.resolved, &pagination).await?;
+
+    Ok(disputes)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_detail(
+    State(pool): State<DbPool>,
+    admin: ApiResult<AdminUser>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<DisputeDetail>> {
+    admin?;
+    let detail = Disputes::get_detail(&pool, id).await?;
+
+    Ok(Json(detail))
+}
+
+#[instrument(skip(pool))]
+pub async fn resolve(
+    State(pool): State<DbPool>,
+    admin: ApiResult<AdminUser>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(form): ValidatedJson<ResolveDisputeForm>,
+) -> ApiResult<Json<Dispute>> {
+    let admin = admin.map(|AdminUser(u)| u)?;
+    let dispute = Disputes::resolve(&pool, &admin, id, form).await?;
+
+    Ok(Json(dispute))
+}
@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Path, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE},
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    blob::FileStore,
+    config::export::ExportPolicy,
+    dtos::export::{ExportBody, ExportDownload},
+    error::ApiResult,
+    extractors::LoggedInUser,
+    services::export::Exports,
+    storage::DbPool,
+};
+
+#[instrument(skip(pool, files))]
+pub async fn request(
+    State(pool): State<DbPool>,
+    State(files): State<FileStore>,
+    State(policy): State<ExportPolicy>,
+    user: ApiResult<LoggedInUser>,
+) -> ApiResult<(StatusCode, Json<ExportBody>)> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let export = Exports::request(&pool, &files, policy, &user).await?;
+
+    Ok((StatusCode::ACCEPTED, Json(export)))
+}
+
+#[instrument(skip(pool, files))]
+pub async fn download(
+    State(pool): State<DbPool>,
+    State(files): State<FileStore>,
+    user: ApiResult<LoggedInUser>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Response> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let download = Exports::download(&pool, &files, &user, id).await?;
+
+    Ok(match download {
+        ExportDownload::Pending(export) => (StatusCode::ACCEPTED, Json(export)).into_response(),
+        ExportDownload::Ready { file_name, bytes } => (
+            [
+                (CONTENT_TYPE, String::from("application/zip")),
+                (
+                    CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{file_name}\""),
+                ),
+                (CACHE_CONTROL, String::from("private, no-store")),
+            ],
+            bytes,
+        )
+            .into_response(),
+    })
+}
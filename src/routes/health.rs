@@ -0,0 +1,35 @@
+use axum::{extract::State, http::StatusCode, Json};
+use tracing::instrument;
+
+use crate::{
+    blob::FileStore,
+    config::health::HealthPolicy,
+    dtos::health::HealthBody,
+    services::{
+        event::EventHub,
+        health::{Health, Lifecycle},
+    },
+    storage::DbPool,
+};
+
+pub async fn live() -> Json<HealthBody> {
+    Json(Health::live())
+}
+
+#[instrument(skip_all)]
+pub async fn ready(
+    State(pool): State<DbPool>,
+    State(files): State<FileStore>,
+    State(events): State<EventHub>,
+    State(policy): State<HealthPolicy>,
+    State(lifecycle): State<Lifecycle>,
+) -> (StatusCode, Json<HealthBody>) {
+    let health = Health::ready(&pool, &files, &events, policy, &lifecycle).await;
+    let status = if health.is_ok() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(health))
+}
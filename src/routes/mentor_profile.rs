@@ -0,0 +1,57 @@
+use axum::{extract::State, http::StatusCode, Json};
+use tracing::instrument;
+
+use crate::{
+    dtos::mentor_profile::{MentorProfileBody, MentorProfileForm, MentorSort, MentorsQuery},
+    error::ApiResult,
+    extractors::{LoggedInUserId, PageFilter, ValidatedJson},
+    models::mentor_profile::MentorListing,
+    pagination::{Page, Pagination},
+    services::mentor_profile::MentorProfiles,
+    storage::DbPool,
+};
+
+#[instrument(skip(pool))]
+pub async fn search(
+    State(pool): State<DbPool>,
+    PageFilter(query): PageFilter<MentorsQuery>,
+    pagination: Pagination<MentorSort>,
+) -> ApiResult<Page<MentorListing>> {
+    let mentors = MentorProfiles::search(&pool, query, &pagination).await?;
+
+    Ok(mentors)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_own(
+    State(pool): State<DbPool>,
+    id: ApiResult<LoggedInUserId>,
+) -> ApiResult<Json<MentorProfileBody>> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    let profile = MentorProfiles::get_own(&pool, id).await?;
+
+    Ok(Json(profile))
+}
+
+#[instrument(skip(pool))]
+pub async fn edit(
+    State(pool): State<DbPool>,
+    id: ApiResult<LoggedInUserId>,
+    ValidatedJson(form): ValidatedJson<MentorProfileForm>,
+) -> ApiResult<Json<MentorProfileBody>> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    let profile = MentorProfiles::edit(&pool, id, form).await?;
+
+    Ok(Json(profile))
+}
+
+#[instrument(skip(pool))]
+pub async fn delete(
+    State(pool): State<DbPool>,
+    id: ApiResult<LoggedInUserId>,
+) -> ApiResult<StatusCode> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    MentorProfiles::delete(&pool, id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,80 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::message::{MessageForm, MessageSort},
+    error::ApiResult,
+    extractors::{LoggedInUser, ValidatedJson},
+    models::message::Message,
+    pagination::{Page, Pagination},
+    services::message::Messages,
+    storage::DbPool,
+};
+
+#[instrument(skip(pool))]
+pub async fn get_page(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    Path(order_id): Path<Uuid>,
+    pagination: Pagination<MessageSort>,
+) -> ApiResult<Page<Message>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let page = Messages::get_page(&pool, &user, order_id, &pagination).await?;
+
+    Ok(page)
+}
+
+#[instrument(skip(pool))]
+pub async fn create(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    Path(order_id): Path<Uuid>,
+    ValidatedJson(form): ValidatedJson<MessageForm>,
+) -> ApiResult<(StatusCode, Json<Message>)> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let message = Messages::create(&pool, &user, order_id, form).await?;
+
+    Ok((StatusCode::CREATED, Json(message)))
+}
+
+#[instrument(skip(pool))]
+pub async fn edit(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    Path((order_id, message_id)): Path<(Uuid, Uuid)>,
+    ValidatedJson(form): ValidatedJson<MessageForm>,
+) -> ApiResult<Json<Message>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let message = Messages::edit(&pool, &user, order_id, message_id, form).await?;
+
+    Ok(Json(message))
+}
+
+#[instrument(skip(pool))]
+pub async fn delete(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    Path((order_id, message_id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<StatusCode> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    Messages::delete(&pool, &user, order_id, message_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip(pool))]
+pub async fn mark_read(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    Path(order_id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    Messages::mark_read(&pool, &user, order_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,10 @@
+use axum::{extract::State, http::header::CONTENT_TYPE, response::IntoResponse};
+
+use crate::{metrics, storage::DbPool};
+
+pub async fn export(State(pool): State<DbPool>) -> impl IntoResponse {
+    (
+        [(CONTENT_TYPE, metrics::CONTENT_TYPE)],
+        metrics::render(&pool).await,
+    )
+}
@@ -1,4 +1,19 @@
 pub mod auth;
+pub mod calendar;
+pub mod dispute;
+pub mod export;
+pub mod health;
+pub mod mentor_profile;
+pub mod message;
+pub mod metrics;
+pub mod notification;
+pub mod openapi;
+pub mod order;
+pub mod realtime;
+pub mod search;
+pub mod session;
+pub mod subject;
+pub mod upload;
 pub mod user;
 
 pub async fn index() -> &'static str {
@@ -0,0 +1,76 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::notification::{EditNotificationPreferencesForm, NotificationSort, NotificationsQuery},
+    error::ApiResult,
+    extractors::{LoggedInUserId, PageFilter, ValidatedJson},
+    models::notification::{Notification, NotificationPreference},
+    pagination::{Page, Pagination},
+    services::notification::Notifications,
+    storage::DbPool,
+};
+
+#[instrument(skip(pool))]
+pub async fn get_page(
+    State(pool): State<DbPool>,
+    id: ApiResult<LoggedInUserId>,
+    PageFilter(query): PageFilter<NotificationsQuery>,
+    pagination: Pagination<NotificationSort>,
+) -> ApiResult<Page<Notification>> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    let page = Notifications::get_page(&pool, id, query, &pagination).await?;
+
+    Ok(page)
+}
+
+#[instrument(skip(pool))]
+pub async fn mark_read(
+    State(pool): State<DbPool>,
+    id: ApiResult<LoggedInUserId>,
+    Path(notification_id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    Notifications::mark_read(&pool, id, notification_id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip(pool))]
+pub async fn mark_all_read(
+    State(pool): State<DbPool>,
+    id: ApiResult<LoggedInUserId>,
+) -> ApiResult<StatusCode> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    Notifications::mark_all_read(&pool, id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_preferences(
+    State(pool): State<DbPool>,
+    id: ApiResult<LoggedInUserId>,
+) -> ApiResult<Json<Vec<NotificationPreference>>> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    let preferences = Notifications::get_preferences(&pool, id).await?;
+
+    Ok(Json(preferences))
+}
+
+#[instrument(skip(pool))]
+pub async fn edit_preferences(
+    State(pool): State<DbPool>,
+    id: ApiResult<LoggedInUserId>,
+    ValidatedJson(form): ValidatedJson<EditNotificationPreferencesForm>,
+) -> ApiResult<Json<Vec<NotificationPreference>>> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    let preferences = Notifications::edit_preferences(&pool, id, form).await?;
+
+    Ok(Json(preferences))
+}
@@ -0,0 +1,12 @@
+use axum::{response::Html, Json};
+use serde_json::Value;
+
+use crate::openapi::{DOCS_PAGE, DOCUMENT};
+
+pub async fn document() -> Json<&'static Value> {
+    Json(&DOCUMENT)
+}
+
+pub async fn docs() -> Html<&'static str> {
+    Html(DOCS_PAGE)
+}
@@ -0,0 +1,54 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::order::{CreateOrderForm, OrderBody, OrderSort, OrdersQuery},
+    error::ApiResult,
+    extractors::{LoggedInUser, PageFilter, ValidatedJson},
+    models::order::Order,
+    pagination::{Page, Pagination},
+    services::order::Orders,
+    storage::DbPool,
+};
+
+#[instrument(skip(pool))]
+pub async fn create(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    ValidatedJson(form): ValidatedJson<CreateOrderForm>,
+) -> ApiResult<(StatusCode, Json<OrderBody>)> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let order = Orders::create(&pool, &user, form).await?;
+
+    Ok((StatusCode::CREATED, Json(order)))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_all(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    PageFilter(query): PageFilter<OrdersQuery>,
+    pagination: Pagination<OrderSort>,
+) -> ApiResult<Page<Order>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let orders = Orders::get_page(&pool, &user, query, &pagination).await?;
+
+    Ok(orders)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_by_id(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<OrderBody>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let order = Orders::get(&pool, &user, id).await?;
+
+    Ok(Json(order))
+}
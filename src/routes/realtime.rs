@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use axum::{
+    extract::{Query, State, WebSocketUpgrade},
+    headers::{authorization::Bearer, Authorization},
+    response::{
+        sse::{Event as SseEvent, KeepAlive, Sse},
+        Response,
+    },
+    TypedHeader,
+};
+use futures_util::Stream;
+use tracing::instrument;
+
+use crate::{
+    auth::jwt::Claims,
+    dtos::realtime::ConnectQuery,
+    error::{ApiResult, Error, Result},
+    extractors::{LastEventId, LoggedInUserId},
+    services::{
+        event::EventHub,
+        realtime::{EventFeed, Realtime},
+    },
+    storage::{user, DbPool},
+};
+
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+#[instrument(skip_all)]
+pub async fn connect(
+    ws: WebSocketUpgrade,
+    State(pool): State<DbPool>,
+    State(hub): State<EventHub>,
+    bearer: Option<TypedHeader<Authorization<Bearer>>>,
+    Query(query): Query<ConnectQuery>,
+    LastEventId(last_event_id): LastEventId,
+) -> ApiResult<Response> {
+    // Browsers cannot set headers on a WebSocket handshake, so the token may also come as a query parameter.
+    let token = bearer
+        .map(|TypedHeader(Authorization(bearer))| bearer.token().to_owned())
+        .or_else(|| query.token.map(|token| token.expose().clone()))
+        .ok_or(Error::MissingToken)?;
+    let claims = Claims::verify(&token)?;
+    if !user::is_active(&pool, claims.sub())
+        .await
+        .map_err(Error::from)?
+    {
+        return Err(Error::AccountDeleted.into());
+    }
+    let feed = EventFeed::new(pool, &hub, claims.sub(), last_event_id);
+
+    Ok(ws.on_upgrade(move |socket| Realtime::serve(socket, feed)))
+}
+
+#[instrument(skip(pool, hub))]
+pub async fn events(
+    State(pool): State<DbPool>,
+    State(hub): State<EventHub>,
+    id: ApiResult<LoggedInUserId>,
+    LastEventId(last_event_id): LastEventId,
+) -> ApiResult<Sse<impl Stream<Item = Result<SseEvent>>>> {
+    let id = id.map(|LoggedInUserId(id)| id)?;
+    let feed = EventFeed::new(pool, &hub, id, last_event_id);
+
+    Ok(Sse::new(Realtime::stream(feed)).keep_alive(
+        KeepAlive::new()
+            .interval(KEEP_ALIVE_INTERVAL)
+            .text("keep-alive"),
+    ))
+}
@@ -0,0 +1,38 @@
+use axum::{extract::State, Json};
+use tracing::instrument;
+
+use crate::{
+    dtos::search::{SearchQuery, SearchSort, UsernameQuery},
+    dtos::user::PublicProfile,
+    error::ApiResult,
+    extractors::{LoggedInUser, PageFilter, ValidatedQuery},
+    models::search::SearchHit,
+    pagination::{Page, Pagination},
+    services::search::Search,
+    storage::DbPool,
+};
+
+#[instrument(skip(pool))]
+pub async fn search(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    PageFilter(query): PageFilter<SearchQuery>,
+    pagination: Pagination<SearchSort>,
+) -> ApiResult<Page<SearchHit>> {
+    let user = user.map(|LoggedInUser(u)| u).ok();
+    let page = Search::search(&pool, user.as_ref(), query, &pagination).await?;
+
+    Ok(page)
+}
+
+#[instrument(skip(pool))]
+pub async fn find_usernames(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    ValidatedQuery(query): ValidatedQuery<UsernameQuery>,
+) -> ApiResult<Json<Vec<PublicProfile>>> {
+    let user = user.map(|LoggedInUser(u)| u).ok();
+    let users = Search::find_usernames(&pool, user.as_ref(), query).await?;
+
+    Ok(Json(users))
+}
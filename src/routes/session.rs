@@ -0,0 +1,83 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    config::session::SessionPolicy,
+    dtos::session::{CancelSessionForm, SessionBody, SessionForm, SessionsQuery},
+    error::ApiResult,
+    extractors::{LoggedInUser, ValidatedJson, ValidatedQuery},
+    models::session::Session,
+    services::session::Sessions,
+    storage::DbPool,
+};
+
+#[instrument(skip(pool))]
+pub async fn book(
+    State(pool): State<DbPool>,
+    State(policy): State<SessionPolicy>,
+    user: ApiResult<LoggedInUser>,
+    Path(order_id): Path<Uuid>,
+    ValidatedJson(form): ValidatedJson<SessionForm>,
+) -> ApiResult<(StatusCode, Json<Session>)> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let session = Sessions::book(&pool, policy, &user, order_id, form).await?;
+
+    Ok((StatusCode::CREATED, Json(session)))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_for_order(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    Path(order_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<Session>>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let sessions = Sessions::get_for_order(&pool, &user, order_id).await?;
+
+    Ok(Json(sessions))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_mine(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    ValidatedQuery(query): ValidatedQuery<SessionsQuery>,
+) -> ApiResult<Json<Vec<SessionBody>>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let sessions = Sessions::get_for_user(&pool, &user, query).await?;
+
+    Ok(Json(sessions))
+}
+
+#[instrument(skip(pool))]
+pub async fn reschedule(
+    State(pool): State<DbPool>,
+    State(policy): State<SessionPolicy>,
+    user: ApiResult<LoggedInUser>,
+    Path((order_id, id)): Path<(Uuid, Uuid)>,
+    ValidatedJson(form): ValidatedJson<SessionForm>,
+) -> ApiResult<Json<Session>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let session = Sessions::reschedule(&pool, policy, &user, order_id, id, form).await?;
+
+    Ok(Json(session))
+}
+
+#[instrument(skip(pool))]
+pub async fn cancel(
+    State(pool): State<DbPool>,
+    State(policy): State<SessionPolicy>,
+    user: ApiResult<LoggedInUser>,
+    Path((order_id, id)): Path<(Uuid, Uuid)>,
+    ValidatedJson(form): ValidatedJson<CancelSessionForm>,
+) -> ApiResult<Json<Session>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let session = Sessions::cancel(&pool, policy, &user, order_id, id, form).await?;
+
+    Ok(Json(session))
+}
@@ -0,0 +1,82 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::subject::{SubjectForm, SubjectIdsForm, SubjectNode},
+    error::ApiResult,
+    extractors::{AdminUser, LoggedInUser, ValidatedJson},
+    models::subject::Subject,
+    services::subject::Subjects,
+    storage::DbPool,
+};
+
+#[instrument(skip(pool))]
+pub async fn get_tree(State(pool): State<DbPool>) -> ApiResult<Json<Vec<SubjectNode>>> {
+    let tree = Subjects::get_tree(&pool).await?;
+
+    Ok(Json(tree))
+}
+
+#[instrument(skip(pool))]
+pub async fn create(
+    State(pool): State<DbPool>,
+    admin: ApiResult<AdminUser>,
+    ValidatedJson(form): ValidatedJson<SubjectForm>,
+) -> ApiResult<(StatusCode, Json<Subject>)> {
+    admin?;
+    let subject = Subjects::create(&pool, form).await?;
+
+    Ok((StatusCode::CREATED, Json(subject)))
+}
+
+#[instrument(skip(pool))]
+pub async fn edit(
+    State(pool): State<DbPool>,
+    admin: ApiResult<AdminUser>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(form): ValidatedJson<SubjectForm>,
+) -> ApiResult<Json<Subject>> {
+    admin?;
+    let subject = Subjects::edit(&pool, id, form).await?;
+
+    Ok(Json(subject))
+}
+
+#[instrument(skip(pool))]
+pub async fn delete(
+    State(pool): State<DbPool>,
+    admin: ApiResult<AdminUser>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    admin?;
+    Subjects::delete(&pool, id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_for_user(
+    State(pool): State<DbPool>,
+    Path(username): Path<String>,
+) -> ApiResult<Json<Vec<Subject>>> {
+    let subjects = Subjects::get_for_user(&pool, username).await?;
+
+    Ok(Json(subjects))
+}
+
+#[instrument(skip(pool))]
+pub async fn set_for_me(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    ValidatedJson(form): ValidatedJson<SubjectIdsForm>,
+) -> ApiResult<Json<Vec<Subject>>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let subjects = Subjects::set_for_user(&pool, user.id, form).await?;
+
+    Ok(Json(subjects))
+}
@@ -0,0 +1,120 @@
+use axum::{
+    extract::{multipart::MultipartRejection, Multipart, Path, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE, X_CONTENT_TYPE_OPTIONS},
+        StatusCode,
+    },
+    response::IntoResponse,
+    Json,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    blob::FileStore,
+    dtos::upload::{DownloadQuery, UploadBody, UploadForm},
+    error::{ApiResult, Error},
+    extractors::{LoggedInUser, ValidatedQuery},
+    services::upload::Uploads,
+    storage::DbPool,
+};
+
+#[instrument(skip_all)]
+pub async fn set_avatar(
+    State(pool): State<DbPool>,
+    State(files): State<FileStore>,
+    user: ApiResult<LoggedInUser>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> ApiResult<Json<UploadBody>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let multipart = multipart.map_err(Error::from)?;
+    let form = UploadForm::read(multipart, files.config().max_avatar_bytes()).await?;
+    let avatar = Uploads::set_avatar(&pool, &files, &user, form).await?;
+
+    Ok(Json(avatar))
+}
+
+#[instrument(skip(pool, files))]
+pub async fn get_avatar(
+    State(pool): State<DbPool>,
+    State(files): State<FileStore>,
+    Path(username): Path<String>,
+) -> ApiResult<Json<UploadBody>> {
+    let avatar = Uploads::get_avatar(&pool, &files, username).await?;
+
+    Ok(Json(avatar))
+}
+
+#[instrument(skip(pool, files))]
+pub async fn delete_avatar(
+    State(pool): State<DbPool>,
+    State(files): State<FileStore>,
+    user: ApiResult<LoggedInUser>,
+) -> ApiResult<StatusCode> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    Uploads::delete_avatar(&pool, &files, &user).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip_all)]
+pub async fn attach(
+    State(pool): State<DbPool>,
+    State(files): State<FileStore>,
+    user: ApiResult<LoggedInUser>,
+    Path(order_id): Path<Uuid>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> ApiResult<(StatusCode, Json<UploadBody>)> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let multipart = multipart.map_err(Error::from)?;
+    let form = UploadForm::read(multipart, files.config().max_attachment_bytes()).await?;
+    let attachment = Uploads::attach(&pool, &files, &user, order_id, form).await?;
+
+    Ok((StatusCode::CREATED, Json(attachment)))
+}
+
+#[instrument(skip(pool, files))]
+pub async fn get_attachments(
+    State(pool): State<DbPool>,
+    State(files): State<FileStore>,
+    user: ApiResult<LoggedInUser>,
+    Path(order_id): Path<Uuid>,
+) -> ApiResult<Json<Vec<UploadBody>>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let attachments = Uploads::get_attachments(&pool, &files, &user, order_id).await?;
+
+    Ok(Json(attachments))
+}
+
+#[instrument(skip(pool, files))]
+pub async fn delete_attachment(
+    State(pool): State<DbPool>,
+    State(files): State<FileStore>,
+    user: ApiResult<LoggedInUser>,
+    Path((order_id, id)): Path<(Uuid, Uuid)>,
+) -> ApiResult<StatusCode> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    Uploads::delete_attachment(&pool, &files, &user, order_id, id).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[instrument(skip(pool, files))]
+pub async fn download(
+    State(pool): State<DbPool>,
+    State(files): State<FileStore>,
+    Path(id): Path<Uuid>,
+    ValidatedQuery(query): ValidatedQuery<DownloadQuery>,
+) -> ApiResult<impl IntoResponse> {
+    let download = Uploads::download(&pool, &files, id, query).await?;
+
+    Ok((
+        [
+            (CONTENT_TYPE, download.content_type().to_owned()),
+            (CONTENT_DISPOSITION, download.content_disposition()),
+            (CACHE_CONTROL, String::from("private, no-store")),
+            (X_CONTENT_TYPE_OPTIONS, String::from("nosniff")),
+        ],
+        download.bytes,
+    ))
+}
@@ -6,34 +6,101 @@ use axum::{
 use tracing::instrument;
 
 use crate::{
-    dtos::user::{EditUserEmailForm, EditUserForm, EditUserPasswordForm},
+    dtos::user::{
+        AdminUserBody, EditUserEmailForm, EditUserForm, EditUserPasswordForm, MeBody, PrivacyBody,
+        PrivacyForm, PublicProfile, UserBody, UserSort, UsersQuery,
+    },
     error::{ApiResult, Error},
-    extractors::{LoggedInUser, LoggedInUserId, ValidatedJson},
-    models::user::User,
-    services::edit::{Edit, TryEdit},
+    extractors::{AdminUser, LoggedInUser, LoggedInUserId, PageFilter, ValidatedJson},
+    pagination::{Page, Pagination},
+    services::{
+        account::Accounts,
+        edit::{Edit, TryEdit},
+        profile::Profiles,
+    },
     storage::{user, DbPool},
 };
 
-#[instrument]
-pub async fn me(user: ApiResult<LoggedInUser>) -> ApiResult<Json<User>> {
-    user.map(|LoggedInUser(u)| Json(u))
+#[instrument(skip(pool))]
+pub async fn me(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+) -> ApiResult<Json<MeBody>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let body = Profiles::me(&pool, user).await?;
+
+    Ok(Json(body))
 }
 
 #[instrument(skip(pool))]
-pub async fn get_all(State(pool): State<DbPool>) -> ApiResult<Json<Vec<User>>> {
-    let users = user::get_all(&pool).await.map_err(Error::from)?;
+pub async fn get_all(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    PageFilter(query): PageFilter<UsersQuery>,
+    pagination: Pagination<UserSort>,
+) -> ApiResult<Page<PublicProfile>> {
+    let user = user.map(|LoggedInUser(u)| u).ok();
+    let users = Profiles::get_page(&pool, user.as_ref(), query, &pagination).await?;
 
-    Ok(Json(users))
+    Ok(users)
 }
 
 #[instrument(skip(pool))]
 pub async fn get_by_username(
     State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
     Path(username): Path<String>,
-) -> ApiResult<Json<User>> {
-    let user = user::get_by_username(&pool, username)
-        .await
-        .map_err(Error::from)?;
+) -> ApiResult<Json<UserBody>> {
+    let user = user.map(|LoggedInUser(u)| u).ok();
+    let body = Profiles::get(&pool, user.as_ref(), username).await?;
+
+    Ok(Json(body))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_privacy(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+) -> ApiResult<Json<PrivacyBody>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let privacy = Profiles::get_privacy(&pool, &user).await?;
+
+    Ok(Json(privacy))
+}
+
+#[instrument(skip(pool))]
+pub async fn edit_privacy(
+    State(pool): State<DbPool>,
+    user: ApiResult<LoggedInUser>,
+    ValidatedJson(form): ValidatedJson<PrivacyForm>,
+) -> ApiResult<Json<PrivacyBody>> {
+    let user = user.map(|LoggedInUser(u)| u)?;
+    let privacy = Profiles::edit_privacy(&pool, &user, form).await?;
+
+    Ok(Json(privacy))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_all_as_admin(
+    State(pool): State<DbPool>,
+    admin: ApiResult<AdminUser>,
+    PageFilter(query): PageFilter<UsersQuery>,
+    pagination: Pagination<UserSort>,
+) -> ApiResult<Page<AdminUserBody>> {
+    admin?;
+    let users = Profiles::get_page_as_admin(&pool, query, &pagination).await?;
+
+    Ok(users)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_by_username_as_admin(
+    State(pool): State<DbPool>,
+    admin: ApiResult<AdminUser>,
+    Path(username): Path<String>,
+) -> ApiResult<Json<AdminUserBody>> {
+    admin?;
+    let user = Profiles::get_as_admin(&pool, username).await?;
 
     Ok(Json(user))
 }
@@ -85,7 +152,7 @@ pub async fn delete(
     id: ApiResult<LoggedInUserId>,
 ) -> ApiResult<StatusCode> {
     let id = id.map(|LoggedInUserId(id)| id)?;
-    user::delete(&pool, id).await.map_err(Error::from)?;
+    Accounts::delete(&pool, id).await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
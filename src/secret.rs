@@ -0,0 +1,38 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize, Serializer};
+use validator::HasLen;
+
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A value that must never show up in logs, spans or error details. Both `Debug` and
+/// `Serialize` print a placeholder, so it is safe to record whatever contains it.
+#[derive(Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+// Validators attach the offending value to their errors.
+impl<T> Serialize for Secret<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+impl HasLen for &Secret<String> {
+    fn length(&self) -> u64 {
+        self.0.chars().count() as u64
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use tracing::{error, info, instrument};
+use uuid::Uuid;
+
+use crate::{
+    auth::password,
+    blob::FileStore,
+    config::account::AccountPolicy,
+    dtos::auth::LoginForm,
+    error::{Error, Result},
+    services::upload::Uploads,
+    storage::{export, upload, user, DbPool},
+};
+
+const PURGE_BATCH: i64 = 100;
+
+pub struct Accounts;
+
+impl Accounts {
+    #[instrument(skip(pool))]
+    pub async fn delete(pool: &DbPool, id: Uuid) -> Result<()> {
+        let now = chrono::offset::Utc::now();
+        user::mark_deleted(pool, id, now).await?;
+        Ok(())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn restore(pool: &DbPool, policy: AccountPolicy, form: LoginForm) -> Result<Uuid> {
+        let user = user::get_by_username(pool, form.username).await?;
+        if !password::verify(form.password.expose(), &user.pwd_hash)? {
+            return Err(Error::WrongCredentials);
+        }
+        if user.deleted_at.is_none() {
+            return Err(Error::AccountNotDeleted);
+        }
+
+        let deleted_after = chrono::offset::Utc::now() - policy.grace_period();
+        if user::restore(pool, user.id, deleted_after).await? {
+            Ok(user.id)
+        } else {
+            Err(Error::RestorePeriodExpired)
+        }
+    }
+
+    /// Purges every account whose grace period has run out and returns how many were purged.
+    /// Accounts whose data exports can't be deleted yet are left for the next run.
+    #[instrument(skip(pool, files))]
+    pub async fn purge(pool: &DbPool, files: &FileStore, policy: AccountPolicy) -> Result<usize> {
+        let now = chrono::offset::Utc::now();
+        let mut purged = 0;
+        let mut postponed = HashSet::new();
+        loop {
+            let ids =
+                user::get_expired_deletions(pool, now - policy.grace_period(), PURGE_BATCH).await?;
+            if ids.iter().all(|id| postponed.contains(id)) {
+                return Ok(purged);
+            }
+            for id in ids {
+                if postponed.contains(&id) {
+                    continue;
+                }
+                if let Err(err) = Self::remove_exports(pool, files, id).await {
+                    error!("Failed to delete data exports of account {}: {}", id, err);
+                    postponed.insert(id);
+                    continue;
+                }
+                let avatar = upload::get_avatar(pool, id).await?;
+                user::purge(pool, id, now).await?;
+
+                if let Some(avatar) = avatar {
+                    Uploads::remove_blobs(files, &avatar).await;
+                }
+                purged += 1;
+            }
+        }
+    }
+
+    // Export rows go away with the account, so their archives have to be deleted first or
+    // nothing would ever find them again.
+    async fn remove_exports(pool: &DbPool, files: &FileStore, user_id: Uuid) -> Result<()> {
+        for key in export::get_storage_keys(pool, user_id).await? {
+            files.delete(&key).await?;
+        }
+        Ok(())
+    }
+
+    pub fn spawn_purge(pool: DbPool, files: FileStore, policy: AccountPolicy) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(policy.purge_interval());
+            loop {
+                interval.tick().await;
+                match Self::purge(&pool, &files, policy).await {
+                    Ok(purged) => info!("Purged {} deleted accounts", purged),
+                    Err(Error::Sqlx(sqlx::Error::PoolClosed)) => break,
+                    Err(err) => error!("Failed to purge deleted accounts: {}", err),
+                }
+            }
+        });
+    }
+}
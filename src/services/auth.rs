@@ -5,6 +5,7 @@ use crate::{
     auth::password,
     dtos::auth::{LoginForm, SignupForm},
     error::{Error, Result},
+    metrics,
     models::user::User,
     storage::{user, DbPool},
 };
@@ -15,7 +16,7 @@ impl Auth {
     #[instrument(skip(pool))]
     pub async fn signup(pool: &DbPool, form: SignupForm) -> Result<Uuid> {
         let id = Uuid::new_v4();
-        let pwd_hash = password::hash(&form.password)?;
+        let pwd_hash = password::hash(form.password.expose())?;
         let now = chrono::offset::Utc::now();
         let user = User {
             id,
@@ -27,21 +28,34 @@ impl Auth {
             age: None,
             about: None,
             verified: false,
+            is_admin: false,
             created_at: now,
             updated_at: now,
+            deleted_at: None,
+            purged_at: None,
+            language: None,
         };
         user::create(pool, user).await?;
+        metrics::record_signup();
         Ok(id)
     }
 
     #[instrument(skip(pool))]
     pub async fn login(pool: &DbPool, form: LoginForm) -> Result<Uuid> {
+        let result = Self::authenticate(pool, form).await;
+        metrics::record_login(result.is_ok());
+        result
+    }
+
+    async fn authenticate(pool: &DbPool, form: LoginForm) -> Result<Uuid> {
         let user = user::get_by_username(pool, form.username).await?;
 
-        if password::verify(&form.password, &user.pwd_hash)? {
-            Ok(user.id)
-        } else {
-            Err(Error::WrongCredentials)
+        if !password::verify(form.password.expose(), &user.pwd_hash)? {
+            return Err(Error::WrongCredentials);
+        }
+        if user.deleted_at.is_some() {
+            return Err(Error::AccountDeleted);
         }
+        Ok(user.id)
     }
 }
@@ -0,0 +1,159 @@
+use std::fmt::Write;
+
+use chrono::{DateTime, Duration, Utc};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::calendar::CalendarTokenBody,
+    error::Result,
+    models::{
+        order::{Order, OrderStatus},
+        session::{Session, SessionStatus},
+    },
+    storage::{calendar, order, session, DbPool},
+};
+
+const MAX_LINE_OCTETS: usize = 75;
+
+pub struct Calendars;
+
+impl Calendars {
+    #[instrument(skip(pool))]
+    pub async fn get_token(pool: &DbPool, user_id: Uuid) -> Result<CalendarTokenBody> {
+        match calendar::get_token(pool, user_id).await? {
+            Some(token) => Ok(CalendarTokenBody::new(token)),
+            None => Self::rotate_token(pool, user_id).await,
+        }
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn rotate_token(pool: &DbPool, user_id: Uuid) -> Result<CalendarTokenBody> {
+        let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        calendar::set_token(pool, user_id, &token).await?;
+        Ok(CalendarTokenBody::new(token))
+    }
+
+    #[instrument(skip_all)]
+    pub async fn feed(pool: &DbPool, token: &str) -> Result<String> {
+        let user = calendar::get_user_by_token(pool, token).await?;
+        let since = Utc::now() - Duration::days(30);
+        let orders = order::get_all_by_participant(pool, user.id).await?;
+        let sessions = session::get_for_user(pool, user.id, since, None).await?;
+
+        let mut ics = ICalendar::new();
+        for session in &sessions {
+            let title = orders
+                .iter()
+                .find(|order| order.id == session.order_id)
+                .map_or("Session", |order| order.title.as_str());
+            ics.session(session, title);
+        }
+        for order in &orders {
+            if matches!(order.deadline, Some(deadline) if deadline > since) {
+                ics.deadline(order);
+            }
+        }
+        Ok(ics.finish())
+    }
+}
+
+struct ICalendar(String);
+
+impl ICalendar {
+    fn new() -> Self {
+        let mut ics = Self(String::new());
+        ics.line("BEGIN:VCALENDAR");
+        ics.line("VERSION:2.0");
+        ics.line("PRODID:-//s4s//Sessions//EN");
+        ics.line("CALSCALE:GREGORIAN");
+        ics.line("METHOD:PUBLISH");
+        ics.line("X-WR-CALNAME:s4s");
+        ics
+    }
+
+    fn session(&mut self, session: &Session, title: &str) {
+        let status = if session.has_status(SessionStatus::Cancelled) {
+            "CANCELLED"
+        } else {
+            "CONFIRMED"
+        };
+        self.line("BEGIN:VEVENT");
+        self.line(&format!("UID:session-{}@s4s", session.id));
+        self.line(&format!("DTSTAMP:{}", Self::date_time(session.updated_at)));
+        self.line(&format!(
+            "LAST-MODIFIED:{}",
+            Self::date_time(session.updated_at)
+        ));
+        self.line(&format!("DTSTART:{}", Self::date_time(session.starts_at)));
+        self.line(&format!("DTEND:{}", Self::date_time(session.ends_at)));
+        self.line(&format!("SEQUENCE:{}", session.sequence));
+        self.line(&format!("STATUS:{status}"));
+        self.line(&format!("SUMMARY:{}", Self::text(title)));
+        if let Some(reason) = &session.cancellation_reason {
+            self.line(&format!("DESCRIPTION:{}", Self::text(reason)));
+        }
+        self.line("END:VEVENT");
+    }
+
+    fn deadline(&mut self, order: &Order) {
+        let Some(deadline) = order.deadline else {
+            return;
+        };
+        let status = if order.has_status(OrderStatus::Refunded) {
+            "CANCELLED"
+        } else {
+            "CONFIRMED"
+        };
+        self.line("BEGIN:VEVENT");
+        self.line(&format!("UID:order-{}-deadline@s4s", order.id));
+        self.line(&format!("DTSTAMP:{}", Self::date_time(order.updated_at)));
+        self.line(&format!(
+            "LAST-MODIFIED:{}",
+            Self::date_time(order.updated_at)
+        ));
+        self.line(&format!("DTSTART:{}", Self::date_time(deadline)));
+        self.line(&format!("STATUS:{status}"));
+        self.line(&format!(
+            "SUMMARY:{}",
+            Self::text(&format!("Deadline: {}", order.title))
+        ));
+        self.line("END:VEVENT");
+    }
+
+    fn finish(mut self) -> String {
+        self.line("END:VCALENDAR");
+        self.0
+    }
+
+    fn line(&mut self, line: &str) {
+        let mut octets = 0;
+        for c in line.chars() {
+            if octets + c.len_utf8() > MAX_LINE_OCTETS {
+                self.0.push_str("\r\n ");
+                octets = 1;
+            }
+            self.0.push(c);
+            octets += c.len_utf8();
+        }
+        self.0.push_str("\r\n");
+    }
+
+    fn date_time(at: DateTime<Utc>) -> String {
+        at.format("%Y%m%dT%H%M%SZ").to_string()
+    }
+
+    fn text(text: &str) -> String {
+        text.chars().fold(String::new(), |mut escaped, c| {
+            match c {
+                '\\' | ';' | ',' => {
+                    let _ = write!(escaped, "\\{c}");
+                }
+                '\n' => escaped.push_str("\\n"),
+                '\r' => {}
+                c => escaped.push(c),
+            }
+            escaped
+        })
+    }
+}
@@ -0,0 +1,225 @@
+use serde_json::json;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::dispute::{DisputeDetail, DisputeSort, OpenDisputeForm, ResolveDisputeForm},
+    error::{Error, Result},
+    models::{
+        dispute::{Dispute, DisputeOutcome, DisputeStatus},
+        notification::NotificationKind,
+        order::{Order, OrderStatus},
+        user::User,
+    },
+    pagination::{Page, Pagination},
+    services::{
+        event::{EventKind, Events},
+        notification::Notifications,
+    },
+    storage::{dispute, message, order, retry, DbPool, DbTransaction},
+};
+
+pub struct Disputes;
+
+impl Disputes {
+    #[instrument(skip(pool))]
+    pub async fn open(
+        pool: &DbPool,
+        user: &User,
+        order_id: Uuid,
+        form: OpenDisputeForm,
+    ) -> Result<Dispute> {
+        retry(|| Self::record_opening(pool, user, order_id, &form)).await
+    }
+
+    async fn record_opening(
+        pool: &DbPool,
+        user: &User,
+        order_id: Uuid,
+        form: &OpenDisputeForm,
+    ) -> Result<Dispute> {
+        let now = chrono::offset::Utc::now();
+        let mut tx = pool.begin().await?;
+        let order = order::get_by_id_for_update(&mut tx, order_id).await?;
+        if !order.is_participant(user.id) {
+            return Err(Error::Forbidden);
+        }
+        if !(order.has_status(OrderStatus::InProgress) || order.has_status(OrderStatus::Delivered))
+        {
+            return Err(Error::OrderNotDisputable);
+        }
+        let dispute = Dispute {
+            id: Uuid::new_v4(),
+            order_id,
+            opened_by: user.id,
+            reason: form.reason.clone(),
+            evidence: form.evidence.clone(),
+            status: DisputeStatus::Open.as_str().to_owned(),
+            outcome: None,
+            student_amount: None,
+            mentor_amount: None,
+            resolution_note: None,
+            resolved_by: None,
+            created_at: now,
+            resolved_at: None,
+        };
+        dispute::create(&mut tx, &dispute).await?;
+        order::set_status(
+            &mut tx,
+            order_id,
+            OrderStatus::Disputed.as_str(),
+            user.id,
+            now,
+        )
+        .await?;
+        Self::announce(&mut tx, &order, OrderStatus::Disputed, user.id).await?;
+        let payload = json!({ "order_id": order_id, "dispute_id": dispute.id });
+        for recipient in order
+            .participants()
+            .into_iter()
+            .filter(|&participant| participant != user.id)
+        {
+            Notifications::notify(
+                &mut tx,
+                recipient,
+                NotificationKind::DisputeOpened,
+                &payload,
+            )
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(dispute)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_for_order(pool: &DbPool, user: &User, order_id: Uuid) -> Result<Vec<Dispute>> {
+        let order = order::get_by_id(pool, order_id).await?;
+        if !(user.is_admin || order.is_participant(user.id)) {
+            return Err(Error::Forbidden);
+        }
+        Ok(dispute::get_all_by_order(pool, order_id).await?)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_queue(
+        pool: &DbPool,
+        resolved: bool,
+        pagination: &Pagination<DisputeSort>,
+    ) -> Result<Page<Dispute>> {
+        let status = if resolved {
+            DisputeStatus::Resolved
+        } else {
+            DisputeStatus::Open
+        };
+        let disputes =
+            dispute::get_page_by_status(pool, status.as_str(), &pagination.keyset()).await?;
+        Ok(pagination.page(disputes))
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_detail(pool: &DbPool, id: Uuid) -> Result<DisputeDetail> {
+        let dispute = dispute::get_by_id(pool, id).await?;
+        let order = order::get_by_id(pool, dispute.order_id).await?;
+        let history = order::get_history(pool, order.id).await?;
+        let disputes = dispute::get_all_by_order(pool, order.id).await?;
+        let messages = message::get_all_by_order(pool, order.id).await?;
+        Ok(DisputeDetail {
+            dispute,
+            order,
+            history,
+            disputes,
+            messages,
+        })
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn resolve(
+        pool: &DbPool,
+        admin: &User,
+        id: Uuid,
+        form: ResolveDisputeForm,
+    ) -> Result<Dispute> {
+        retry(|| Self::record_resolution(pool, admin, id, &form)).await
+    }
+
+    async fn record_resolution(
+        pool: &DbPool,
+        admin: &User,
+        id: Uuid,
+        form: &ResolveDisputeForm,
+    ) -> Result<Dispute> {
+        let now = chrono::offset::Utc::now();
+        let mut tx = pool.begin().await?;
+        let dispute = dispute::get_by_id_for_update(&mut tx, id).await?;
+        if dispute.status != DisputeStatus::Open.as_str() {
+            return Err(Error::DisputeResolved);
+        }
+        let order = order::get_by_id_for_update(&mut tx, dispute.order_id).await?;
+        let (student_amount, status) = match form.outcome {
+            DisputeOutcome::Release => (0, OrderStatus::Completed),
+            DisputeOutcome::Refund => (order.price, OrderStatus::Refunded),
+            DisputeOutcome::Split => (
+                form.student_amount
+                    .filter(|amount| (0..=order.price).contains(amount))
+                    .ok_or(Error::InvalidSplit)?,
+                OrderStatus::Completed,
+            ),
+        };
+        let dispute = Dispute {
+            status: DisputeStatus::Resolved.as_str().to_owned(),
+            outcome: Some(form.outcome.as_str().to_owned()),
+            student_amount: Some(student_amount),
+            mentor_amount: Some(order.price - student_amount),
+            resolution_note: form.note.clone(),
+            resolved_by: Some(admin.id),
+            resolved_at: Some(now),
+            ..dispute
+        };
+        dispute::resolve(&mut tx, &dispute).await?;
+        order::set_status(&mut tx, order.id, status.as_str(), admin.id, now).await?;
+        Self::announce(&mut tx, &order, status, admin.id).await?;
+        let payload =
+            json!({ "order_id": order.id, "dispute_id": dispute.id, "outcome": dispute.outcome });
+        for recipient in order.participants() {
+            Notifications::notify(
+                &mut tx,
+                recipient,
+                NotificationKind::DisputeResolved,
+                &payload,
+            )
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(dispute)
+    }
+
+    async fn announce(
+        tx: &mut DbTransaction<'_>,
+        order: &Order,
+        status: OrderStatus,
+        changed_by: Uuid,
+    ) -> Result<()> {
+        let payload = json!({ "order_id": order.id, "status": status.as_str() });
+        Events::publish(
+            tx,
+            &order.participants(),
+            EventKind::OrderStatusChanged,
+            &payload,
+        )
+        .await?;
+        for recipient in order
+            .participants()
+            .into_iter()
+            .filter(|&participant| participant != changed_by)
+        {
+            Notifications::notify(
+                tx,
+                recipient,
+                NotificationKind::OrderStatusChanged,
+                &payload,
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
@@ -25,6 +25,7 @@ impl Edit<EditUserForm> for User {
             last_name: other.last_name,
             age: other.age,
             about: other.about,
+            language: other.language,
             ..self
         }
     }
@@ -44,7 +45,7 @@ impl TryEdit<EditUserPasswordForm> for User {
 
     fn try_with(self, other: EditUserPasswordForm) -> ApiResult<Self> {
         Ok(User {
-            pwd_hash: password::hash(&other.password).map_err(Error::from)?,
+            pwd_hash: password::hash(other.password.expose()).map_err(Error::from)?,
             ..self
         })
     }
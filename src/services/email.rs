@@ -0,0 +1,49 @@
+use tracing::{instrument, warn};
+use uuid::Uuid;
+
+use crate::{
+    error::Result,
+    i18n,
+    models::{email::Email, notification::NotificationKind, user::User},
+    storage::{email, DbTransaction},
+};
+
+pub struct Emails;
+
+impl Emails {
+    // Emails are queued in an outbox table and handed over to the mail transport from there.
+    #[instrument(skip(tx))]
+    pub async fn enqueue_notification(
+        tx: &mut DbTransaction<'_>,
+        user: &User,
+        kind: NotificationKind,
+    ) -> Result<()> {
+        let locale = user
+            .language
+            .as_deref()
+            .and_then(|language| language.parse().ok())
+            .unwrap_or_default();
+        let text = |key: &str, args: &[(&str, String)]| {
+            i18n::translate(locale, key, args).unwrap_or_else(|| {
+                warn!("Missing email string {key}");
+                key.to_owned()
+            })
+        };
+        let kind = kind.as_str();
+        let email = Email {
+            id: Uuid::new_v4(),
+            address: user.email.clone(),
+            subject: text(&format!("email.{kind}.subject"), &[]),
+            body: format!(
+                "{}\n\n{}\n\n{}",
+                text("email.greeting", &[("username", user.username.clone())]),
+                text(&format!("email.{kind}.summary"), &[]),
+                text("email.footer", &[]),
+            ),
+            sent_at: None,
+            created_at: chrono::offset::Utc::now(),
+        };
+        email::create(tx, email).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use chrono::Duration as ChronoDuration;
+use serde::Serialize;
+use sqlx::{postgres::PgListener, Result as SqlxResult};
+use tokio::sync::broadcast::{self, Receiver, Sender};
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+
+use crate::{
+    error::Result,
+    models::event::Event,
+    storage::{event, DbPool, DbTransaction},
+};
+
+const CHANNEL: &str = "events";
+const CAPACITY: usize = 1024;
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const RETENTION_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy)]
+pub enum EventKind {
+    MessageCreated,
+    MessageEdited,
+    MessageDeleted,
+    MessagesRead,
+    NotificationCreated,
+    OrderStatusChanged,
+}
+
+impl EventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MessageCreated => "message.created",
+            Self::MessageEdited => "message.edited",
+            Self::MessageDeleted => "message.deleted",
+            Self::MessagesRead => "messages.read",
+            Self::NotificationCreated => "notification.created",
+            Self::OrderStatusChanged => "order.status_changed",
+        }
+    }
+}
+
+pub struct Events;
+
+impl Events {
+    /// Subscribers only hear about the event once `tx` commits, so a rolled back write is
+    /// never announced and a committed one always is.
+    #[instrument(skip(tx, payload))]
+    pub async fn publish<T: Serialize>(
+        tx: &mut DbTransaction<'_>,
+        user_ids: &[Uuid],
+        kind: EventKind,
+        payload: &T,
+    ) -> Result<()> {
+        let payload = serde_json::to_value(payload)?;
+        let now = chrono::offset::Utc::now();
+        event::create(tx, user_ids, kind.as_str(), payload, now).await?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EventHub {
+    sender: Sender<Arc<Event>>,
+    listening: Arc<AtomicBool>,
+}
+
+impl EventHub {
+    pub fn spawn(pool: DbPool) -> Self {
+        let (sender, _) = broadcast::channel(CAPACITY);
+        let listening = Arc::new(AtomicBool::new(false));
+        tokio::spawn(Self::listen(
+            pool.clone(),
+            sender.clone(),
+            listening.clone(),
+        ));
+        tokio::spawn(Self::purge(pool));
+        Self { sender, listening }
+    }
+
+    pub fn subscribe(&self) -> Receiver<Arc<Event>> {
+        self.sender.subscribe()
+    }
+
+    /// Whether events published by other instances currently reach this one.
+    pub fn is_listening(&self) -> bool {
+        self.listening.load(Ordering::Relaxed)
+    }
+
+    async fn listen(pool: DbPool, sender: Sender<Arc<Event>>, listening: Arc<AtomicBool>) {
+        while let Err(err) = Self::relay(&pool, &sender, &listening).await {
+            listening.store(false, Ordering::Relaxed);
+            if let sqlx::Error::PoolClosed = err {
+                break;
+            }
+            error!("Event listener failed: {}", err);
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+
+    // Clients resuming from an event older than the retention window only get what is left.
+    async fn purge(pool: DbPool) {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let before = chrono::offset::Utc::now() - ChronoDuration::days(RETENTION_DAYS);
+            match event::delete_before(&pool, before).await {
+                Ok(purged) => info!("Purged {} expired events", purged),
+                Err(sqlx::Error::PoolClosed) => break,
+                Err(err) => error!("Failed to purge events: {}", err),
+            }
+        }
+    }
+
+    async fn relay(
+        pool: &DbPool,
+        sender: &Sender<Arc<Event>>,
+        listening: &AtomicBool,
+    ) -> SqlxResult<()> {
+        let mut listener = PgListener::connect_with(pool).await?;
+        listener.listen(CHANNEL).await?;
+        listening.store(true, Ordering::Relaxed);
+
+        loop {
+            let notification = listener.recv().await?;
+            let Ok(id) = notification.payload().parse::<i64>() else {
+                warn!("Malformed event notification: {}", notification.payload());
+                continue;
+            };
+            match event::get_by_id(pool, id).await {
+                // Having no subscribers on this instance is not an error.
+                Ok(event) => drop(sender.send(Arc::new(event))),
+                Err(sqlx::Error::RowNotFound) => warn!("Event {} vanished before relay", id),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::io::{Cursor, Write};
+
+use serde_json::Value;
+use tracing::{error, info, instrument, warn};
+use uuid::Uuid;
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    blob::FileStore,
+    config::export::ExportPolicy,
+    dtos::export::{ExportBody, ExportDownload},
+    error::{Error, Result},
+    models::{
+        export::{DataExport, ExportStatus},
+        user::User,
+    },
+    storage::{export, DbPool},
+};
+
+pub struct Exports;
+
+impl Exports {
+    #[instrument(skip(pool, files))]
+    pub async fn request(
+        pool: &DbPool,
+        files: &FileStore,
+        policy: ExportPolicy,
+        user: &User,
+    ) -> Result<ExportBody> {
+        let now = chrono::offset::Utc::now();
+        if export::count_since(pool, user.id, now - policy.window()).await? >= policy.max_requests()
+        {
+            return Err(Error::TooManyExports);
+        }
+
+        let pending = DataExport {
+            id: Uuid::new_v4(),
+            user_id: user.id,
+            status: ExportStatus::Pending.as_str().to_owned(),
+            storage_key: None,
+            size: None,
+            created_at: now,
+            completed_at: None,
+            expires_at: None,
+        };
+        export::create(pool, &pending).await?;
+
+        tokio::spawn(Self::build(
+            pool.clone(),
+            files.clone(),
+            policy,
+            pending.clone(),
+        ));
+        Ok(pending.into())
+    }
+
+    #[instrument(skip(pool, files))]
+    pub async fn download(
+        pool: &DbPool,
+        files: &FileStore,
+        user: &User,
+        id: Uuid,
+    ) -> Result<ExportDownload> {
+        let export = export::get_by_id(pool, id, user.id).await?;
+        let now = chrono::offset::Utc::now();
+
+        match export.storage_key.as_deref() {
+            _ if export.has_status(ExportStatus::Pending) => {
+                Ok(ExportDownload::Pending(export.into()))
+            }
+            _ if export.has_status(ExportStatus::Failed) => Err(Error::ExportFailed),
+            Some(key)
+                if export
+                    .expires_at
+                    .map_or(true, |expires_at| expires_at > now) =>
+            {
+                Ok(ExportDownload::Ready {
+                    file_name: export.file_name(),
+                    bytes: files.get(key).await?,
+                })
+            }
+            _ => Err(Error::ExportExpired),
+        }
+    }
+
+    /// Gives up on builds that were lost along with the instance running them, then deletes
+    /// archives past their expiry and returns how many were removed. Archives that can't be
+    /// deleted are left for the next run.
+    #[instrument(skip(pool, files))]
+    pub async fn cleanup(pool: &DbPool, files: &FileStore, policy: ExportPolicy) -> Result<usize> {
+        let now = chrono::offset::Utc::now();
+        let abandoned =
+            export::fail_pending_before(pool, now - policy.build_timeout(), now).await?;
+        if abandoned > 0 {
+            warn!("Gave up on {} unfinished data exports", abandoned);
+        }
+
+        let expired = export::get_expired(pool, now).await?;
+        let mut removed = 0;
+        for export in &expired {
+            if let Some(key) = &export.storage_key {
+                if let Err(err) = files.delete(key).await {
+                    error!("Failed to delete data export {}: {}", export.id, err);
+                    continue;
+                }
+            }
+            export::expire(pool, export.id).await?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    // The first tick fires right away, so exports left pending by a crash are settled on
+    // startup.
+    pub fn spawn_cleanup(pool: DbPool, files: FileStore, policy: ExportPolicy) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(policy.cleanup_interval());
+            loop {
+                interval.tick().await;
+                match Self::cleanup(&pool, &files, policy).await {
+                    Ok(removed) => info!("Removed {} expired data exports", removed),
+                    Err(Error::Sqlx(sqlx::Error::PoolClosed)) => break,
+                    Err(err) => error!("Failed to remove expired data exports: {}", err),
+                }
+            }
+        });
+    }
+
+    async fn build(pool: DbPool, files: FileStore, policy: ExportPolicy, pending: DataExport) {
+        let id = pending.id;
+        let result = match Self::archive(&pool, &files, policy, pending).await {
+            Ok(export) => export::complete(&pool, &export).await,
+            Err(err) => {
+                error!("Failed to build data export {id}: {err}");
+                export::fail(&pool, id, chrono::offset::Utc::now()).await
+            }
+        };
+        if let Err(err) = result {
+            error!("Failed to record data export {id}: {err}");
+        }
+    }
+
+    async fn archive(
+        pool: &DbPool,
+        files: &FileStore,
+        policy: ExportPolicy,
+        pending: DataExport,
+    ) -> Result<DataExport> {
+        let data = export::collect(pool, pending.user_id).await?;
+        let bytes = Self::zip(data)?;
+        let key = format!("exports/{}/{}.zip", pending.user_id, pending.id);
+        let size = i64::try_from(bytes.len()).unwrap_or(i64::MAX);
+        files.put(&key, "application/zip", bytes).await?;
+
+        let now = chrono::offset::Utc::now();
+        Ok(DataExport {
+            status: ExportStatus::Ready.as_str().to_owned(),
+            storage_key: Some(key),
+            size: Some(size),
+            completed_at: Some(now),
+            expires_at: Some(now + policy.retention()),
+            ..pending
+        })
+    }
+
+    fn zip(data: Value) -> Result<Vec<u8>> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        if let Value::Object(entities) = data {
+            for (name, value) in entities {
+                writer.start_file(format!("{name}.json"), options)?;
+                writer.write_all(&serde_json::to_vec_pretty(&value)?)?;
+            }
+        }
+        Ok(writer.finish()?.into_inner())
+    }
+}
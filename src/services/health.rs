@@ -0,0 +1,177 @@
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use sqlx::migrate::Migrator;
+use tracing::{instrument, warn};
+
+use crate::{
+    blob::FileStore,
+    config::health::HealthPolicy,
+    dtos::health::{CheckBody, CheckStatus, HealthBody, HealthStatus},
+    services::event::EventHub,
+    storage::{health, DbPool},
+};
+
+static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Shared between the server and its shutdown handler so readiness can be withdrawn
+/// before connections are drained.
+#[derive(Debug, Clone, Default)]
+pub struct Lifecycle {
+    draining: Arc<AtomicBool>,
+}
+
+impl Lifecycle {
+    pub fn drain(&self) {
+        self.draining.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+}
+
+type CheckResult = std::result::Result<Option<String>, CheckFailure>;
+
+/// Readiness is public, so only `summary` goes into the response; `cause` may contain
+/// hostnames, paths or driver messages and is only logged.
+struct CheckFailure {
+    summary: &'static str,
+    cause: String,
+}
+
+impl CheckFailure {
+    fn new(summary: &'static str, cause: String) -> Self {
+        Self { summary, cause }
+    }
+}
+
+pub struct Health;
+
+impl Health {
+    pub fn live() -> HealthBody {
+        HealthBody {
+            status: HealthStatus::Ok,
+            checks: BTreeMap::new(),
+        }
+    }
+
+    #[instrument(skip_all)]
+    pub async fn ready(
+        pool: &DbPool,
+        files: &FileStore,
+        events: &EventHub,
+        policy: HealthPolicy,
+        lifecycle: &Lifecycle,
+    ) -> HealthBody {
+        let timeout = policy.check_timeout();
+        let (database, migrations, files, events) = tokio::join!(
+            Self::timed("database", timeout, Self::database(pool)),
+            Self::timed("migrations", timeout, Self::migrations(pool)),
+            Self::timed("files", timeout, Self::files(files)),
+            Self::timed("events", timeout, async { Self::events(events) }),
+        );
+        let checks = BTreeMap::from([
+            ("database", database),
+            ("migrations", migrations),
+            ("files", files),
+            ("events", events),
+        ]);
+
+        let status = if lifecycle.is_draining() {
+            HealthStatus::Draining
+        } else if checks.values().all(|check| check.status == CheckStatus::Ok) {
+            HealthStatus::Ok
+        } else {
+            HealthStatus::Unavailable
+        };
+        HealthBody { status, checks }
+    }
+
+    async fn database(pool: &DbPool) -> CheckResult {
+        health::ping(pool)
+            .await
+            .map_err(|err| CheckFailure::new("database is unreachable", err.to_string()))?;
+        Ok(None)
+    }
+
+    async fn migrations(pool: &DbPool) -> CheckResult {
+        let applied = health::applied_migrations(pool)
+            .await
+            .map_err(|err| CheckFailure::new("migrations could not be read", err.to_string()))?;
+        let pending = MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .filter(|migration| !applied.contains(&migration.version))
+            .map(|migration| migration.version.to_string())
+            .collect::<Vec<_>>();
+        if pending.is_empty() {
+            Ok(applied
+                .last()
+                .map(|version| format!("at version {version}")))
+        } else {
+            Err(CheckFailure::new(
+                "migrations are pending",
+                format!("pending migrations: {}", pending.join(", ")),
+            ))
+        }
+    }
+
+    async fn files(files: &FileStore) -> CheckResult {
+        files
+            .probe()
+            .await
+            .map_err(|err| CheckFailure::new("file store is unavailable", err.to_string()))?;
+        Ok(None)
+    }
+
+    fn events(events: &EventHub) -> CheckResult {
+        if events.is_listening() {
+            Ok(None)
+        } else {
+            Err(CheckFailure::new(
+                "event listener is not connected",
+                "event listener is not connected".to_owned(),
+            ))
+        }
+    }
+
+    async fn timed(
+        name: &str,
+        timeout: Duration,
+        check: impl Future<Output = CheckResult>,
+    ) -> CheckBody {
+        let started = Instant::now();
+        let result = tokio::time::timeout(timeout, check)
+            .await
+            .unwrap_or_else(|_| {
+                Err(CheckFailure::new(
+                    "timed out",
+                    format!("timed out after {}ms", timeout.as_millis()),
+                ))
+            });
+        let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+        match result {
+            Ok(detail) => CheckBody {
+                status: CheckStatus::Ok,
+                duration_ms,
+                detail,
+            },
+            Err(failure) => {
+                warn!("Readiness check {} failed: {}", name, failure.cause);
+                CheckBody {
+                    status: CheckStatus::Failed,
+                    duration_ms,
+                    detail: Some(failure.summary.to_owned()),
+                }
+            }
+        }
+    }
+}
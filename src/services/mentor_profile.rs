@@ -0,0 +1,131 @@
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::mentor_profile::{MentorProfileBody, MentorProfileForm, MentorSort, MentorsQuery},
+    error::{Error, Result},
+    models::mentor_profile::{
+        AvailabilityException, AvailabilitySlot, MentorListing, MentorProfile,
+    },
+    pagination::{Page, Pagination},
+    services::subject::Subjects,
+    storage::{mentor_profile, subject, DbPool},
+};
+
+pub struct MentorProfiles;
+
+impl MentorProfiles {
+    #[instrument(skip(pool))]
+    pub async fn get(pool: &DbPool, user_id: Uuid) -> Result<Option<MentorProfileBody>> {
+        let Some(profile) = mentor_profile::get_by_user(pool, user_id).await? else {
+            return Ok(None);
+        };
+        let subjects = subject::get_by_user(pool, user_id).await?;
+        let availability = mentor_profile::get_availability(pool, user_id).await?;
+        let exceptions = mentor_profile::get_exceptions(pool, user_id).await?;
+
+        Ok(Some(MentorProfileBody {
+            profile,
+            subjects,
+            availability,
+            exceptions,
+        }))
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_own(pool: &DbPool, user_id: Uuid) -> Result<MentorProfileBody> {
+        Self::get(pool, user_id)
+            .await?
+            .ok_or(Error::NotFound(sqlx::Error::RowNotFound))
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn edit(
+        pool: &DbPool,
+        user_id: Uuid,
+        form: MentorProfileForm,
+    ) -> Result<MentorProfileBody> {
+        if form
+            .availability
+            .iter()
+            .any(|slot| slot.start_time >= slot.end_time)
+            || form
+                .exceptions
+                .iter()
+                .any(|exception| exception.starts_at >= exception.ends_at)
+        {
+            return Err(Error::InvalidTimeRange);
+        }
+        Subjects::ensure_exist(pool, &form.subject_ids).await?;
+
+        let now = chrono::offset::Utc::now();
+        let profile = MentorProfile {
+            user_id,
+            headline: form.headline,
+            hourly_rate: form.hourly_rate,
+            languages: form.languages,
+            education: form.education,
+            time_zone: form.time_zone,
+            created_at: now,
+            updated_at: now,
+        };
+        let availability: Vec<_> = form
+            .availability
+            .into_iter()
+            .map(|slot| AvailabilitySlot {
+                id: Uuid::new_v4(),
+                weekday: slot.weekday,
+                start_time: slot.start_time,
+                end_time: slot.end_time,
+            })
+            .collect();
+        let exceptions: Vec<_> = form
+            .exceptions
+            .into_iter()
+            .map(|exception| AvailabilityException {
+                id: Uuid::new_v4(),
+                starts_at: exception.starts_at,
+                ends_at: exception.ends_at,
+                reason: exception.reason,
+            })
+            .collect();
+
+        let mut tx = pool.begin().await?;
+        mentor_profile::upsert(&mut tx, &profile).await?;
+        mentor_profile::set_availability(&mut tx, user_id, &availability).await?;
+        mentor_profile::set_exceptions(&mut tx, user_id, &exceptions).await?;
+        subject::set_for_user(&mut tx, user_id, &form.subject_ids).await?;
+        tx.commit().await?;
+
+        Self::get_own(pool, user_id).await
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn delete(pool: &DbPool, user_id: Uuid) -> Result<()> {
+        mentor_profile::delete(pool, user_id).await?;
+        Ok(())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn search(
+        pool: &DbPool,
+        query: MentorsQuery,
+        pagination: &Pagination<MentorSort>,
+    ) -> Result<Page<MentorListing>> {
+        match (query.available_from, query.available_to) {
+            (Some(from), Some(to)) if from >= to => return Err(Error::InvalidTimeRange),
+            (Some(_), None) | (None, Some(_)) => return Err(Error::IncompleteTimeRange),
+            _ => {}
+        }
+        let mentors = mentor_profile::search(
+            pool,
+            query.subject,
+            query.available_from,
+            query.available_to,
+            query.max_rate,
+            &pagination.keyset(),
+        )
+        .await?;
+        Ok(pagination.page(mentors))
+    }
+}
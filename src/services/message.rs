@@ -0,0 +1,187 @@
+use chrono::Duration;
+use serde_json::json;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::message::{MessageForm, MessageSort},
+    error::{Error, Result},
+    models::{message::Message, notification::NotificationKind, order::Order, user::User},
+    pagination::{Page, Pagination},
+    services::{
+        event::{EventKind, Events},
+        notification::Notifications,
+    },
+    storage::{message, order, DbPool},
+};
+
+const EDIT_WINDOW_MINUTES: i64 = 15;
+const DELETE_WINDOW_MINUTES: i64 = 60;
+
+pub struct Messages;
+
+impl Messages {
+    #[instrument(skip(pool))]
+    pub async fn get_page(
+        pool: &DbPool,
+        user: &User,
+        order_id: Uuid,
+        pagination: &Pagination<MessageSort>,
+    ) -> Result<Page<Message>> {
+        Self::authorize(pool, user, order_id).await?;
+        let messages = message::get_page(pool, order_id, &pagination.keyset()).await?;
+        Ok(pagination.page(messages))
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn create(
+        pool: &DbPool,
+        user: &User,
+        order_id: Uuid,
+        form: MessageForm,
+    ) -> Result<Message> {
+        let order = Self::authorize(pool, user, order_id).await?;
+        let id = Uuid::new_v4();
+        let message = Message {
+            id,
+            order_id,
+            sender_id: user.id,
+            body: form.body,
+            read_at: None,
+            edited_at: None,
+            deleted_at: None,
+            created_at: chrono::offset::Utc::now(),
+        };
+        let mut tx = pool.begin().await?;
+        let message = message::create(&mut tx, message).await?;
+        Events::publish(
+            &mut tx,
+            &order.participants(),
+            EventKind::MessageCreated,
+            &message,
+        )
+        .await?;
+        let payload = json!({ "order_id": order_id, "message_id": id, "sender_id": user.id });
+        for recipient in order
+            .participants()
+            .into_iter()
+            .filter(|&participant| participant != user.id)
+        {
+            Notifications::notify(&mut tx, recipient, NotificationKind::NewMessage, &payload)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(message)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn edit(
+        pool: &DbPool,
+        user: &User,
+        order_id: Uuid,
+        message_id: Uuid,
+        form: MessageForm,
+    ) -> Result<Message> {
+        let (order, message) = Self::get_own(pool, user, order_id, message_id).await?;
+        let now = chrono::offset::Utc::now();
+        if now - message.created_at > Duration::minutes(EDIT_WINDOW_MINUTES) {
+            return Err(Error::MessageLocked);
+        }
+        let message = Message {
+            body: form.body,
+            edited_at: Some(now),
+            ..message
+        };
+        let mut tx = pool.begin().await?;
+        let message = message::edit(&mut tx, message).await?;
+        Events::publish(
+            &mut tx,
+            &order.participants(),
+            EventKind::MessageEdited,
+            &message,
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(message)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn delete(
+        pool: &DbPool,
+        user: &User,
+        order_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<()> {
+        let now = chrono::offset::Utc::now();
+        let order = if user.is_admin {
+            let message = message::get_by_id(pool, message_id).await?;
+            if message.order_id != order_id {
+                return Err(Error::NotFound(sqlx::Error::RowNotFound));
+            }
+            order::get_by_id(pool, order_id).await?
+        } else {
+            let (order, message) = Self::get_own(pool, user, order_id, message_id).await?;
+            if now - message.created_at > Duration::minutes(DELETE_WINDOW_MINUTES) {
+                return Err(Error::MessageLocked);
+            }
+            order
+        };
+        let mut tx = pool.begin().await?;
+        message::delete(&mut tx, message_id, now).await?;
+        Events::publish(
+            &mut tx,
+            &order.participants(),
+            EventKind::MessageDeleted,
+            &json!({ "id": message_id, "order_id": order_id }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn mark_read(pool: &DbPool, user: &User, order_id: Uuid) -> Result<()> {
+        let order = Self::authorize(pool, user, order_id).await?;
+        if !order.is_participant(user.id) {
+            return Err(Error::Forbidden);
+        }
+        let now = chrono::offset::Utc::now();
+        let mut tx = pool.begin().await?;
+        message::mark_read(&mut tx, order_id, user.id, now).await?;
+        Events::publish(
+            &mut tx,
+            &order.participants(),
+            EventKind::MessagesRead,
+            &json!({ "order_id": order_id, "reader_id": user.id, "read_at": now }),
+        )
+        .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn authorize(pool: &DbPool, user: &User, order_id: Uuid) -> Result<Order> {
+        let order = order::get_by_id(pool, order_id).await?;
+        if user.is_admin || order.is_participant(user.id) {
+            Ok(order)
+        } else {
+            Err(Error::Forbidden)
+        }
+    }
+
+    async fn get_own(
+        pool: &DbPool,
+        user: &User,
+        order_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<(Order, Message)> {
+        let order = Self::authorize(pool, user, order_id).await?;
+        let message = message::get_by_id(pool, message_id).await?;
+        if message.order_id != order_id || message.deleted_at.is_some() {
+            return Err(Error::NotFound(sqlx::Error::RowNotFound));
+        }
+        if message.sender_id != user.id {
+            return Err(Error::Forbidden);
+        }
+        Ok((order, message))
+    }
+}
@@ -1,2 +1,19 @@
+pub mod account;
 pub mod auth;
+pub mod calendar;
+pub mod dispute;
 pub mod edit;
+pub mod email;
+pub mod event;
+pub mod export;
+pub mod health;
+pub mod mentor_profile;
+pub mod message;
+pub mod notification;
+pub mod order;
+pub mod profile;
+pub mod realtime;
+pub mod search;
+pub mod session;
+pub mod subject;
+pub mod upload;
@@ -0,0 +1,116 @@
+use serde::Serialize;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::notification::{EditNotificationPreferencesForm, NotificationSort, NotificationsQuery},
+    error::{Error, Result},
+    models::notification::{Notification, NotificationKind, NotificationPreference},
+    pagination::{Page, Pagination},
+    services::{
+        email::Emails,
+        event::{EventKind, Events},
+    },
+    storage::{notification, notification_preference, user, DbPool, DbTransaction},
+};
+
+pub struct Notifications;
+
+impl Notifications {
+    #[instrument(skip(tx, payload))]
+    pub async fn notify<T: Serialize>(
+        tx: &mut DbTransaction<'_>,
+        user_id: Uuid,
+        kind: NotificationKind,
+        payload: &T,
+    ) -> Result<()> {
+        let notification = Notification {
+            id: Uuid::new_v4(),
+            user_id,
+            kind: kind.as_str().to_owned(),
+            payload: serde_json::to_value(payload)?,
+            read_at: None,
+            created_at: chrono::offset::Utc::now(),
+        };
+        notification::create(tx, &notification).await?;
+        Events::publish(
+            tx,
+            &[user_id],
+            EventKind::NotificationCreated,
+            &notification,
+        )
+        .await?;
+
+        let email = notification_preference::get_email(tx, user_id, kind.as_str()).await?;
+        if email.unwrap_or_else(|| kind.emails_by_default()) {
+            let user = user::get_by_id_for_share(tx, user_id).await?;
+            Emails::enqueue_notification(tx, &user, kind).await?;
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_page(
+        pool: &DbPool,
+        user_id: Uuid,
+        query: NotificationsQuery,
+        pagination: &Pagination<NotificationSort>,
+    ) -> Result<Page<Notification>> {
+        let notifications =
+            notification::get_page(pool, user_id, query.unread, &pagination.keyset()).await?;
+        Ok(pagination.page(notifications))
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn mark_read(pool: &DbPool, user_id: Uuid, id: Uuid) -> Result<()> {
+        let notification = notification::get_by_id(pool, id).await?;
+        if notification.user_id != user_id {
+            return Err(Error::NotFound(sqlx::Error::RowNotFound));
+        }
+        notification::mark_read(pool, id, chrono::offset::Utc::now()).await?;
+        Ok(())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn mark_all_read(pool: &DbPool, user_id: Uuid) -> Result<()> {
+        notification::mark_all_read(pool, user_id, chrono::offset::Utc::now()).await?;
+        Ok(())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_preferences(
+        pool: &DbPool,
+        user_id: Uuid,
+    ) -> Result<Vec<NotificationPreference>> {
+        let stored = notification_preference::get_all(pool, user_id).await?;
+        let preferences = NotificationKind::ALL
+            .iter()
+            .map(|kind| NotificationPreference {
+                kind: kind.as_str().to_owned(),
+                email: stored
+                    .iter()
+                    .find(|p| p.kind == kind.as_str())
+                    .map_or_else(|| kind.emails_by_default(), |p| p.email),
+            })
+            .collect();
+        Ok(preferences)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn edit_preferences(
+        pool: &DbPool,
+        user_id: Uuid,
+        form: EditNotificationPreferencesForm,
+    ) -> Result<Vec<NotificationPreference>> {
+        for preference in form.preferences {
+            notification_preference::upsert(
+                pool,
+                user_id,
+                preference.kind.as_str(),
+                preference.email,
+            )
+            .await?;
+        }
+        Self::get_preferences(pool, user_id).await
+    }
+}
@@ -0,0 +1,92 @@
+use serde_json::json;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::order::{CreateOrderForm, OrderBody, OrderSort, OrdersQuery},
+    error::{Error, Result},
+    metrics,
+    models::{
+        notification::NotificationKind,
+        order::{Order, OrderStatus},
+        user::User,
+    },
+    pagination::{Page, Pagination},
+    services::{notification::Notifications, subject::Subjects},
+    storage::{order, subject, user, DbPool},
+};
+
+pub struct Orders;
+
+impl Orders {
+    #[instrument(skip(pool))]
+    pub async fn create(pool: &DbPool, student: &User, form: CreateOrderForm) -> Result<OrderBody> {
+        let mentor = user::get_active_by_username(pool, form.mentor).await?;
+        if mentor.id == student.id {
+            return Err(Error::SelfOrder);
+        }
+        Subjects::ensure_exist(pool, &form.subject_ids).await?;
+
+        let id = Uuid::new_v4();
+        let now = chrono::offset::Utc::now();
+        if matches!(form.deadline, Some(deadline) if deadline <= now) {
+            return Err(Error::DeadlineInPast);
+        }
+        let order = Order {
+            id,
+            student_id: student.id,
+            mentor_id: mentor.id,
+            price: form.price,
+            title: form.title,
+            description: form.description,
+            status: OrderStatus::InProgress.as_str().to_owned(),
+            deadline: form.deadline,
+            language: form.language,
+            created_at: now,
+            updated_at: now,
+        };
+        let mut tx = pool.begin().await?;
+        order::create(&mut tx, &order).await?;
+        subject::set_for_order(&mut tx, id, &form.subject_ids).await?;
+        Notifications::notify(
+            &mut tx,
+            mentor.id,
+            NotificationKind::OrderCreated,
+            &json!({ "order_id": id }),
+        )
+        .await?;
+        tx.commit().await?;
+        metrics::record_order_created();
+
+        Self::get(pool, student, id).await
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_page(
+        pool: &DbPool,
+        user: &User,
+        query: OrdersQuery,
+        pagination: &Pagination<OrderSort>,
+    ) -> Result<Page<Order>> {
+        let participant_id = (!user.is_admin).then_some(user.id);
+        let orders = order::get_page(
+            pool,
+            participant_id,
+            query.subject,
+            query.status.map(OrderStatus::as_str),
+            &pagination.keyset(),
+        )
+        .await?;
+        Ok(pagination.page(orders))
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get(pool: &DbPool, user: &User, id: Uuid) -> Result<OrderBody> {
+        let order = order::get_by_id(pool, id).await?;
+        if !(user.is_admin || order.is_participant(user.id)) {
+            return Err(Error::Forbidden);
+        }
+        let subjects = subject::get_by_order(pool, id).await?;
+        Ok(OrderBody { order, subjects })
+    }
+}
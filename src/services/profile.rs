@@ -0,0 +1,149 @@
+use tracing::instrument;
+
+use crate::{
+    dtos::user::{
+        AdminUserBody, MeBody, PrivacyBody, PrivacyForm, PublicProfile, UserBody, UserSort,
+        UsersQuery,
+    },
+    error::Result,
+    models::{
+        privacy::{Audience, PrivacySettings},
+        user::User,
+    },
+    pagination::{Page, Pagination},
+    services::mentor_profile::MentorProfiles,
+    storage::{message, privacy, user, DbPool},
+};
+
+pub struct Profiles;
+
+impl Profiles {
+    #[instrument(skip(pool))]
+    pub async fn me(pool: &DbPool, user: User) -> Result<MeBody> {
+        let unread = message::count_unread(pool, user.id).await?;
+        let privacy = Self::privacy(pool, &user).await?;
+
+        Ok(MeBody {
+            profile: user.into(),
+            privacy: privacy.into(),
+            unread_messages: unread.into(),
+        })
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_page(
+        pool: &DbPool,
+        viewer: Option<&User>,
+        query: UsersQuery,
+        pagination: &Pagination<UserSort>,
+    ) -> Result<Page<PublicProfile>> {
+        let users = user::get_page(
+            pool,
+            query.subject,
+            query.verified,
+            false,
+            &pagination.keyset(),
+        )
+        .await?;
+        let page = pagination.page(users);
+        let settings = Self::privacy_for(pool, page.items()).await?;
+
+        Ok(page.map(|user| Self::public(viewer, user, &settings)))
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get(pool: &DbPool, viewer: Option<&User>, username: String) -> Result<UserBody> {
+        let user = user::get_active_by_username(pool, username).await?;
+        let privacy = Self::privacy(pool, &user).await?;
+        let mentor_profile = MentorProfiles::get(pool, user.id).await?;
+        let audience = Audience::of(viewer, user.id);
+
+        Ok(UserBody {
+            profile: PublicProfile::new(user, &privacy, audience),
+            mentor_profile,
+        })
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn publish(
+        pool: &DbPool,
+        viewer: Option<&User>,
+        users: Vec<User>,
+    ) -> Result<Vec<PublicProfile>> {
+        let settings = Self::privacy_for(pool, &users).await?;
+
+        Ok(users
+            .into_iter()
+            .map(|user| Self::public(viewer, user, &settings))
+            .collect())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_page_as_admin(
+        pool: &DbPool,
+        query: UsersQuery,
+        pagination: &Pagination<UserSort>,
+    ) -> Result<Page<AdminUserBody>> {
+        let users = user::get_page(
+            pool,
+            query.subject,
+            query.verified,
+            true,
+            &pagination.keyset(),
+        )
+        .await?;
+
+        Ok(pagination.page(users).map(AdminUserBody::from))
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_as_admin(pool: &DbPool, username: String) -> Result<AdminUserBody> {
+        Ok(user::get_by_username(pool, username).await?.into())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_privacy(pool: &DbPool, user: &User) -> Result<PrivacyBody> {
+        Ok(Self::privacy(pool, user).await?.into())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn edit_privacy(
+        pool: &DbPool,
+        user: &User,
+        form: PrivacyForm,
+    ) -> Result<PrivacyBody> {
+        let settings = PrivacySettings {
+            user_id: user.id,
+            name: form.name.as_str().to_owned(),
+            email: form.email.as_str().to_owned(),
+            age: form.age.as_str().to_owned(),
+            about: form.about.as_str().to_owned(),
+            updated_at: chrono::offset::Utc::now(),
+        };
+        privacy::upsert(pool, &settings).await?;
+
+        Ok(settings.into())
+    }
+
+    async fn privacy(pool: &DbPool, user: &User) -> Result<PrivacySettings> {
+        let settings = privacy::get(pool, user.id).await?;
+
+        Ok(settings.unwrap_or_else(|| PrivacySettings::default_for(user.id, user.created_at)))
+    }
+
+    async fn privacy_for(pool: &DbPool, users: &[User]) -> Result<Vec<PrivacySettings>> {
+        let ids: Vec<_> = users.iter().map(|user| user.id).collect();
+
+        Ok(privacy::get_for_users(pool, &ids).await?)
+    }
+
+    fn public(viewer: Option<&User>, user: User, settings: &[PrivacySettings]) -> PublicProfile {
+        let audience = Audience::of(viewer, user.id);
+        let privacy = settings
+            .iter()
+            .find(|s| s.user_id == user.id)
+            .cloned()
+            .unwrap_or_else(|| PrivacySettings::default_for(user.id, user.created_at));
+        PublicProfile::new(user, &privacy, audience)
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::ws::{Message, WebSocket},
+    response::sse::Event as SseEvent,
+};
+use futures_util::{stream, Stream};
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use tracing::{instrument, warn};
+use uuid::Uuid;
+
+use crate::{
+    error::Result,
+    models::event::Event,
+    services::event::EventHub,
+    storage::{event, DbPool},
+};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(75);
+const REPLAY_BATCH: i64 = 100;
+
+pub struct EventFeed {
+    pool: DbPool,
+    events: Receiver<Arc<Event>>,
+    user_id: Uuid,
+    last_event_id: i64,
+    backlog: VecDeque<Arc<Event>>,
+    catching_up: bool,
+}
+
+impl EventFeed {
+    pub fn new(pool: DbPool, hub: &EventHub, user_id: Uuid, last_event_id: i64) -> Self {
+        // Subscribe before replaying so nothing published in between is missed.
+        Self {
+            pool,
+            events: hub.subscribe(),
+            user_id,
+            last_event_id,
+            backlog: VecDeque::new(),
+            catching_up: true,
+        }
+    }
+
+    pub async fn next(&mut self) -> Result<Option<Arc<Event>>> {
+        loop {
+            if let Some(event) = self.backlog.pop_front() {
+                self.last_event_id = event.id;
+                return Ok(Some(event));
+            }
+            if self.catching_up {
+                let events =
+                    event::get_after(&self.pool, self.user_id, self.last_event_id, REPLAY_BATCH)
+                        .await?;
+                self.catching_up = i64::try_from(events.len()).unwrap_or_default() == REPLAY_BATCH;
+                self.backlog.extend(events.into_iter().map(Arc::new));
+                continue;
+            }
+            match self.events.recv().await {
+                Ok(event) if event.user_id == self.user_id && event.id > self.last_event_id => {
+                    self.last_event_id = event.id;
+                    return Ok(Some(event));
+                }
+                Ok(_) => {}
+                // Slow consumers fall back to reading the log until they catch up.
+                Err(RecvError::Lagged(_)) => self.catching_up = true,
+                Err(RecvError::Closed) => return Ok(None),
+            }
+        }
+    }
+}
+
+pub struct Realtime;
+
+impl Realtime {
+    #[instrument(skip(socket, feed))]
+    pub async fn serve(mut socket: WebSocket, mut feed: EventFeed) {
+        let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
+        let mut last_seen = Instant::now();
+
+        loop {
+            tokio::select! {
+                event = feed.next() => match event {
+                    Ok(Some(event)) => {
+                        if Self::send(&mut socket, &event).await.is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(err) => {
+                        warn!("Failed to read events: {}", err);
+                        break;
+                    }
+                },
+                message = socket.recv() => match message {
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                    Some(Ok(_)) => last_seen = Instant::now(),
+                },
+                _ = heartbeat.tick() => {
+                    if last_seen.elapsed() > HEARTBEAT_TIMEOUT
+                        || socket.send(Message::Ping(Vec::new())).await.is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn stream(feed: EventFeed) -> impl Stream<Item = Result<SseEvent>> {
+        stream::unfold(feed, |mut feed| async move {
+            match feed.next().await {
+                Ok(Some(event)) => Some((Self::sse_event(&event), feed)),
+                Ok(None) => None,
+                Err(err) => {
+                    warn!("Failed to read events: {}", err);
+                    None
+                }
+            }
+        })
+    }
+
+    async fn send(socket: &mut WebSocket, event: &Event) -> Result<()> {
+        let text = serde_json::to_string(event)?;
+        socket.send(Message::Text(text)).await?;
+        Ok(())
+    }
+
+    fn sse_event(event: &Event) -> Result<SseEvent> {
+        Ok(SseEvent::default()
+            .id(event.id.to_string())
+            .event(&event.kind)
+            .json_data(event)?)
+    }
+}
@@ -0,0 +1,51 @@
+use tracing::instrument;
+
+use crate::{
+    dtos::{
+        search::{SearchKind, SearchQuery, SearchSort, UsernameQuery},
+        user::PublicProfile,
+    },
+    error::Result,
+    models::{search::SearchHit, user::User},
+    pagination::{Page, Pagination},
+    services::profile::Profiles,
+    storage::{search, DbPool},
+};
+
+const DEFAULT_LANGUAGE: &str = "english";
+const USERNAME_MATCHES: i64 = 10;
+
+pub struct Search;
+
+impl Search {
+    #[instrument(skip(pool))]
+    pub async fn search(
+        pool: &DbPool,
+        viewer: Option<&User>,
+        query: SearchQuery,
+        pagination: &Pagination<SearchSort>,
+    ) -> Result<Page<SearchHit>> {
+        let hits = search::search(
+            pool,
+            &query.q,
+            query.lang.as_deref().unwrap_or(DEFAULT_LANGUAGE),
+            query.kind.map(SearchKind::as_str),
+            viewer.map(|user| user.id),
+            viewer.map_or(false, |user| user.is_admin),
+            &pagination.keyset(),
+        )
+        .await?;
+
+        Ok(pagination.page(hits))
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn find_usernames(
+        pool: &DbPool,
+        viewer: Option<&User>,
+        query: UsernameQuery,
+    ) -> Result<Vec<PublicProfile>> {
+        let users = search::find_usernames(pool, &query.q.to_lowercase(), USERNAME_MATCHES).await?;
+        Profiles::publish(pool, viewer, users).await
+    }
+}
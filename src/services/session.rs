@@ -0,0 +1,184 @@
+use chrono::Utc;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    config::session::SessionPolicy,
+    dtos::session::{CancelSessionForm, SessionBody, SessionForm, SessionsQuery},
+    error::{Error, Result},
+    models::{
+        order::OrderStatus,
+        session::{Session, SessionStatus},
+        user::User,
+    },
+    storage::{order, session, DbPool, DbTransaction},
+};
+
+const EXCLUSION_VIOLATION: &str = "23P01";
+
+pub struct Sessions;
+
+impl Sessions {
+    #[instrument(skip(pool))]
+    pub async fn book(
+        pool: &DbPool,
+        policy: SessionPolicy,
+        student: &User,
+        order_id: Uuid,
+        form: SessionForm,
+    ) -> Result<Session> {
+        let order = order::get_by_id(pool, order_id).await?;
+        if order.student_id != student.id {
+            return Err(Error::Forbidden);
+        }
+        if !order.has_status(OrderStatus::InProgress) {
+            return Err(Error::OrderNotBookable);
+        }
+        Self::check_slot(pool, policy, order.mentor_id, &form).await?;
+
+        let now = Utc::now();
+        let session = Session {
+            id: Uuid::new_v4(),
+            order_id,
+            mentor_id: order.mentor_id,
+            student_id: order.student_id,
+            starts_at: form.starts_at,
+            ends_at: form.ends_at,
+            status: SessionStatus::Booked.as_str().to_owned(),
+            cancelled_by: None,
+            cancellation_reason: None,
+            sequence: 0,
+            created_at: now,
+            updated_at: now,
+        };
+        session::create(pool, &session)
+            .await
+            .map_err(Self::map_conflict)?;
+
+        Ok(session)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_for_order(pool: &DbPool, user: &User, order_id: Uuid) -> Result<Vec<Session>> {
+        let order = order::get_by_id(pool, order_id).await?;
+        if !(user.is_admin || order.is_participant(user.id)) {
+            return Err(Error::Forbidden);
+        }
+        Ok(session::get_by_order(pool, order_id).await?)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_for_user(
+        pool: &DbPool,
+        user: &User,
+        query: SessionsQuery,
+    ) -> Result<Vec<SessionBody>> {
+        let tz = query.tz();
+        let from = query.from.unwrap_or_else(Utc::now);
+        let sessions = session::get_for_user(pool, user.id, from, query.to).await?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| SessionBody::new(session, tz))
+            .collect())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn reschedule(
+        pool: &DbPool,
+        policy: SessionPolicy,
+        user: &User,
+        order_id: Uuid,
+        id: Uuid,
+        form: SessionForm,
+    ) -> Result<Session> {
+        let mut tx = pool.begin().await?;
+        let current = Self::get_changeable(&mut tx, policy, user, order_id, id).await?;
+        Self::check_slot(pool, policy, current.mentor_id, &form).await?;
+
+        let session = session::reschedule(&mut tx, id, form.starts_at, form.ends_at)
+            .await
+            .map_err(Self::map_conflict)?;
+        tx.commit().await?;
+
+        Ok(session)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn cancel(
+        pool: &DbPool,
+        policy: SessionPolicy,
+        user: &User,
+        order_id: Uuid,
+        id: Uuid,
+        form: CancelSessionForm,
+    ) -> Result<Session> {
+        let mut tx = pool.begin().await?;
+        Self::get_changeable(&mut tx, policy, user, order_id, id).await?;
+        let session = session::cancel(&mut tx, id, user.id, form.reason).await?;
+        tx.commit().await?;
+
+        Ok(session)
+    }
+
+    // Locks the order before the session, like opening a dispute does, so a session can't be
+    // changed once a dispute on its order has been committed.
+    async fn get_changeable(
+        tx: &mut DbTransaction<'_>,
+        policy: SessionPolicy,
+        user: &User,
+        order_id: Uuid,
+        id: Uuid,
+    ) -> Result<Session> {
+        let order = order::get_by_id_for_update(tx, order_id).await?;
+        let session = session::get_by_id_for_update(tx, id).await?;
+        if session.order_id != order_id {
+            return Err(Error::NotFound(sqlx::Error::RowNotFound));
+        }
+        if !session.is_participant(user.id) {
+            return Err(Error::Forbidden);
+        }
+        if session.has_status(SessionStatus::Cancelled) {
+            return Err(Error::SessionCancelled);
+        }
+        if order.has_status(OrderStatus::Disputed) {
+            return Err(Error::OrderDisputed);
+        }
+        if session.starts_at - Utc::now() < policy.notice() {
+            return Err(Error::NoticePeriod(policy.notice_hours()));
+        }
+        Ok(session)
+    }
+
+    async fn check_slot(
+        pool: &DbPool,
+        policy: SessionPolicy,
+        mentor_id: Uuid,
+        form: &SessionForm,
+    ) -> Result<()> {
+        if form.starts_at >= form.ends_at {
+            return Err(Error::InvalidTimeRange);
+        }
+        if form.starts_at <= Utc::now() {
+            return Err(Error::SessionInPast);
+        }
+        if !policy.allows_duration(form.ends_at - form.starts_at) {
+            return Err(Error::InvalidSessionDuration);
+        }
+        if !session::is_available(pool, mentor_id, form.starts_at, form.ends_at).await? {
+            return Err(Error::MentorUnavailable);
+        }
+        Ok(())
+    }
+
+    fn map_conflict(err: sqlx::Error) -> Error {
+        match err {
+            sqlx::Error::Database(ref db_err)
+                if db_err.code().as_deref() == Some(EXCLUSION_VIOLATION) =>
+            {
+                Error::SessionConflict
+            }
+            err => Error::from(err),
+        }
+    }
+}
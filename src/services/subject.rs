@@ -0,0 +1,96 @@
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    dtos::subject::{SubjectForm, SubjectIdsForm, SubjectNode},
+    error::{Error, Result},
+    models::subject::Subject,
+    storage::{subject, user, DbPool},
+};
+
+pub struct Subjects;
+
+impl Subjects {
+    #[instrument(skip(pool))]
+    pub async fn get_tree(pool: &DbPool) -> Result<Vec<SubjectNode>> {
+        let subjects = subject::get_all(pool).await?;
+        Ok(SubjectNode::tree(&subjects))
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn create(pool: &DbPool, form: SubjectForm) -> Result<Subject> {
+        if let Some(parent_id) = form.parent_id {
+            Self::ensure_exist(pool, &[parent_id]).await?;
+        }
+        let id = Uuid::new_v4();
+        let subject = Subject {
+            id,
+            parent_id: form.parent_id,
+            slug: form.slug,
+            name: form.name,
+            created_at: chrono::offset::Utc::now(),
+        };
+        subject::create(pool, subject).await?;
+        Ok(subject::get_by_id(pool, id).await?)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn edit(pool: &DbPool, id: Uuid, form: SubjectForm) -> Result<Subject> {
+        let subject = subject::get_by_id(pool, id).await?;
+        if let Some(parent_id) = form.parent_id {
+            Self::ensure_exist(pool, &[parent_id]).await?;
+            if subject::is_descendant(pool, id, parent_id).await? {
+                return Err(Error::SubjectCycle);
+            }
+        }
+        let subject = Subject {
+            parent_id: form.parent_id,
+            slug: form.slug,
+            name: form.name,
+            ..subject
+        };
+        subject::edit(pool, subject).await?;
+        Ok(subject::get_by_id(pool, id).await?)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn delete(pool: &DbPool, id: Uuid) -> Result<()> {
+        subject::get_by_id(pool, id).await?;
+        if subject::has_children(pool, id).await? {
+            return Err(Error::SubjectHasChildren);
+        }
+        subject::delete(pool, id).await?;
+        Ok(())
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn get_for_user(pool: &DbPool, username: String) -> Result<Vec<Subject>> {
+        let user = user::get_active_by_username(pool, username).await?;
+        Ok(subject::get_by_user(pool, user.id).await?)
+    }
+
+    #[instrument(skip(pool))]
+    pub async fn set_for_user(
+        pool: &DbPool,
+        user_id: Uuid,
+        form: SubjectIdsForm,
+    ) -> Result<Vec<Subject>> {
+        Self::ensure_exist(pool, &form.subject_ids).await?;
+        let mut tx = pool.begin().await?;
+        subject::set_for_user(&mut tx, user_id, &form.subject_ids).await?;
+        tx.commit().await?;
+        Ok(subject::get_by_user(pool, user_id).await?)
+    }
+
+    pub async fn ensure_exist(pool: &DbPool, ids: &[Uuid]) -> Result<()> {
+        let mut ids = ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        let existing = subject::count_existing(pool, &ids).await?;
+        if usize::try_from(existing) == Ok(ids.len()) {
+            Ok(())
+        } else {
+            Err(Error::UnknownSubject)
+        }
+    }
+}
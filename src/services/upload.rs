@@ -0,0 +1,296 @@
+use std::io::Cursor;
+
+use image::{ImageFormat, ImageOutputFormat};
+use tracing::{instrument, warn};
+use uuid::Uuid;
+
+use crate::{
+    blob::FileStore,
+    dtos::upload::{Download, DownloadQuery, FileVariant, UploadBody, UploadForm},
+    error::{Error, Result},
+    models::{
+        upload::{Upload, UploadKind},
+        user::User,
+    },
+    storage::{order, upload, user, DbPool},
+};
+
+pub struct Uploads;
+
+impl Uploads {
+    #[instrument(skip(pool, files))]
+    pub async fn set_avatar(
+        pool: &DbPool,
+        files: &FileStore,
+        user: &User,
+        form: UploadForm,
+    ) -> Result<UploadBody> {
+        if !files.config().allows_avatar(&form.content_type) {
+            return Err(Error::UnsupportedContentType(form.content_type));
+        }
+        let format = image::guess_format(&form.bytes).map_err(|_| Error::InvalidImage)?;
+        if format.to_mime_type() != form.content_type {
+            return Err(Error::InvalidImage);
+        }
+        let size = files.config().thumbnail_size();
+        // Decoding and resizing are CPU bound and must not stall the async workers.
+        let (bytes, thumbnail) = tokio::task::spawn_blocking(move || {
+            let thumbnail = Self::thumbnail(&form.bytes, format, size);
+            (form.bytes, thumbnail)
+        })
+        .await
+        .map_err(std::io::Error::from)?;
+        let thumbnail = thumbnail?;
+        let scan_status = files.scan(&bytes).await?;
+
+        let id = Uuid::new_v4();
+        let storage_key = format!("avatars/{}/{id}", user.id);
+        let thumbnail_key = format!("avatars/{}/{id}-thumbnail.png", user.id);
+        let avatar = Upload {
+            id,
+            owner_id: user.id,
+            order_id: None,
+            kind: UploadKind::Avatar.as_str().to_owned(),
+            file_name: form.file_name,
+            content_type: form.content_type,
+            size: i64::try_from(bytes.len()).unwrap_or(i64::MAX),
+            storage_key,
+            thumbnail_key: Some(thumbnail_key.clone()),
+            scan_status: scan_status.as_str().to_owned(),
+            created_at: chrono::offset::Utc::now(),
+        };
+        let stored = async {
+            files
+                .put(&avatar.storage_key, &avatar.content_type, bytes)
+                .await?;
+            files.put(&thumbnail_key, "image/png", thumbnail).await?;
+
+            let mut tx = pool.begin().await?;
+            let previous = upload::delete_avatar(&mut tx, user.id).await?;
+            upload::create(&mut tx, &avatar).await?;
+            tx.commit().await?;
+            Ok(previous)
+        };
+        let previous = Self::discard_on_error(files, &avatar, stored.await).await?;
+
+        if let Some(previous) = previous {
+            Self::remove_blobs(files, &previous).await;
+        }
+        Ok(Self::body(files, avatar))
+    }
+
+    #[instrument(skip(pool, files))]
+    pub async fn get_avatar(
+        pool: &DbPool,
+        files: &FileStore,
+        username: String,
+    ) -> Result<UploadBody> {
+        let user = user::get_active_by_username(pool, username).await?;
+        let avatar = upload::get_avatar(pool, user.id)
+            .await?
+            .ok_or(Error::NotFound(sqlx::Error::RowNotFound))?;
+        Ok(Self::body(files, avatar))
+    }
+
+    #[instrument(skip(pool, files))]
+    pub async fn delete_avatar(pool: &DbPool, files: &FileStore, user: &User) -> Result<()> {
+        let mut tx = pool.begin().await?;
+        let avatar = upload::delete_avatar(&mut tx, user.id).await?;
+        tx.commit().await?;
+
+        if let Some(avatar) = avatar {
+            Self::remove_blobs(files, &avatar).await;
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(pool, files))]
+    pub async fn attach(
+        pool: &DbPool,
+        files: &FileStore,
+        user: &User,
+        order_id: Uuid,
+        form: UploadForm,
+    ) -> Result<UploadBody> {
+        let order = order::get_by_id(pool, order_id).await?;
+        if !order.is_participant(user.id) {
+            return Err(Error::Forbidden);
+        }
+        if !files.config().allows_attachment(&form.content_type) {
+            return Err(Error::UnsupportedContentType(form.content_type));
+        }
+        if !Self::content_matches(&form.content_type, &form.bytes) {
+            return Err(Error::ContentMismatch);
+        }
+        let scan_status = files.scan(&form.bytes).await?;
+
+        let id = Uuid::new_v4();
+        let attachment = Upload {
+            id,
+            owner_id: user.id,
+            order_id: Some(order.id),
+            kind: UploadKind::Attachment.as_str().to_owned(),
+            file_name: form.file_name,
+            content_type: form.content_type,
+            size: i64::try_from(form.bytes.len()).unwrap_or(i64::MAX),
+            storage_key: format!("attachments/{}/{id}", order.id),
+            thumbnail_key: None,
+            scan_status: scan_status.as_str().to_owned(),
+            created_at: chrono::offset::Utc::now(),
+        };
+        let stored = async {
+            files
+                .put(
+                    &attachment.storage_key,
+                    &attachment.content_type,
+                    form.bytes,
+                )
+                .await?;
+
+            let mut tx = pool.begin().await?;
+            upload::create(&mut tx, &attachment).await?;
+            tx.commit().await?;
+            Ok(())
+        };
+        Self::discard_on_error(files, &attachment, stored.await).await?;
+
+        Ok(Self::body(files, attachment))
+    }
+
+    #[instrument(skip(pool, files))]
+    pub async fn get_attachments(
+        pool: &DbPool,
+        files: &FileStore,
+        user: &User,
+        order_id: Uuid,
+    ) -> Result<Vec<UploadBody>> {
+        let order = order::get_by_id(pool, order_id).await?;
+        if !(user.is_admin || order.is_participant(user.id)) {
+            return Err(Error::Forbidden);
+        }
+        let attachments = upload::get_by_order(pool, order.id).await?;
+        Ok(attachments
+            .into_iter()
+            .map(|attachment| Self::body(files, attachment))
+            .collect())
+    }
+
+    #[instrument(skip(pool, files))]
+    pub async fn delete_attachment(
+        pool: &DbPool,
+        files: &FileStore,
+        user: &User,
+        order_id: Uuid,
+        id: Uuid,
+    ) -> Result<()> {
+        let attachment = upload::get_by_id(pool, id).await?;
+        if attachment.order_id != Some(order_id) {
+            return Err(Error::NotFound(sqlx::Error::RowNotFound));
+        }
+        if !(user.is_admin || attachment.owner_id == user.id) {
+            return Err(Error::Forbidden);
+        }
+        upload::delete(pool, attachment.id).await?;
+        Self::remove_blobs(files, &attachment).await;
+        Ok(())
+    }
+
+    #[instrument(skip(pool, files))]
+    pub async fn download(
+        pool: &DbPool,
+        files: &FileStore,
+        id: Uuid,
+        query: DownloadQuery,
+    ) -> Result<Download> {
+        FileStore::verify(
+            id,
+            query.variant.as_str(),
+            query.expires,
+            &query.signature,
+            chrono::offset::Utc::now(),
+        )?;
+        let upload = upload::get_by_id(pool, id).await?;
+        let key = match query.variant {
+            FileVariant::Original => upload.storage_key.as_str(),
+            FileVariant::Thumbnail => upload
+                .thumbnail_key
+                .as_deref()
+                .ok_or(Error::NotFound(sqlx::Error::RowNotFound))?,
+        };
+        let bytes = files.get(key).await?;
+        Ok(Download {
+            upload,
+            variant: query.variant,
+            bytes,
+        })
+    }
+
+    // The declared type comes from the client, so it has to agree with the leading bytes. Types
+    // an operator adds beyond these have no signature to check against.
+    fn content_matches(content_type: &str, bytes: &[u8]) -> bool {
+        match content_type {
+            "application/pdf" => bytes.starts_with(b"%PDF-"),
+            "application/zip"
+            | "application/vnd.openxmlformats-officedocument.wordprocessingml.document" => {
+                bytes.starts_with(b"PK\x03\x04")
+            }
+            "application/msword" => bytes.starts_with(b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1"),
+            "text/plain" => !bytes.contains(&0) && std::str::from_utf8(bytes).is_ok(),
+            _ if content_type.starts_with("image/") => image::guess_format(bytes)
+                .map_or(false, |format| format.to_mime_type() == content_type),
+            _ => true,
+        }
+    }
+
+    fn thumbnail(bytes: &[u8], format: ImageFormat, size: u32) -> Result<Vec<u8>> {
+        let image =
+            image::load_from_memory_with_format(bytes, format).map_err(|_| Error::InvalidImage)?;
+        let mut thumbnail = Vec::new();
+        image
+            .thumbnail(size, size)
+            .write_to(&mut Cursor::new(&mut thumbnail), ImageOutputFormat::Png)?;
+        Ok(thumbnail)
+    }
+
+    fn body(files: &FileStore, upload: Upload) -> UploadBody {
+        let now = chrono::offset::Utc::now();
+        let (url, expires_at) = files.sign(upload.id, FileVariant::Original.as_str(), now);
+        let thumbnail_url = upload.has_kind(UploadKind::Avatar).then(|| {
+            files
+                .sign(upload.id, FileVariant::Thumbnail.as_str(), now)
+                .0
+        });
+        UploadBody {
+            id: upload.id,
+            file_name: upload.file_name,
+            content_type: upload.content_type,
+            size: upload.size,
+            url,
+            thumbnail_url,
+            expires_at,
+            created_at: upload.created_at,
+        }
+    }
+
+    // Blobs are written before their row, so they have to go again when recording the upload
+    // fails.
+    async fn discard_on_error<T>(
+        files: &FileStore,
+        upload: &Upload,
+        result: Result<T>,
+    ) -> Result<T> {
+        if result.is_err() {
+            Self::remove_blobs(files, upload).await;
+        }
+        result
+    }
+
+    // A blob that outlives its row is only wasted space, so the request does not fail over it.
+    pub async fn remove_blobs(files: &FileStore, upload: &Upload) {
+        for key in upload.keys() {
+            if let Err(err) = files.delete(key).await {
+                warn!("failed to delete blob {key}: {err}");
+            }
+        }
+    }
+}
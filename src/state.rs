@@ -0,0 +1,153 @@
+use axum::extract::FromRef;
+
+use crate::{
+    blob::FileStore,
+    config::{
+        account::AccountPolicy, export::ExportPolicy, health::HealthPolicy, session::SessionPolicy,
+        upload::UploadConfig, versioning::VersioningPolicy,
+    },
+    error::Result,
+    services::{account::Accounts, event::EventHub, export::Exports, health::Lifecycle},
+    storage::DbPool,
+};
+
+#[derive(Clone)]
+pub struct AppState {
+    pool: DbPool,
+    events: EventHub,
+    sessions: SessionPolicy,
+    files: FileStore,
+    accounts: AccountPolicy,
+    exports: ExportPolicy,
+    versioning: VersioningPolicy,
+    health: HealthPolicy,
+    lifecycle: Lifecycle,
+}
+
+impl AppState {
+    #[must_use]
+    pub fn new(pool: DbPool) -> Self {
+        let events = EventHub::spawn(pool.clone());
+        Self {
+            pool,
+            events,
+            sessions: SessionPolicy::default(),
+            files: FileStore::default(),
+            accounts: AccountPolicy::default(),
+            exports: ExportPolicy::default(),
+            versioning: VersioningPolicy::default(),
+            health: HealthPolicy::default(),
+            lifecycle: Lifecycle::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_session_policy(self, sessions: SessionPolicy) -> Self {
+        Self { sessions, ..self }
+    }
+
+    #[must_use]
+    pub fn with_upload_config(self, uploads: UploadConfig) -> Self {
+        Self {
+            files: FileStore::new(uploads),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn with_account_policy(self, accounts: AccountPolicy) -> Self {
+        Self { accounts, ..self }
+    }
+
+    #[must_use]
+    pub fn with_export_policy(self, exports: ExportPolicy) -> Self {
+        Self { exports, ..self }
+    }
+
+    #[must_use]
+    pub fn with_versioning_policy(self, versioning: VersioningPolicy) -> Self {
+        Self { versioning, ..self }
+    }
+
+    #[must_use]
+    pub fn with_health_policy(self, health: HealthPolicy) -> Self {
+        Self { health, ..self }
+    }
+
+    pub fn spawn_account_purge(&self) {
+        Accounts::spawn_purge(self.pool.clone(), self.files.clone(), self.accounts);
+    }
+
+    pub async fn purge_deleted_accounts(&self) -> Result<usize> {
+        Accounts::purge(&self.pool, &self.files, self.accounts).await
+    }
+
+    pub fn spawn_export_cleanup(&self) {
+        Exports::spawn_cleanup(self.pool.clone(), self.files.clone(), self.exports);
+    }
+
+    pub async fn remove_expired_exports(&self) -> Result<usize> {
+        Exports::cleanup(&self.pool, &self.files, self.exports).await
+    }
+
+    /// Reports not ready from now on, then waits out the drain period so load balancers
+    /// stop sending traffic before the server stops accepting connections.
+    pub async fn drain(&self) {
+        self.lifecycle.drain();
+        tokio::time::sleep(self.health.drain_period()).await;
+    }
+}
+
+impl FromRef<AppState> for DbPool {
+    fn from_ref(state: &AppState) -> Self {
+        state.pool.clone()
+    }
+}
+
+impl FromRef<AppState> for EventHub {
+    fn from_ref(state: &AppState) -> Self {
+        state.events.clone()
+    }
+}
+
+impl FromRef<AppState> for SessionPolicy {
+    fn from_ref(state: &AppState) -> Self {
+        state.sessions
+    }
+}
+
+impl FromRef<AppState> for FileStore {
+    fn from_ref(state: &AppState) -> Self {
+        state.files.clone()
+    }
+}
+
+impl FromRef<AppState> for AccountPolicy {
+    fn from_ref(state: &AppState) -> Self {
+        state.accounts
+    }
+}
+
+impl FromRef<AppState> for ExportPolicy {
+    fn from_ref(state: &AppState) -> Self {
+        state.exports
+    }
+}
+
+impl FromRef<AppState> for VersioningPolicy {
+    fn from_ref(state: &AppState) -> Self {
+        state.versioning
+    }
+}
+
+impl FromRef<AppState> for HealthPolicy {
+    fn from_ref(state: &AppState) -> Self {
+        state.health
+    }
+}
+
+impl FromRef<AppState> for Lifecycle {
+    fn from_ref(state: &AppState) -> Self {
+        state.lifecycle.clone()
+    }
+}
@@ -0,0 +1,59 @@
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::models::user::User;
+
+use super::DbPool;
+
+#[instrument(skip(pool))]
+pub async fn get_token(pool: &DbPool, user_id: Uuid) -> SqlxResult<Option<String>> {
+    let token = sqlx::query_scalar!(
+        r#"
+            SELECT calendar_tokens.token
+            FROM calendar_tokens
+            WHERE calendar_tokens.user_id = $1;
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(token)
+}
+
+#[instrument(skip_all)]
+pub async fn get_user_by_token(pool: &DbPool, token: &str) -> SqlxResult<User> {
+    let user = sqlx::query_as!(
+        User,
+        r#"
+            SELECT users.*
+            FROM users
+            JOIN calendar_tokens ON calendar_tokens.user_id = users.id
+            WHERE calendar_tokens.token = $1 AND users.deleted_at IS NULL;
+        "#,
+        token
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(user)
+}
+
+#[instrument(skip(pool, token))]
+pub async fn set_token(pool: &DbPool, user_id: Uuid, token: &str) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO calendar_tokens (user_id, token, created_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (user_id) DO UPDATE
+            SET token = EXCLUDED.token, created_at = EXCLUDED.created_at;
+        "#,
+        user_id,
+        token,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
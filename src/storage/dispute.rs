@@ -0,0 +1,149 @@
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{models::dispute::Dispute, pagination::Keyset};
+
+use super::{DbPool, DbTransaction};
+
+#[instrument(skip(pool))]
+pub async fn get_by_id(pool: &DbPool, id: Uuid) -> SqlxResult<Dispute> {
+    let dispute = sqlx::query_as!(
+        Dispute,
+        r#"
+            SELECT *
+            FROM disputes
+            WHERE disputes.id = $1;
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(dispute)
+}
+
+#[instrument(skip(tx))]
+pub async fn get_by_id_for_update(tx: &mut DbTransaction<'_>, id: Uuid) -> SqlxResult<Dispute> {
+    let dispute = sqlx::query_as!(
+        Dispute,
+        r#"
+            SELECT *
+            FROM disputes
+            WHERE disputes.id = $1
+            FOR UPDATE;
+        "#,
+        id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(dispute)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_page_by_status(
+    pool: &DbPool,
+    status: &str,
+    keyset: &Keyset,
+) -> SqlxResult<Vec<Dispute>> {
+    // One statement per direction, so that both walk the index.
+    macro_rules! page {
+        ($cmp:tt, $direction:tt) => {
+            sqlx::query_as!(
+                Dispute,
+                r#"
+                    SELECT *
+                    FROM disputes
+                    WHERE disputes.status = $1
+                      AND ($3::UUID IS NULL OR (disputes.created_at, disputes.id) "#
+                    + $cmp
+                    + r#" ($2::TIMESTAMPTZ, $3))
+                    ORDER BY disputes.created_at "#
+                    + $direction
+                    + ", disputes.id "
+                    + $direction
+                    + r#"
+                    LIMIT $4;
+                "#,
+                status,
+                keyset.after_timestamp(),
+                keyset.after_id,
+                keyset.limit,
+            )
+            .fetch_all(pool)
+            .await?
+        };
+    }
+
+    let disputes = if keyset.descending {
+        page!("<", "DESC")
+    } else {
+        page!(">", "ASC")
+    };
+
+    Ok(disputes)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_all_by_order(pool: &DbPool, order_id: Uuid) -> SqlxResult<Vec<Dispute>> {
+    let disputes = sqlx::query_as!(
+        Dispute,
+        r#"
+            SELECT *
+            FROM disputes
+            WHERE disputes.order_id = $1
+            ORDER BY disputes.created_at;
+        "#,
+        order_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(disputes)
+}
+
+#[instrument(skip(tx))]
+pub async fn create(tx: &mut DbTransaction<'_>, dispute: &Dispute) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO disputes (id, order_id, opened_by, reason, evidence, status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7);
+        "#,
+        dispute.id,
+        dispute.order_id,
+        dispute.opened_by,
+        dispute.reason,
+        &dispute.evidence,
+        dispute.status,
+        dispute.created_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(tx))]
+pub async fn resolve(tx: &mut DbTransaction<'_>, dispute: &Dispute) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE disputes
+            SET (status, outcome, student_amount, mentor_amount, resolution_note, resolved_by, resolved_at)
+                = ($2, $3, $4, $5, $6, $7, $8)
+            WHERE disputes.id = $1;
+        "#,
+        dispute.id,
+        dispute.status,
+        dispute.outcome,
+        dispute.student_amount,
+        dispute.mentor_amount,
+        dispute.resolution_note,
+        dispute.resolved_by,
+        dispute.resolved_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
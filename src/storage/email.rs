@@ -0,0 +1,26 @@
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+
+use crate::models::email::Email;
+
+use super::DbTransaction;
+
+#[instrument(skip(tx))]
+pub async fn create(tx: &mut DbTransaction<'_>, email: Email) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO emails (id, address, subject, body, sent_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6);
+        "#,
+        email.id,
+        email.address,
+        email.subject,
+        email.body,
+        email.sent_at,
+        email.created_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::models::event::Event;
+
+use super::{DbPool, DbTransaction};
+
+#[instrument(skip(pool))]
+pub async fn get_by_id(pool: &DbPool, id: i64) -> SqlxResult<Event> {
+    let event = sqlx::query_as!(
+        Event,
+        r#"
+            SELECT *
+            FROM events
+            WHERE events.id = $1;
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(event)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_after(
+    pool: &DbPool,
+    user_id: Uuid,
+    after: i64,
+    limit: i64,
+) -> SqlxResult<Vec<Event>> {
+    let events = sqlx::query_as!(
+        Event,
+        r#"
+            SELECT *
+            FROM events
+            WHERE events.user_id = $1 AND events.id > $2
+            ORDER BY events.id
+            LIMIT $3;
+        "#,
+        user_id,
+        after,
+        limit,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(events)
+}
+
+#[instrument(skip(tx))]
+pub async fn create(
+    tx: &mut DbTransaction<'_>,
+    user_ids: &[Uuid],
+    kind: &str,
+    payload: Value,
+    created_at: DateTime<Utc>,
+) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO events (user_id, kind, payload, created_at)
+            SELECT UNNEST($1::UUID[]), $2, $3, $4;
+        "#,
+        user_ids,
+        kind,
+        payload,
+        created_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn delete_before(pool: &DbPool, before: DateTime<Utc>) -> SqlxResult<u64> {
+    let result = sqlx::query!(
+        r#"
+            DELETE FROM events
+            WHERE events.created_at < $1;
+        "#,
+        before
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
@@ -0,0 +1,194 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::models::export::{DataExport, ExportStatus};
+
+use super::DbPool;
+
+#[instrument(skip(pool))]
+pub async fn get_by_id(pool: &DbPool, id: Uuid, user_id: Uuid) -> SqlxResult<DataExport> {
+    let export = sqlx::query_as!(
+        DataExport,
+        r#"
+            SELECT *
+            FROM data_exports
+            WHERE data_exports.id = $1 AND data_exports.user_id = $2;
+        "#,
+        id,
+        user_id,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(export)
+}
+
+#[instrument(skip(pool))]
+pub async fn count_since(pool: &DbPool, user_id: Uuid, since: DateTime<Utc>) -> SqlxResult<i64> {
+    let count = sqlx::query_scalar!(
+        r#"
+            SELECT COUNT(*) AS "count!"
+            FROM data_exports
+            WHERE data_exports.user_id = $1
+              AND data_exports.created_at > $2
+              AND data_exports.status <> 'failed';
+        "#,
+        user_id,
+        since,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_expired(pool: &DbPool, before: DateTime<Utc>) -> SqlxResult<Vec<DataExport>> {
+    let exports = sqlx::query_as!(
+        DataExport,
+        r#"
+            SELECT *
+            FROM data_exports
+            WHERE data_exports.storage_key IS NOT NULL AND data_exports.expires_at < $1;
+        "#,
+        before,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(exports)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_storage_keys(pool: &DbPool, user_id: Uuid) -> SqlxResult<Vec<String>> {
+    let keys = sqlx::query_scalar!(
+        r#"
+            SELECT storage_key AS "storage_key!"
+            FROM data_exports
+            WHERE data_exports.user_id = $1 AND data_exports.storage_key IS NOT NULL;
+        "#,
+        user_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(keys)
+}
+
+#[instrument(skip(pool))]
+pub async fn collect(pool: &DbPool, user_id: Uuid) -> SqlxResult<Value> {
+    let data = sqlx::query_scalar!(
+        r#"
+            SELECT export_user_data($1) AS "data!";
+        "#,
+        user_id,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(data)
+}
+
+#[instrument(skip(pool))]
+pub async fn create(pool: &DbPool, export: &DataExport) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO data_exports (id, user_id, status, storage_key, size, created_at, completed_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8);
+        "#,
+        export.id,
+        export.user_id,
+        export.status,
+        export.storage_key,
+        export.size,
+        export.created_at,
+        export.completed_at,
+        export.expires_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn complete(pool: &DbPool, export: &DataExport) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE data_exports
+            SET status = $2, storage_key = $3, size = $4, completed_at = $5, expires_at = $6
+            WHERE data_exports.id = $1;
+        "#,
+        export.id,
+        export.status,
+        export.storage_key,
+        export.size,
+        export.completed_at,
+        export.expires_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn expire(pool: &DbPool, id: Uuid) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE data_exports
+            SET status = $2, storage_key = NULL
+            WHERE data_exports.id = $1;
+        "#,
+        id,
+        ExportStatus::Expired.as_str(),
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn fail_pending_before(
+    pool: &DbPool,
+    before: DateTime<Utc>,
+    at: DateTime<Utc>,
+) -> SqlxResult<u64> {
+    let result = sqlx::query!(
+        r#"
+            UPDATE data_exports
+            SET status = $3, completed_at = $4
+            WHERE data_exports.status = $1 AND data_exports.created_at < $2;
+        "#,
+        ExportStatus::Pending.as_str(),
+        before,
+        ExportStatus::Failed.as_str(),
+        at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+#[instrument(skip(pool))]
+pub async fn fail(pool: &DbPool, id: Uuid, at: DateTime<Utc>) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE data_exports
+            SET status = $2, completed_at = $3
+            WHERE data_exports.id = $1;
+        "#,
+        id,
+        ExportStatus::Failed.as_str(),
+        at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+
+use super::DbPool;
+
+#[instrument(skip(pool))]
+pub async fn ping(pool: &DbPool) -> SqlxResult<()> {
+    sqlx::query!("SELECT 1 AS one;").fetch_one(pool).await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn applied_migrations(pool: &DbPool) -> SqlxResult<Vec<i64>> {
+    let versions = sqlx::query_scalar!(
+        r#"
+            SELECT version
+            FROM _sqlx_migrations
+            WHERE success
+            ORDER BY version;
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(versions)
+}
@@ -0,0 +1,278 @@
+use chrono::{DateTime, Utc};
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    models::mentor_profile::{
+        AvailabilityException, AvailabilitySlot, MentorListing, MentorProfile,
+    },
+    pagination::Keyset,
+};
+
+use super::{DbPool, DbTransaction};
+
+#[instrument(skip(pool))]
+pub async fn get_by_user(pool: &DbPool, user_id: Uuid) -> SqlxResult<Option<MentorProfile>> {
+    let profile = sqlx::query_as!(
+        MentorProfile,
+        r#"
+            SELECT *
+            FROM mentor_profiles
+            WHERE mentor_profiles.user_id = $1;
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(profile)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_availability(pool: &DbPool, user_id: Uuid) -> SqlxResult<Vec<AvailabilitySlot>> {
+    let slots = sqlx::query_as!(
+        AvailabilitySlot,
+        r#"
+            SELECT id, weekday, start_time, end_time
+            FROM mentor_availability
+            WHERE mentor_availability.user_id = $1
+            ORDER BY mentor_availability.weekday, mentor_availability.start_time;
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(slots)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_exceptions(
+    pool: &DbPool,
+    user_id: Uuid,
+) -> SqlxResult<Vec<AvailabilityException>> {
+    let exceptions = sqlx::query_as!(
+        AvailabilityException,
+        r#"
+            SELECT id, starts_at, ends_at, reason
+            FROM mentor_availability_exceptions
+            WHERE mentor_availability_exceptions.user_id = $1
+            ORDER BY mentor_availability_exceptions.starts_at;
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(exceptions)
+}
+
+#[instrument(skip(pool))]
+pub async fn search(
+    pool: &DbPool,
+    subject: Option<String>,
+    available_from: Option<DateTime<Utc>>,
+    available_to: Option<DateTime<Utc>>,
+    max_rate: Option<i32>,
+    keyset: &Keyset,
+) -> SqlxResult<Vec<MentorListing>> {
+    // One statement per sort column and direction, so that each walks its own index.
+    macro_rules! page {
+        ($column:tt, $key_type:tt, $after:expr) => {
+            if keyset.descending {
+                page!($column, $key_type, $after, "<", "DESC")
+            } else {
+                page!($column, $key_type, $after, ">", "ASC")
+            }
+        };
+        ($column:tt, $key_type:tt, $after:expr, $cmp:tt, $direction:tt) => {
+            sqlx::query_as!(
+                MentorListing,
+                r#"
+                    SELECT
+                        users.id AS user_id,
+                        users.username,
+                        users.first_name,
+                        users.last_name,
+                        mentor_profiles.headline,
+                        mentor_profiles.hourly_rate,
+                        mentor_profiles.languages,
+                        mentor_profiles.time_zone,
+                        mentor_profiles.created_at
+                    FROM mentor_profiles
+                    JOIN users ON users.id = mentor_profiles.user_id
+                    WHERE users.deleted_at IS NULL
+                      AND ($1::VARCHAR IS NULL OR EXISTS (
+                            SELECT 1
+                            FROM mentor_subjects
+                            WHERE mentor_subjects.user_id = users.id
+                              AND mentor_subjects.subject_id IN (SELECT id FROM subject_subtree($1))
+                          ))
+                      AND ($2::TIMESTAMPTZ IS NULL OR $3::TIMESTAMPTZ IS NULL
+                           OR mentor_is_available(users.id, $2, $3))
+                      AND ($4::INTEGER IS NULL OR mentor_profiles.hourly_rate <= $4)
+                      AND ($6::UUID IS NULL OR ("# + $column + ", users.id) " + $cmp + " ($5::" + $key_type + r#", $6))
+                    ORDER BY "# + $column + " " + $direction + ", users.id " + $direction + r#"
+                    LIMIT $7;
+                "#,
+                subject,
+                available_from,
+                available_to,
+                max_rate,
+                $after,
+                keyset.after_id,
+                keyset.limit,
+            )
+            .fetch_all(pool)
+            .await?
+        };
+    }
+
+    let mentors = match keyset.sort {
+        "username" => page!("users.username", "VARCHAR", keyset.after_text()),
+        "created_at" => page!(
+            "mentor_profiles.created_at",
+            "TIMESTAMPTZ",
+            keyset.after_timestamp()
+        ),
+        _ => page!(
+            "mentor_profiles.hourly_rate",
+            "BIGINT",
+            keyset.after_integer()
+        ),
+    };
+
+    Ok(mentors)
+}
+
+#[instrument(skip(tx))]
+pub async fn upsert(tx: &mut DbTransaction<'_>, profile: &MentorProfile) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO mentor_profiles (user_id, headline, hourly_rate, languages, education, time_zone, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (user_id) DO UPDATE
+            SET headline = EXCLUDED.headline,
+                hourly_rate = EXCLUDED.hourly_rate,
+                languages = EXCLUDED.languages,
+                education = EXCLUDED.education,
+                time_zone = EXCLUDED.time_zone,
+                updated_at = EXCLUDED.updated_at;
+        "#,
+        profile.user_id,
+        profile.headline,
+        profile.hourly_rate,
+        &profile.languages,
+        profile.education,
+        profile.time_zone,
+        profile.created_at,
+        profile.updated_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(tx))]
+pub async fn set_availability(
+    tx: &mut DbTransaction<'_>,
+    user_id: Uuid,
+    slots: &[AvailabilitySlot],
+) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            DELETE FROM mentor_availability
+            WHERE mentor_availability.user_id = $1;
+        "#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let ids: Vec<_> = slots.iter().map(|slot| slot.id).collect();
+    let weekdays: Vec<_> = slots.iter().map(|slot| slot.weekday).collect();
+    let start_times: Vec<_> = slots.iter().map(|slot| slot.start_time).collect();
+    let end_times: Vec<_> = slots.iter().map(|slot| slot.end_time).collect();
+    sqlx::query!(
+        r#"
+            INSERT INTO mentor_availability (id, user_id, weekday, start_time, end_time)
+            SELECT slot.id, $1, slot.weekday, slot.start_time, slot.end_time
+            FROM UNNEST($2::UUID[], $3::SMALLINT[], $4::TIME[], $5::TIME[])
+                AS slot (id, weekday, start_time, end_time);
+        "#,
+        user_id,
+        &ids,
+        &weekdays,
+        &start_times,
+        &end_times,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(tx))]
+pub async fn set_exceptions(
+    tx: &mut DbTransaction<'_>,
+    user_id: Uuid,
+    exceptions: &[AvailabilityException],
+) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            DELETE FROM mentor_availability_exceptions
+            WHERE mentor_availability_exceptions.user_id = $1;
+        "#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let ids: Vec<_> = exceptions.iter().map(|exception| exception.id).collect();
+    let starts: Vec<_> = exceptions
+        .iter()
+        .map(|exception| exception.starts_at)
+        .collect();
+    let ends: Vec<_> = exceptions
+        .iter()
+        .map(|exception| exception.ends_at)
+        .collect();
+    let reasons: Vec<_> = exceptions
+        .iter()
+        .map(|exception| exception.reason.clone())
+        .collect();
+    sqlx::query!(
+        r#"
+            INSERT INTO mentor_availability_exceptions (id, user_id, starts_at, ends_at, reason)
+            SELECT exception.id, $1, exception.starts_at, exception.ends_at, exception.reason
+            FROM UNNEST($2::UUID[], $3::TIMESTAMPTZ[], $4::TIMESTAMPTZ[], $5::VARCHAR[])
+                AS exception (id, starts_at, ends_at, reason);
+        "#,
+        user_id,
+        &ids,
+        &starts,
+        &ends,
+        &reasons as &[Option<String>],
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn delete(pool: &DbPool, user_id: Uuid) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            DELETE FROM mentor_profiles
+            WHERE mentor_profiles.user_id = $1;
+        "#,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
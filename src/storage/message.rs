@@ -0,0 +1,199 @@
+use chrono::{DateTime, Utc};
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    models::message::{Message, UnreadCount},
+    pagination::Keyset,
+};
+
+use super::{DbPool, DbTransaction};
+
+#[instrument(skip(pool))]
+pub async fn get_by_id(pool: &DbPool, id: Uuid) -> SqlxResult<Message> {
+    let message = sqlx::query_as!(
+        Message,
+        r#"
+            SELECT *
+            FROM messages
+            WHERE messages.id = $1;
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(message)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_all_by_order(pool: &DbPool, order_id: Uuid) -> SqlxResult<Vec<Message>> {
+    let messages = sqlx::query_as!(
+        Message,
+        r#"
+            SELECT *
+            FROM messages
+            WHERE messages.order_id = $1
+            ORDER BY messages.created_at, messages.id;
+        "#,
+        order_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(messages)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_page(pool: &DbPool, order_id: Uuid, keyset: &Keyset) -> SqlxResult<Vec<Message>> {
+    // One statement per direction, so that both walk the index.
+    macro_rules! page {
+        ($cmp:tt, $direction:tt) => {
+            sqlx::query_as!(
+                Message,
+                r#"
+                    SELECT *
+                    FROM messages
+                    WHERE messages.order_id = $1
+                      AND ($3::UUID IS NULL OR (messages.created_at, messages.id) "#
+                    + $cmp
+                    + r#" ($2::TIMESTAMPTZ, $3))
+                    ORDER BY messages.created_at "#
+                    + $direction
+                    + ", messages.id "
+                    + $direction
+                    + r#"
+                    LIMIT $4;
+                "#,
+                order_id,
+                keyset.after_timestamp(),
+                keyset.after_id,
+                keyset.limit,
+            )
+            .fetch_all(pool)
+            .await?
+        };
+    }
+
+    let messages = if keyset.descending {
+        page!("<", "DESC")
+    } else {
+        page!(">", "ASC")
+    };
+
+    Ok(messages)
+}
+
+#[instrument(skip(tx))]
+pub async fn create(tx: &mut DbTransaction<'_>, message: Message) -> SqlxResult<Message> {
+    let message = sqlx::query_as!(
+        Message,
+        r#"
+            INSERT INTO messages (id, order_id, sender_id, body, read_at, edited_at, deleted_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *;
+        "#,
+        message.id,
+        message.order_id,
+        message.sender_id,
+        message.body,
+        message.read_at,
+        message.edited_at,
+        message.deleted_at,
+        message.created_at,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(message)
+}
+
+#[instrument(skip(tx))]
+pub async fn edit(tx: &mut DbTransaction<'_>, message: Message) -> SqlxResult<Message> {
+    let message = sqlx::query_as!(
+        Message,
+        r#"
+            UPDATE messages
+            SET (body, edited_at) = ($2, $3)
+            WHERE messages.id = $1
+            RETURNING *;
+        "#,
+        message.id,
+        message.body,
+        message.edited_at,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(message)
+}
+
+#[instrument(skip(tx))]
+pub async fn delete(
+    tx: &mut DbTransaction<'_>,
+    id: Uuid,
+    deleted_at: DateTime<Utc>,
+) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE messages
+            SET (body, deleted_at) = ('', $2)
+            WHERE messages.id = $1;
+        "#,
+        id,
+        deleted_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(tx))]
+pub async fn mark_read(
+    tx: &mut DbTransaction<'_>,
+    order_id: Uuid,
+    reader_id: Uuid,
+    read_at: DateTime<Utc>,
+) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE messages
+            SET read_at = $3
+            WHERE messages.order_id = $1
+              AND messages.sender_id <> $2
+              AND messages.read_at IS NULL;
+        "#,
+        order_id,
+        reader_id,
+        read_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn count_unread(pool: &DbPool, user_id: Uuid) -> SqlxResult<Vec<UnreadCount>> {
+    let counts = sqlx::query_as!(
+        UnreadCount,
+        r#"
+            SELECT messages.order_id, COUNT(*) AS "count!"
+            FROM messages
+            JOIN orders ON orders.id = messages.order_id
+            WHERE (orders.student_id = $1 OR orders.mentor_id = $1)
+              AND messages.sender_id <> $1
+              AND messages.read_at IS NULL
+              AND messages.deleted_at IS NULL
+            GROUP BY messages.order_id
+            ORDER BY messages.order_id;
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(counts)
+}
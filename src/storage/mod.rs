@@ -1,5 +1,48 @@
+pub mod calendar;
+pub mod dispute;
+pub mod email;
+pub mod event;
+pub mod export;
+pub mod health;
+pub mod mentor_profile;
+pub mod message;
+pub mod notification;
+pub mod notification_preference;
+pub mod order;
+pub mod privacy;
+pub mod search;
+pub mod session;
+pub mod subject;
+pub mod upload;
 pub mod user;
 
-use sqlx::PgPool;
+use std::future::Future;
+
+use sqlx::{PgPool, Postgres, Transaction};
+use tracing::warn;
+
+use crate::error::{Error, Result};
 
 pub type DbPool = PgPool;
+pub type DbTransaction<'a> = Transaction<'a, Postgres>;
+
+const MAX_ATTEMPTS: usize = 3;
+
+/// Runs a transaction again when Postgres aborted it in favour of a concurrent one. Only
+/// use this for work that has no side effects outside of the transaction.
+pub async fn retry<T, F, Fut>(mut transaction: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match transaction().await {
+            Err(Error::SerializationFailure) if attempt < MAX_ATTEMPTS => {
+                warn!("Retrying transaction after serialization failure (attempt {attempt})");
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
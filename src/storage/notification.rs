@@ -0,0 +1,126 @@
+use chrono::{DateTime, Utc};
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{models::notification::Notification, pagination::Keyset};
+
+use super::{DbPool, DbTransaction};
+
+#[instrument(skip(pool))]
+pub async fn get_by_id(pool: &DbPool, id: Uuid) -> SqlxResult<Notification> {
+    let notification = sqlx::query_as!(
+        Notification,
+        r#"
+            SELECT *
+            FROM notifications
+            WHERE notifications.id = $1;
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(notification)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_page(
+    pool: &DbPool,
+    user_id: Uuid,
+    unread_only: bool,
+    keyset: &Keyset,
+) -> SqlxResult<Vec<Notification>> {
+    // One statement per direction, so that both walk the index.
+    macro_rules! page {
+        ($cmp:tt, $direction:tt) => {
+            sqlx::query_as!(
+                Notification,
+                r#"
+                    SELECT *
+                    FROM notifications
+                    WHERE notifications.user_id = $1
+                      AND (NOT $2 OR notifications.read_at IS NULL)
+                      AND ($4::UUID IS NULL OR (notifications.created_at, notifications.id) "#
+                    + $cmp
+                    + r#" ($3::TIMESTAMPTZ, $4))
+                    ORDER BY notifications.created_at "#
+                    + $direction
+                    + ", notifications.id "
+                    + $direction
+                    + r#"
+                    LIMIT $5;
+                "#,
+                user_id,
+                unread_only,
+                keyset.after_timestamp(),
+                keyset.after_id,
+                keyset.limit,
+            )
+            .fetch_all(pool)
+            .await?
+        };
+    }
+
+    let notifications = if keyset.descending {
+        page!("<", "DESC")
+    } else {
+        page!(">", "ASC")
+    };
+
+    Ok(notifications)
+}
+
+#[instrument(skip(tx))]
+pub async fn create(tx: &mut DbTransaction<'_>, notification: &Notification) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO notifications (id, user_id, kind, payload, read_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6);
+        "#,
+        notification.id,
+        notification.user_id,
+        notification.kind,
+        notification.payload,
+        notification.read_at,
+        notification.created_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn mark_read(pool: &DbPool, id: Uuid, read_at: DateTime<Utc>) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE notifications
+            SET read_at = $2
+            WHERE notifications.id = $1 AND notifications.read_at IS NULL;
+        "#,
+        id,
+        read_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn mark_all_read(pool: &DbPool, user_id: Uuid, read_at: DateTime<Utc>) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE notifications
+            SET read_at = $2
+            WHERE notifications.user_id = $1 AND notifications.read_at IS NULL;
+        "#,
+        user_id,
+        read_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::models::notification::NotificationPreference;
+
+use super::{DbPool, DbTransaction};
+
+#[instrument(skip(pool))]
+pub async fn get_all(pool: &DbPool, user_id: Uuid) -> SqlxResult<Vec<NotificationPreference>> {
+    let preferences = sqlx::query_as!(
+        NotificationPreference,
+        r#"
+            SELECT kind, email
+            FROM notification_preferences
+            WHERE notification_preferences.user_id = $1;
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(preferences)
+}
+
+#[instrument(skip(tx))]
+pub async fn get_email(
+    tx: &mut DbTransaction<'_>,
+    user_id: Uuid,
+    kind: &str,
+) -> SqlxResult<Option<bool>> {
+    let email = sqlx::query_scalar!(
+        r#"
+            SELECT email
+            FROM notification_preferences
+            WHERE notification_preferences.user_id = $1 AND notification_preferences.kind = $2;
+        "#,
+        user_id,
+        kind,
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    Ok(email)
+}
+
+#[instrument(skip(pool))]
+pub async fn upsert(pool: &DbPool, user_id: Uuid, kind: &str, email: bool) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO notification_preferences (user_id, kind, email)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, kind) DO UPDATE SET email = EXCLUDED.email;
+        "#,
+        user_id,
+        kind,
+        email,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,201 @@
+use chrono::{DateTime, Utc};
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    models::order::{Order, OrderHistoryEntry},
+    pagination::Keyset,
+};
+
+use super::{DbPool, DbTransaction};
+
+#[instrument(skip(pool))]
+pub async fn get_by_id(pool: &DbPool, id: Uuid) -> SqlxResult<Order> {
+    let order = sqlx::query_as!(
+        Order,
+        r#"
+            SELECT *
+            FROM orders
+            WHERE orders.id = $1;
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(order)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_all_by_participant(pool: &DbPool, participant_id: Uuid) -> SqlxResult<Vec<Order>> {
+    let orders = sqlx::query_as!(
+        Order,
+        r#"
+            SELECT *
+            FROM orders
+            WHERE $1 IN (orders.student_id, orders.mentor_id)
+            ORDER BY orders.created_at DESC;
+        "#,
+        participant_id,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(orders)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_page(
+    pool: &DbPool,
+    participant_id: Option<Uuid>,
+    subject: Option<String>,
+    status: Option<&str>,
+    keyset: &Keyset,
+) -> SqlxResult<Vec<Order>> {
+    // One statement per sort column and direction, so that each walks its own index.
+    macro_rules! page {
+        ($column:tt, $key_type:tt, $after:expr) => {
+            if keyset.descending {
+                page!($column, $key_type, $after, "<", "DESC")
+            } else {
+                page!($column, $key_type, $after, ">", "ASC")
+            }
+        };
+        ($column:tt, $key_type:tt, $after:expr, $cmp:tt, $direction:tt) => {
+            sqlx::query_as!(
+                Order,
+                r#"
+                    SELECT *
+                    FROM orders
+                    WHERE ($1::UUID IS NULL OR $1 IN (orders.student_id, orders.mentor_id))
+                      AND ($2::VARCHAR IS NULL OR EXISTS (
+                          SELECT 1
+                          FROM order_subjects
+                          WHERE order_subjects.order_id = orders.id
+                            AND order_subjects.subject_id IN (SELECT id FROM subject_subtree($2))
+                      ))
+                      AND ($3::VARCHAR IS NULL OR orders.status = $3)
+                      AND ($5::UUID IS NULL OR ("# + $column + ", orders.id) " + $cmp + " ($4::" + $key_type + r#", $5))
+                    ORDER BY "# + $column + " " + $direction + ", orders.id " + $direction + r#"
+                    LIMIT $6;
+                "#,
+                participant_id,
+                subject,
+                status,
+                $after,
+                keyset.after_id,
+                keyset.limit,
+            )
+            .fetch_all(pool)
+            .await?
+        };
+    }
+
+    let orders = match keyset.sort {
+        "price" => page!("orders.price", "BIGINT", keyset.after_integer()),
+        "updated_at" => page!("orders.updated_at", "TIMESTAMPTZ", keyset.after_timestamp()),
+        _ => page!("orders.created_at", "TIMESTAMPTZ", keyset.after_timestamp()),
+    };
+
+    Ok(orders)
+}
+
+#[instrument(skip(tx))]
+pub async fn create(tx: &mut DbTransaction<'_>, order: &Order) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO orders (id, student_id, mentor_id, price, title, description, status, deadline, language, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11);
+        "#,
+        order.id,
+        order.student_id,
+        order.mentor_id,
+        order.price,
+        order.title,
+        order.description,
+        order.status,
+        order.deadline,
+        order.language,
+        order.created_at,
+        order.updated_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(tx))]
+pub async fn get_by_id_for_update(tx: &mut DbTransaction<'_>, id: Uuid) -> SqlxResult<Order> {
+    let order = sqlx::query_as!(
+        Order,
+        r#"
+            SELECT *
+            FROM orders
+            WHERE orders.id = $1
+            FOR UPDATE;
+        "#,
+        id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(order)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_history(pool: &DbPool, order_id: Uuid) -> SqlxResult<Vec<OrderHistoryEntry>> {
+    let history = sqlx::query_as!(
+        OrderHistoryEntry,
+        r#"
+            SELECT *
+            FROM order_history
+            WHERE order_history.order_id = $1
+            ORDER BY order_history.created_at;
+        "#,
+        order_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(history)
+}
+
+#[instrument(skip(tx))]
+pub async fn set_status(
+    tx: &mut DbTransaction<'_>,
+    id: Uuid,
+    status: &str,
+    changed_by: Uuid,
+    changed_at: DateTime<Utc>,
+) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE orders
+            SET (status, updated_at) = ($2, $3)
+            WHERE orders.id = $1;
+        "#,
+        id,
+        status,
+        changed_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO order_history (id, order_id, status, changed_by, created_at)
+            VALUES ($1, $2, $3, $4, $5);
+        "#,
+        Uuid::new_v4(),
+        id,
+        status,
+        changed_by,
+        changed_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
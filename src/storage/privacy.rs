@@ -0,0 +1,67 @@
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::models::privacy::PrivacySettings;
+
+use super::DbPool;
+
+#[instrument(skip(pool))]
+pub async fn get(pool: &DbPool, user_id: Uuid) -> SqlxResult<Option<PrivacySettings>> {
+    let settings = sqlx::query_as!(
+        PrivacySettings,
+        r#"
+            SELECT *
+            FROM privacy_settings
+            WHERE privacy_settings.user_id = $1;
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(settings)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_for_users(pool: &DbPool, user_ids: &[Uuid]) -> SqlxResult<Vec<PrivacySettings>> {
+    let settings = sqlx::query_as!(
+        PrivacySettings,
+        r#"
+            SELECT *
+            FROM privacy_settings
+            WHERE privacy_settings.user_id = ANY($1);
+        "#,
+        user_ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(settings)
+}
+
+#[instrument(skip(pool))]
+pub async fn upsert(pool: &DbPool, settings: &PrivacySettings) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO privacy_settings (user_id, name, email, age, about, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_id) DO UPDATE SET
+                name = EXCLUDED.name,
+                email = EXCLUDED.email,
+                age = EXCLUDED.age,
+                about = EXCLUDED.about,
+                updated_at = EXCLUDED.updated_at;
+        "#,
+        settings.user_id,
+        settings.name,
+        settings.email,
+        settings.age,
+        settings.about,
+        settings.updated_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    models::{search::SearchHit, user::User},
+    pagination::Keyset,
+};
+
+use super::DbPool;
+
+#[instrument(skip(pool))]
+pub async fn search(
+    pool: &DbPool,
+    q: &str,
+    language: &str,
+    kind: Option<&str>,
+    viewer_id: Option<Uuid>,
+    is_admin: bool,
+    keyset: &Keyset,
+) -> SqlxResult<Vec<SearchHit>> {
+    // One statement per direction, so that the cursor compares the way the rows are ordered.
+    macro_rules! page {
+        ($cmp:tt, $direction:tt) => {
+            sqlx::query_as!(
+                SearchHit,
+                r#"
+                    WITH query AS (
+                        SELECT
+                            search_config($2) AS config,
+                            WEBSEARCH_TO_TSQUERY(search_config($2), $1)
+                                || WEBSEARCH_TO_TSQUERY('simple', $1) AS tsquery
+                    )
+                    SELECT
+                        hits.id AS "id!",
+                        hits.kind AS "kind!",
+                        hits.order_id,
+                        hits.username,
+                        hits.title AS "title!",
+                        hits.snippet AS "snippet!",
+                        hits.rank AS "rank!"
+                    FROM (
+                        SELECT
+                            orders.id,
+                            'order' AS kind,
+                            orders.id AS order_id,
+                            NULL::VARCHAR AS username,
+                            TS_HEADLINE(order_search.config, html_escape(orders.title), query.tsquery,
+                                'HighlightAll=true, StartSel=<mark>, StopSel=</mark>') AS title,
+                            TS_HEADLINE(order_search.config, html_escape(orders.description), query.tsquery,
+                                'MaxFragments=2, MaxWords=24, MinWords=8, StartSel=<mark>, StopSel=</mark>') AS snippet,
+                            TS_RANK_CD(order_search.document, query.tsquery) AS rank
+                        FROM orders
+                        JOIN order_search ON order_search.order_id = orders.id
+                        CROSS JOIN query
+                        WHERE ($3::VARCHAR IS NULL OR $3 = 'order')
+                          AND ($5 OR $4 IN (orders.student_id, orders.mentor_id))
+                          AND order_search.document @@ query.tsquery
+                        UNION ALL
+                        SELECT
+                            users.id,
+                            'mentor' AS kind,
+                            NULL::UUID AS order_id,
+                            users.username,
+                            TS_HEADLINE(mentor_search.config, html_escape(mentor_profiles.headline), query.tsquery,
+                                'HighlightAll=true, StartSel=<mark>, StopSel=</mark>') AS title,
+                            TS_HEADLINE(mentor_search.config, html_escape(CONCAT_WS(' ',
+                                CASE WHEN COALESCE(privacy_settings.about, 'public') = 'public' THEN users.about END,
+                                mentor_profiles.education)), query.tsquery,
+                                'MaxFragments=2, MaxWords=24, MinWords=8, StartSel=<mark>, StopSel=</mark>') AS snippet,
+                            TS_RANK_CD(mentor_search.document, query.tsquery) AS rank
+                        FROM mentor_profiles
+                        JOIN users ON users.id = mentor_profiles.user_id
+                        JOIN mentor_search ON mentor_search.user_id = mentor_profiles.user_id
+                        LEFT JOIN privacy_settings ON privacy_settings.user_id = mentor_profiles.user_id
+                        CROSS JOIN query
+                        WHERE ($3::VARCHAR IS NULL OR $3 = 'mentor')
+                          AND users.deleted_at IS NULL
+                          AND mentor_search.document @@ query.tsquery
+                    ) AS hits
+                    WHERE $7::UUID IS NULL OR (hits.rank, hits.id) "#
+                    + $cmp
+                    + r#" ($6::REAL, $7)
+                    ORDER BY hits.rank "#
+                    + $direction
+                    + ", hits.id "
+                    + $direction
+                    + r#"
+                    LIMIT $8;
+                "#,
+                q,
+                language,
+                kind,
+                viewer_id,
+                is_admin,
+                keyset.after_float(),
+                keyset.after_id,
+                keyset.limit,
+            )
+            .fetch_all(pool)
+            .await?
+        };
+    }
+
+    let hits = if keyset.descending {
+        page!("<", "DESC")
+    } else {
+        page!(">", "ASC")
+    };
+
+    Ok(hits)
+}
+
+#[instrument(skip(pool))]
+pub async fn find_usernames(pool: &DbPool, q: &str, limit: i64) -> SqlxResult<Vec<User>> {
+    let users = sqlx::query_as!(
+        User,
+        r#"
+            SELECT *
+            FROM users
+            WHERE (users.username % $1 OR STARTS_WITH(LOWER(users.username), $1))
+              AND users.deleted_at IS NULL
+            ORDER BY SIMILARITY(users.username, $1) DESC, users.username
+            LIMIT $2;
+        "#,
+        q,
+        limit,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(users)
+}
@@ -0,0 +1,187 @@
+use chrono::{DateTime, Utc};
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::models::session::{Session, SessionStatus};
+
+use super::{DbPool, DbTransaction};
+
+#[instrument(skip(pool))]
+pub async fn get_by_id(pool: &DbPool, id: Uuid) -> SqlxResult<Session> {
+    let session = sqlx::query_as!(
+        Session,
+        r#"
+            SELECT *
+            FROM sessions
+            WHERE sessions.id = $1;
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(session)
+}
+
+#[instrument(skip(tx))]
+pub async fn get_by_id_for_update(tx: &mut DbTransaction<'_>, id: Uuid) -> SqlxResult<Session> {
+    let session = sqlx::query_as!(
+        Session,
+        r#"
+            SELECT *
+            FROM sessions
+            WHERE sessions.id = $1
+            FOR UPDATE;
+        "#,
+        id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(session)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_by_order(pool: &DbPool, order_id: Uuid) -> SqlxResult<Vec<Session>> {
+    let sessions = sqlx::query_as!(
+        Session,
+        r#"
+            SELECT *
+            FROM sessions
+            WHERE sessions.order_id = $1
+            ORDER BY sessions.starts_at;
+        "#,
+        order_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(sessions)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_for_user(
+    pool: &DbPool,
+    user_id: Uuid,
+    from: DateTime<Utc>,
+    to: Option<DateTime<Utc>>,
+) -> SqlxResult<Vec<Session>> {
+    let sessions = sqlx::query_as!(
+        Session,
+        r#"
+            SELECT *
+            FROM sessions
+            WHERE $1 IN (sessions.mentor_id, sessions.student_id)
+              AND sessions.ends_at > $2
+              AND ($3::TIMESTAMPTZ IS NULL OR sessions.starts_at < $3)
+            ORDER BY sessions.starts_at;
+        "#,
+        user_id,
+        from,
+        to,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(sessions)
+}
+
+#[instrument(skip(pool))]
+pub async fn is_available(
+    pool: &DbPool,
+    mentor_id: Uuid,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+) -> SqlxResult<bool> {
+    let is_available = sqlx::query_scalar!(
+        r#"
+            SELECT mentor_is_available($1, $2, $3) AS "available!";
+        "#,
+        mentor_id,
+        starts_at,
+        ends_at,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(is_available)
+}
+
+#[instrument(skip(pool))]
+pub async fn create(pool: &DbPool, session: &Session) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO sessions (id, order_id, mentor_id, student_id, starts_at, ends_at, status, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9);
+        "#,
+        session.id,
+        session.order_id,
+        session.mentor_id,
+        session.student_id,
+        session.starts_at,
+        session.ends_at,
+        session.status,
+        session.created_at,
+        session.updated_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(tx))]
+pub async fn reschedule(
+    tx: &mut DbTransaction<'_>,
+    id: Uuid,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+) -> SqlxResult<Session> {
+    let session = sqlx::query_as!(
+        Session,
+        r#"
+            UPDATE sessions
+            SET starts_at = $2, ends_at = $3, sequence = sessions.sequence + 1, updated_at = NOW()
+            WHERE sessions.id = $1
+            RETURNING *;
+        "#,
+        id,
+        starts_at,
+        ends_at,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(session)
+}
+
+#[instrument(skip(tx))]
+pub async fn cancel(
+    tx: &mut DbTransaction<'_>,
+    id: Uuid,
+    cancelled_by: Uuid,
+    reason: Option<String>,
+) -> SqlxResult<Session> {
+    let session = sqlx::query_as!(
+        Session,
+        r#"
+            UPDATE sessions
+            SET status = $2,
+                cancelled_by = $3,
+                cancellation_reason = $4,
+                sequence = sessions.sequence + 1,
+                updated_at = NOW()
+            WHERE sessions.id = $1
+            RETURNING *;
+        "#,
+        id,
+        SessionStatus::Cancelled.as_str(),
+        cancelled_by,
+        reason,
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(session)
+}
@@ -0,0 +1,241 @@
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::models::subject::Subject;
+
+use super::{DbPool, DbTransaction};
+
+#[instrument(skip(pool))]
+pub async fn get_all(pool: &DbPool) -> SqlxResult<Vec<Subject>> {
+    let subjects = sqlx::query_as!(
+        Subject,
+        r#"
+            SELECT *
+            FROM subjects
+            ORDER BY subjects.name;
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(subjects)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_by_id(pool: &DbPool, id: Uuid) -> SqlxResult<Subject> {
+    let subject = sqlx::query_as!(
+        Subject,
+        r#"
+            SELECT *
+            FROM subjects
+            WHERE subjects.id = $1;
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(subject)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_by_user(pool: &DbPool, user_id: Uuid) -> SqlxResult<Vec<Subject>> {
+    let subjects = sqlx::query_as!(
+        Subject,
+        r#"
+            SELECT subjects.*
+            FROM subjects
+            JOIN mentor_subjects ON mentor_subjects.subject_id = subjects.id
+            WHERE mentor_subjects.user_id = $1
+            ORDER BY subjects.name;
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(subjects)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_by_order(pool: &DbPool, order_id: Uuid) -> SqlxResult<Vec<Subject>> {
+    let subjects = sqlx::query_as!(
+        Subject,
+        r#"
+            SELECT subjects.*
+            FROM subjects
+            JOIN order_subjects ON order_subjects.subject_id = subjects.id
+            WHERE order_subjects.order_id = $1
+            ORDER BY subjects.name;
+        "#,
+        order_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(subjects)
+}
+
+#[instrument(skip(pool))]
+pub async fn count_existing(pool: &DbPool, ids: &[Uuid]) -> SqlxResult<i64> {
+    let count = sqlx::query_scalar!(
+        r#"
+            SELECT COUNT(*) AS "count!"
+            FROM subjects
+            WHERE subjects.id = ANY($1);
+        "#,
+        ids
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(count)
+}
+
+#[instrument(skip(pool))]
+pub async fn has_children(pool: &DbPool, id: Uuid) -> SqlxResult<bool> {
+    let has_children = sqlx::query_scalar!(
+        r#"
+            SELECT EXISTS (
+                SELECT 1
+                FROM subjects
+                WHERE subjects.parent_id = $1
+            ) AS "exists!";
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(has_children)
+}
+
+#[instrument(skip(pool))]
+pub async fn is_descendant(pool: &DbPool, ancestor_id: Uuid, id: Uuid) -> SqlxResult<bool> {
+    let is_descendant = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree AS (
+                SELECT subjects.id FROM subjects WHERE subjects.id = $1
+                UNION
+                SELECT subjects.id FROM subjects JOIN subtree ON subjects.parent_id = subtree.id
+            )
+            SELECT EXISTS (
+                SELECT 1
+                FROM subtree
+                WHERE subtree.id = $2
+            ) AS "exists!";
+        "#,
+        ancestor_id,
+        id,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(is_descendant)
+}
+
+#[instrument(skip(pool))]
+pub async fn create(pool: &DbPool, subject: Subject) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO subjects (id, parent_id, slug, name, created_at)
+            VALUES ($1, $2, $3, $4, $5);
+        "#,
+        subject.id,
+        subject.parent_id,
+        subject.slug,
+        subject.name,
+        subject.created_at,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn edit(pool: &DbPool, subject: Subject) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE subjects
+            SET (parent_id, slug, name) = ($2, $3, $4)
+            WHERE subjects.id = $1;
+        "#,
+        subject.id,
+        subject.parent_id,
+        subject.slug,
+        subject.name,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn delete(pool: &DbPool, id: Uuid) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            DELETE FROM subjects
+            WHERE subjects.id = $1;
+        "#,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(tx))]
+pub async fn set_for_user(
+    tx: &mut DbTransaction<'_>,
+    user_id: Uuid,
+    subject_ids: &[Uuid],
+) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            DELETE FROM mentor_subjects
+            WHERE mentor_subjects.user_id = $1;
+        "#,
+        user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO mentor_subjects (user_id, subject_id)
+            SELECT $1, UNNEST($2::UUID[])
+            ON CONFLICT DO NOTHING;
+        "#,
+        user_id,
+        subject_ids,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(tx))]
+pub async fn set_for_order(
+    tx: &mut DbTransaction<'_>,
+    order_id: Uuid,
+    subject_ids: &[Uuid],
+) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO order_subjects (order_id, subject_id)
+            SELECT $1, UNNEST($2::UUID[])
+            ON CONFLICT DO NOTHING;
+        "#,
+        order_id,
+        subject_ids,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
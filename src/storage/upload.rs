@@ -0,0 +1,119 @@
+use sqlx::Result as SqlxResult;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::models::upload::Upload;
+
+use super::{DbPool, DbTransaction};
+
+#[instrument(skip(pool))]
+pub async fn get_by_id(pool: &DbPool, id: Uuid) -> SqlxResult<Upload> {
+    let upload = sqlx::query_as!(
+        Upload,
+        r#"
+            SELECT *
+            FROM uploads
+            WHERE uploads.id = $1;
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(upload)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_avatar(pool: &DbPool, owner_id: Uuid) -> SqlxResult<Option<Upload>> {
+    let upload = sqlx::query_as!(
+        Upload,
+        r#"
+            SELECT *
+            FROM uploads
+            WHERE uploads.owner_id = $1 AND uploads.kind = 'avatar';
+        "#,
+        owner_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(upload)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_by_order(pool: &DbPool, order_id: Uuid) -> SqlxResult<Vec<Upload>> {
+    let uploads = sqlx::query_as!(
+        Upload,
+        r#"
+            SELECT *
+            FROM uploads
+            WHERE uploads.order_id = $1
+            ORDER BY uploads.created_at, uploads.id;
+        "#,
+        order_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(uploads)
+}
+
+#[instrument(skip(tx))]
+pub async fn create(tx: &mut DbTransaction<'_>, upload: &Upload) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO uploads (id, owner_id, order_id, kind, file_name, content_type, size, storage_key, thumbnail_key, scan_status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11);
+        "#,
+        upload.id,
+        upload.owner_id,
+        upload.order_id,
+        upload.kind,
+        upload.file_name,
+        upload.content_type,
+        upload.size,
+        upload.storage_key,
+        upload.thumbnail_key,
+        upload.scan_status,
+        upload.created_at,
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
+#[instrument(skip(tx))]
+pub async fn delete_avatar(
+    tx: &mut DbTransaction<'_>,
+    owner_id: Uuid,
+) -> SqlxResult<Option<Upload>> {
+    let upload = sqlx::query_as!(
+        Upload,
+        r#"
+            DELETE FROM uploads
+            WHERE uploads.owner_id = $1 AND uploads.kind = 'avatar'
+            RETURNING *;
+        "#,
+        owner_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    Ok(upload)
+}
+
+#[instrument(skip(pool))]
+pub async fn delete(pool: &DbPool, id: Uuid) -> SqlxResult<()> {
+    sqlx::query!(
+        r#"
+            DELETE FROM uploads
+            WHERE uploads.id = $1;
+        "#,
+        id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
@@ -1,22 +1,63 @@
+use chrono::{DateTime, Utc};
 use sqlx::Result as SqlxResult;
 use tracing::instrument;
 use uuid::Uuid;
 
-use crate::models::user::User;
+use crate::{models::user::User, pagination::Keyset};
 
-use super::DbPool;
+use super::{DbPool, DbTransaction};
 
 #[instrument(skip(pool))]
-pub async fn get_all(pool: &DbPool) -> SqlxResult<Vec<User>> {
-    let users = sqlx::query_as!(
-        User,
-        r#"
-            SELECT *
-            FROM users;
-        "#
-    )
-    .fetch_all(pool)
-    .await?;
+pub async fn get_page(
+    pool: &DbPool,
+    subject: Option<String>,
+    verified: Option<bool>,
+    include_deleted: bool,
+    keyset: &Keyset,
+) -> SqlxResult<Vec<User>> {
+    // One statement per sort column and direction, so that each walks its own index.
+    macro_rules! page {
+        ($column:tt, $key_type:tt, $after:expr) => {
+            if keyset.descending {
+                page!($column, $key_type, $after, "<", "DESC")
+            } else {
+                page!($column, $key_type, $after, ">", "ASC")
+            }
+        };
+        ($column:tt, $key_type:tt, $after:expr, $cmp:tt, $direction:tt) => {
+            sqlx::query_as!(
+                User,
+                r#"
+                    SELECT *
+                    FROM users
+                    WHERE ($1::VARCHAR IS NULL OR EXISTS (
+                            SELECT 1
+                            FROM mentor_subjects
+                            WHERE mentor_subjects.user_id = users.id
+                              AND mentor_subjects.subject_id IN (SELECT id FROM subject_subtree($1))
+                        ))
+                      AND ($2::BOOLEAN IS NULL OR users.verified = $2)
+                      AND ($3 OR users.deleted_at IS NULL)
+                      AND ($5::UUID IS NULL OR ("# + $column + ", users.id) " + $cmp + " ($4::" + $key_type + r#", $5))
+                    ORDER BY "# + $column + " " + $direction + ", users.id " + $direction + r#"
+                    LIMIT $6;
+                "#,
+                subject,
+                verified,
+                include_deleted,
+                $after,
+                keyset.after_id,
+                keyset.limit,
+            )
+            .fetch_all(pool)
+            .await?
+        };
+    }
+
+    let users = match keyset.sort {
+        "username" => page!("users.username", "VARCHAR", keyset.after_text()),
+        _ => page!("users.created_at", "TIMESTAMPTZ", keyset.after_timestamp()),
+    };
 
     Ok(users)
 }
@@ -38,6 +79,25 @@ pub async fn get_by_id(pool: &DbPool, id: Uuid) -> SqlxResult<User> {
     Ok(user)
 }
 
+// Holds off a concurrent purge until whatever is being sent to the user is committed.
+#[instrument(skip(tx))]
+pub async fn get_by_id_for_share(tx: &mut DbTransaction<'_>, id: Uuid) -> SqlxResult<User> {
+    let user = sqlx::query_as!(
+        User,
+        r#"
+            SELECT *
+            FROM users
+            WHERE users.id = $1
+            FOR SHARE;
+        "#,
+        id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(user)
+}
+
 #[instrument(skip(pool))]
 pub async fn get_by_email(pool: &DbPool, email: String) -> SqlxResult<User> {
     let user = sqlx::query_as!(
@@ -72,6 +132,62 @@ pub async fn get_by_username(pool: &DbPool, username: String) -> SqlxResult<User
     Ok(user)
 }
 
+#[instrument(skip(pool))]
+pub async fn get_active_by_username(pool: &DbPool, username: String) -> SqlxResult<User> {
+    let user = sqlx::query_as!(
+        User,
+        r#"
+            SELECT *
+            FROM users
+            WHERE users.username = $1 AND users.deleted_at IS NULL;
+        "#,
+        username
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(user)
+}
+
+#[instrument(skip(pool))]
+pub async fn is_active(pool: &DbPool, id: Uuid) -> SqlxResult<bool> {
+    let active = sqlx::query_scalar!(
+        r#"
+            SELECT users.deleted_at IS NULL AS "active!"
+            FROM users
+            WHERE users.id = $1;
+        "#,
+        id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(active)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_expired_deletions(
+    pool: &DbPool,
+    before: DateTime<Utc>,
+    limit: i64,
+) -> SqlxResult<Vec<Uuid>> {
+    let ids = sqlx::query_scalar!(
+        r#"
+            SELECT users.id
+            FROM users
+            WHERE users.deleted_at < $1 AND users.purged_at IS NULL
+            ORDER BY users.deleted_at
+            LIMIT $2;
+        "#,
+        before,
+        limit,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ids)
+}
+
 #[instrument(skip(pool))]
 pub async fn create(pool: &DbPool, user: User) -> SqlxResult<()> {
     sqlx::query!(
@@ -102,7 +218,7 @@ pub async fn edit(pool: &DbPool, user: User) -> SqlxResult<()> {
     sqlx::query!(
         r#"
             UPDATE users 
-            SET (first_name, last_name, username, age, about) = ($2, $3, $4, $5, $6)
+            SET (first_name, last_name, username, age, about, language) = ($2, $3, $4, $5, $6, $7)
             WHERE users.id = $1;
         "#,
         user.id,
@@ -111,6 +227,7 @@ pub async fn edit(pool: &DbPool, user: User) -> SqlxResult<()> {
         user.username,
         user.age,
         user.about,
+        user.language,
     )
     .execute(pool)
     .await?;
@@ -153,16 +270,50 @@ pub async fn edit_password(pool: &DbPool, user: User) -> SqlxResult<()> {
 }
 
 #[instrument(skip(pool))]
-pub async fn delete(pool: &DbPool, id: Uuid) -> SqlxResult<()> {
+pub async fn mark_deleted(pool: &DbPool, id: Uuid, at: DateTime<Utc>) -> SqlxResult<()> {
     sqlx::query!(
         r#"
-            DELETE FROM users
-            WHERE users.id = $1;
+            UPDATE users
+            SET deleted_at = $2
+            WHERE users.id = $1 AND users.deleted_at IS NULL;
         "#,
-        id
+        id,
+        at,
     )
     .execute(pool)
     .await?;
 
     Ok(())
 }
+
+#[instrument(skip(pool))]
+pub async fn restore(pool: &DbPool, id: Uuid, deleted_after: DateTime<Utc>) -> SqlxResult<bool> {
+    let result = sqlx::query!(
+        r#"
+            UPDATE users
+            SET deleted_at = NULL
+            WHERE users.id = $1 AND users.deleted_at > $2 AND users.purged_at IS NULL;
+        "#,
+        id,
+        deleted_after,
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[instrument(skip(pool))]
+pub async fn purge(pool: &DbPool, id: Uuid, at: DateTime<Utc>) -> SqlxResult<bool> {
+    let removed = sqlx::query_scalar!(
+        r#"
+            SELECT purge_user($1, $2) AS "removed!";
+        "#,
+        id,
+        at,
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(removed)
+}
@@ -0,0 +1,143 @@
+mod sql;
+
+use axum::http::{HeaderMap, HeaderName};
+use once_cell::sync::OnceCell;
+use opentelemetry::{
+    propagation::{Extractor, TextMapPropagator},
+    trace::TracerProvider as _,
+    Context, KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{
+    export::trace::SpanExporter,
+    propagation::TraceContextPropagator,
+    runtime,
+    trace::{self, Sampler, TracerProvider},
+    Resource,
+};
+use tracing::Subscriber;
+use tracing_log::LogTracer;
+use tracing_subscriber::{
+    filter::LevelFilter, fmt::MakeWriter, layer::SubscriberExt, registry::LookupSpan,
+    util::SubscriberInitExt, EnvFilter, Layer, Registry,
+};
+
+use crate::config::telemetry::{LogFormat, TelemetryConfig};
+
+use self::sql::SqlSpans;
+
+static PROVIDER: OnceCell<TracerProvider> = OnceCell::new();
+
+pub struct Telemetry;
+
+impl Telemetry {
+    pub fn initialize(config: &TelemetryConfig) {
+        let filter = match std::env::var(EnvFilter::DEFAULT_ENV) {
+            Ok(directives) => EnvFilter::try_new(directives),
+            Err(_) => EnvFilter::try_new(config.log_filter()),
+        }
+        .expect("Failed to initialize env filter!");
+        let traces = config.otlp_endpoint().map(|endpoint| {
+            let exporter = opentelemetry_otlp::new_exporter()
+                .http()
+                .with_endpoint(endpoint)
+                .build_span_exporter()
+                .expect("Failed to build OTLP exporter!");
+            let provider = TracerProvider::builder()
+                .with_batch_exporter(exporter, runtime::Tokio)
+                .with_config(Self::trace_config(config))
+                .build();
+            let _ = PROVIDER.set(provider.clone());
+            Self::export(provider)
+        });
+        Registry::default()
+            .with(Self::logs(config.log_format(), filter, std::io::stdout))
+            .with(traces)
+            .init();
+        LogTracer::init().expect("Failed to set logger!");
+    }
+
+    /// A subscriber that writes logs as configured to `writer`, ignoring `RUST_LOG`, so
+    /// tests can inspect exactly what would have been logged.
+    pub fn logging_to<W>(config: &TelemetryConfig, writer: W) -> impl Subscriber + Send + Sync
+    where
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    {
+        let filter = EnvFilter::try_new(config.log_filter()).expect("Invalid log filter!");
+        Registry::default().with(Self::logs(config.log_format(), filter, writer))
+    }
+
+    /// A subscriber that sends every span to `exporter` and logs nothing, so tests can
+    /// inspect what would have been exported.
+    pub fn exporting_to<E: SpanExporter + 'static>(exporter: E) -> impl Subscriber + Send + Sync {
+        let provider = TracerProvider::builder()
+            .with_simple_exporter(exporter)
+            .with_config(Self::trace_config(&TelemetryConfig::default()))
+            .build();
+        Registry::default().with(Self::export(provider))
+    }
+
+    /// Flushes spans that are still waiting to be exported.
+    pub fn shutdown() {
+        if let Some(provider) = PROVIDER.get() {
+            provider.force_flush();
+        }
+    }
+
+    fn logs<S, W>(format: LogFormat, filter: EnvFilter, writer: W) -> impl Layer<S>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+    {
+        let layer = tracing_subscriber::fmt::layer().with_writer(writer);
+        let layer = match format {
+            LogFormat::Pretty => layer.pretty().boxed(),
+            LogFormat::Compact => layer.compact().boxed(),
+            LogFormat::Json => layer
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .boxed(),
+        };
+        layer.with_filter(filter)
+    }
+
+    fn trace_config(config: &TelemetryConfig) -> trace::Config {
+        trace::config()
+            .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+                config.sample_ratio(),
+            ))))
+            .with_resource(Resource::new([KeyValue::new(
+                "service.name",
+                config.service_name().to_owned(),
+            )]))
+    }
+
+    fn export<S>(provider: TracerProvider) -> impl Layer<S>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
+        tracing_opentelemetry::layer()
+            .with_tracer(tracer.clone())
+            .and_then(SqlSpans::new(tracer, provider))
+            .with_filter(LevelFilter::INFO)
+    }
+}
+
+/// The trace a request continues, taken from its W3C `traceparent` and `tracestate` headers.
+pub(crate) fn remote_context(headers: &HeaderMap) -> Context {
+    TraceContextPropagator::new().extract(&HeaderExtractor(headers))
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(HeaderName::as_str).collect()
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    fmt,
+    time::{Duration, SystemTime},
+};
+
+use opentelemetry::{
+    trace::{Span, SpanKind, Tracer as _},
+    KeyValue,
+};
+use opentelemetry_sdk::trace::{Tracer, TracerProvider};
+use tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use tracing_log::NormalizeEvent;
+use tracing_opentelemetry::{OtelData, PreSampledTracer};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+const TARGET: &str = "sqlx::query";
+
+/// sqlx only logs statements once they finished, so this turns those log lines into spans
+/// under whichever span ran the query.
+pub struct SqlSpans {
+    tracer: Tracer,
+    // Tracers only hold a weak reference to their provider, which would stop exporting
+    // as soon as nothing else owns it.
+    _provider: TracerProvider,
+}
+
+impl SqlSpans {
+    pub fn new(tracer: Tracer, provider: TracerProvider) -> Self {
+        Self {
+            tracer,
+            _provider: provider,
+        }
+    }
+}
+
+impl<S> Layer<S> for SqlSpans
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let target = event
+            .normalized_metadata()
+            .map_or_else(|| event.metadata().target(), |metadata| metadata.target());
+        if target != TARGET {
+            return;
+        }
+        let Some(span) = ctx.event_span(event) else {
+            return;
+        };
+        let mut message = Message::default();
+        event.record(&mut message);
+        let Some(query) = Query::parse(&message.0) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        let Some(data) = extensions.get_mut::<OtelData>() else {
+            return;
+        };
+        let parent = self.tracer.sampled_context(data);
+
+        let end = SystemTime::now();
+        self.tracer
+            .span_builder(query.operation().to_owned())
+            .with_kind(SpanKind::Client)
+            .with_start_time(end - query.elapsed)
+            .with_attributes(vec![
+                KeyValue::new("db.system", "postgresql"),
+                KeyValue::new("db.statement", query.statement.to_owned()),
+            ])
+            .start_with_context(&self.tracer, &parent)
+            .end_with_timestamp(end);
+    }
+}
+
+#[derive(Default)]
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{value:?}");
+        }
+    }
+}
+
+struct Query<'a> {
+    statement: &'a str,
+    elapsed: Duration,
+}
+
+impl<'a> Query<'a> {
+    // Logged as "<summary>; rows affected: 0, rows returned: 1, elapsed: 1.234ms", followed
+    // by the whole statement after a blank line when the summary had to shorten it.
+    fn parse(message: &'a str) -> Option<Self> {
+        let (summary, rest) = message.split_once("; rows affected: ")?;
+        let (stats, statement) = rest.split_once("\n\n").unwrap_or((rest, summary));
+        let (_, elapsed) = stats.split_once("elapsed: ")?;
+        Some(Self {
+            statement: statement.trim(),
+            elapsed: parse_duration(elapsed.trim())?,
+        })
+    }
+
+    fn operation(&self) -> &'a str {
+        self.statement.split_whitespace().next().unwrap_or("SQL")
+    }
+}
+
+// The inverse of `Duration`'s `Debug` output, e.g. "12.345µs".
+fn parse_duration(value: &str) -> Option<Duration> {
+    let unit = value.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let amount = value[..unit].parse::<f64>().ok()?;
+    let seconds = match &value[unit..] {
+        "ns" => amount / 1e9,
+        "µs" => amount / 1e6,
+        "ms" => amount / 1e3,
+        "s" => amount,
+        _ => return None,
+    };
+    Some(Duration::from_secs_f64(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Both shapes are copied from sqlx-core 0.6's `QueryLogger::finish`; update them together
+    // with the parser when upgrading sqlx.
+    #[test]
+    fn parses_short_statements() {
+        let query =
+            Query::parse("SELECT 1; rows affected: 0, rows returned: 1, elapsed: 1.234ms").unwrap();
+
+        assert_eq!(query.statement, "SELECT 1");
+        assert_eq!(query.operation(), "SELECT");
+        assert_eq!(query.elapsed.as_nanos(), 1_234_000);
+    }
+
+    #[test]
+    fn parses_shortened_statements() {
+        let message = "UPDATE users SET username …; rows affected: 1, rows returned: 0, \
+            elapsed: 12.500µs\n\nUPDATE\n  users\nSET\n  username = $1\nWHERE\n  id = $2\n";
+        let query = Query::parse(message).unwrap();
+
+        assert_eq!(
+            query.statement,
+            "UPDATE\n  users\nSET\n  username = $1\nWHERE\n  id = $2"
+        );
+        assert_eq!(query.operation(), "UPDATE");
+        assert_eq!(query.elapsed.as_nanos(), 12_500);
+    }
+
+    #[test]
+    fn ignores_other_messages() {
+        assert!(Query::parse("relation \"users\" already exists, skipping").is_none());
+        assert!(Query::parse("SELECT 1; rows affected: 0, elapsed: soon").is_none());
+    }
+}
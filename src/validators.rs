@@ -1,10 +1,37 @@
 use validator::ValidationError;
 
+use crate::i18n::Locale;
+
 pub fn is_lowercase_alphanumeric(s: &str) -> Result<(), ValidationError> {
     s.chars()
         .all(|c| (c.is_alphanumeric() && c.is_lowercase()) || c == '_')
         .then_some(())
-        .ok_or(ValidationError::new(
-            "Only lowercase and alphabetic are allowed",
-        ))
+        .ok_or(ValidationError::new("lowercase_alphanumeric"))
+}
+
+pub fn is_slug(s: &str) -> Result<(), ValidationError> {
+    s.chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        .then_some(())
+        .ok_or(ValidationError::new("slug"))
+}
+
+pub fn is_time_zone(s: &str) -> Result<(), ValidationError> {
+    s.parse::<chrono_tz::Tz>()
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("time_zone"))
+}
+
+pub fn are_languages(languages: &[String]) -> Result<(), ValidationError> {
+    languages
+        .iter()
+        .all(|language| (2..=32).contains(&language.chars().count()))
+        .then_some(())
+        .ok_or(ValidationError::new("languages"))
+}
+
+pub fn is_supported_language(s: &str) -> Result<(), ValidationError> {
+    s.parse::<Locale>()
+        .map(|_| ())
+        .map_err(|()| ValidationError::new("supported_language"))
 }
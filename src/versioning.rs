@@ -0,0 +1,54 @@
+use axum::{
+    extract::State,
+    http::{
+        header::{LINK, USER_AGENT},
+        HeaderName, HeaderValue, Request,
+    },
+    middleware::Next,
+    response::Response,
+};
+use tracing::warn;
+
+use crate::config::versioning::VersioningPolicy;
+
+/// Prefix of the current API version. Unversioned paths are deprecated aliases of it.
+pub const PREFIX: &str = "/v1";
+
+const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Marks responses from unversioned paths as deprecated and points to their successor.
+pub async fn deprecate<B>(
+    State(policy): State<VersioningPolicy>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    let uri = request.uri();
+    // Only the path: the query may carry credentials, such as the WebSocket `token`.
+    let successor = format!("{PREFIX}{}", uri.path());
+    let user_agent = request
+        .headers()
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    warn!(
+        method = %request.method(),
+        path = uri.path(),
+        user_agent,
+        "Deprecated unversioned endpoint called"
+    );
+
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(DEPRECATION, HeaderValue::from_static("true"));
+    if let Some(sunset) = policy
+        .sunset()
+        .and_then(|at| HeaderValue::from_str(&at).ok())
+    {
+        headers.insert(SUNSET, sunset);
+    }
+    if let Ok(link) = HeaderValue::from_str(&format!("<{successor}>; rel=\"successor-version\"")) {
+        headers.append(LINK, link);
+    }
+    response
+}
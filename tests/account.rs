@@ -0,0 +1,249 @@
+pub mod common;
+
+use hyper::StatusCode;
+use s4s::state::AppState;
+use serde_json::{json, Value};
+use sqlx::types::Uuid;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
+
+async fn signup(app: &mut TestApp) -> TestResult<(Value, String)> {
+    let signup_form = TestApp::fake_signup_form_json();
+    let request = TestRequest::post("/auth/signup")
+        .with_json(signup_form.clone())
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let token = TestApp::body_to_token(response.into_body()).await?;
+    Ok((TestApp::fake_login_form_json(&signup_form), token))
+}
+
+async fn delete(app: &mut TestApp, token: &str) -> TestResult<()> {
+    let request = TestRequest::delete("/users/me").with_auth(token).build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::NO_CONTENT);
+    Ok(())
+}
+
+async fn backdate_deletion(pool: &DbPool, username: &str, days: i32) -> TestResult<()> {
+    sqlx::query(
+        "UPDATE users SET deleted_at = NOW() - MAKE_INTERVAL(days => $2) WHERE username = $1",
+    )
+    .bind(username)
+    .bind(days)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+#[sqlx::test]
+fn deleted_account_is_locked_and_hidden(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let (login_form, token) = signup(&mut app).await?;
+    let username = login_form["username"].as_str().unwrap().to_owned();
+    delete(&mut app, &token).await?;
+
+    let request = TestRequest::post("/auth/login")
+        .with_json(login_form)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::get("/users/me").with_auth(&token).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::get("/users/me/notifications")
+        .with_auth(&token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::get(format!("/users/{username}")).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::NOT_FOUND);
+
+    let request = TestRequest::get("/users").build()?;
+    let response = app.oneshot(request).await?;
+    let page = TestApp::body_to_json(response.into_body()).await?;
+    assert!(page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|user| user["username"] != json!(username)));
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn restore_within_grace_period(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let (login_form, token) = signup(&mut app).await?;
+
+    let request = TestRequest::post("/auth/restore")
+        .with_json(login_form.clone())
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::CONFLICT);
+
+    delete(&mut app, &token).await?;
+
+    let wrong_password = json!({
+        "username": login_form["username"],
+        "password": "notthepassword",
+    });
+    let request = TestRequest::post("/auth/restore")
+        .with_json(wrong_password)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::UNAUTHORIZED);
+
+    let request = TestRequest::post("/auth/restore")
+        .with_json(login_form.clone())
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::OK);
+
+    let request = TestRequest::get("/users/me").with_auth(&token).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "username": login_form["username"] }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn restore_after_grace_period(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let (login_form, token) = signup(&mut app).await?;
+    delete(&mut app, &token).await?;
+    backdate_deletion(&pool, login_form["username"].as_str().unwrap(), 31).await?;
+
+    let request = TestRequest::post("/auth/restore")
+        .with_json(login_form)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::GONE);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn purge_removes_or_anonymises_expired_accounts(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let loner = app.signup().await?;
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let recent = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    for user in [&loner, &student, &recent] {
+        delete(&mut app, &user.token).await?;
+    }
+    backdate_deletion(&pool, &loner.username, 31).await?;
+    backdate_deletion(&pool, &student.username, 31).await?;
+    let (addresses,): (Vec<String>,) =
+        sqlx::query_as("SELECT ARRAY_AGG(email) FROM users WHERE id = ANY($1)")
+            .bind(vec![loner.id, student.id])
+            .fetch_one(&pool)
+            .await?;
+    sqlx::query(
+        "INSERT INTO emails (id, address, subject, body, created_at) \
+         SELECT gen_random_uuid(), UNNEST($1::VARCHAR[]), 'Subject', 'Body', NOW()",
+    )
+    .bind(&addresses)
+    .execute(&pool)
+    .await?;
+
+    let purged = AppState::new(pool.clone())
+        .purge_deleted_accounts()
+        .await
+        .unwrap();
+    assert_eq!(purged, 2);
+
+    let (queued,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM emails WHERE address = ANY($1)")
+        .bind(&addresses)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(queued, 0);
+
+    let remaining: Vec<(Uuid, String, Option<String>, bool)> = sqlx::query_as(
+        "SELECT id, email, first_name, purged_at IS NOT NULL FROM users WHERE id = ANY($1)",
+    )
+    .bind(vec![loner.id, student.id, recent.id])
+    .fetch_all(&pool)
+    .await?;
+    assert_eq!(remaining.len(), 2);
+    assert!(remaining.iter().all(|(id, ..)| *id != loner.id));
+
+    let (_, email, first_name, purged) =
+        remaining.iter().find(|(id, ..)| *id == student.id).unwrap();
+    assert!(email.ends_with("@deleted.invalid"));
+    assert!(first_name.is_none());
+    assert!(purged);
+
+    let (student_id,): (Uuid,) = sqlx::query_as("SELECT student_id FROM orders WHERE id = $1")
+        .bind(order_id)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(student_id, student.id);
+
+    let (.., purged) = remaining.iter().find(|(id, ..)| *id == recent.id).unwrap();
+    assert!(!purged);
+
+    let purged = AppState::new(pool).purge_deleted_accounts().await.unwrap();
+    assert_eq!(purged, 0);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn purge_deletes_data_exports(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let user = app.signup().await?;
+
+    let request = TestRequest::post("/users/me/export")
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::ACCEPTED);
+
+    let mut storage_key = None;
+    for _ in 0..50 {
+        storage_key = sqlx::query_scalar(
+            "SELECT storage_key FROM data_exports WHERE user_id = $1 AND status = 'ready'",
+        )
+        .bind(user.id)
+        .fetch_optional(&pool)
+        .await?;
+        if storage_key.is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    let storage_key: String = storage_key.ok_or("export was not built in time")?;
+    let archive = std::env::temp_dir().join("s4s-uploads").join(storage_key);
+    assert!(archive.exists());
+
+    delete(&mut app, &user.token).await?;
+    backdate_deletion(&pool, &user.username, 31).await?;
+    let purged = AppState::new(pool.clone())
+        .purge_deleted_accounts()
+        .await
+        .unwrap();
+
+    assert_eq!(purged, 1);
+    assert!(!archive.exists());
+
+    Ok(())
+}
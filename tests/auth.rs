@@ -1,6 +1,9 @@
 pub mod common;
 
-use hyper::StatusCode;
+use hyper::{header::WWW_AUTHENTICATE, StatusCode};
+use jsonwebtoken::{EncodingKey, Header};
+use serde_json::{json, Value};
+use sqlx::types::Uuid;
 
 use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
 
@@ -45,3 +48,168 @@ fn login(pool: DbPool) -> TestResult<()> {
 
     Ok(())
 }
+
+#[sqlx::test]
+fn signup_with_taken_username_or_email(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let signup_form = TestApp::fake_signup_form_json();
+
+    let request = TestRequest::post("/auth/signup")
+        .with_json(signup_form.clone())
+        .build()?;
+    let _ = app.oneshot(request).await?;
+
+    let mut same_username = TestApp::fake_signup_form_json();
+    same_username["username"] = signup_form["username"].clone();
+    let mut same_email = TestApp::fake_signup_form_json();
+    same_email["email"] = signup_form["email"].clone();
+
+    for (form, field) in [(same_username, "username"), (same_email, "email")] {
+        let request = TestRequest::post("/auth/signup").with_json(form).build()?;
+        let response = app.oneshot(request).await?;
+
+        Assert(response)
+            .status(StatusCode::CONFLICT)
+            .json_include(json!({
+                "code": "unique_violation",
+                "errors": [{ "field": field, "code": "unique" }],
+            }))
+            .await;
+    }
+
+    Ok(())
+}
+
+fn sign(claims: Value) -> TestResult<String> {
+    let secret = std::env::var("JWT_SECRET")?;
+    let token = jsonwebtoken::encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )?;
+    Ok(format!("Bearer {token}"))
+}
+
+#[sqlx::test]
+fn missing_token(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::get("/users/me").build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.headers()[WWW_AUTHENTICATE], "Bearer realm=\"s4s\"");
+    Assert(response)
+        .status(StatusCode::UNAUTHORIZED)
+        .json_include(json!({ "code": "missing_token" }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn malformed_token(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    for header in ["Bearer not.a.jwt", "Basic YWRhOmxvdmVsYWNl"] {
+        let request = TestRequest::get("/users/me")
+            .with_header("authorization", header)
+            .build()?;
+        let response = app.oneshot(request).await?;
+
+        let challenge = response.headers()[WWW_AUTHENTICATE].to_str()?.to_owned();
+        assert!(challenge.starts_with("Bearer realm=\"s4s\", error=\"invalid_token\""));
+        Assert(response)
+            .status(StatusCode::UNAUTHORIZED)
+            .json_include(json!({ "code": "invalid_token" }))
+            .await;
+    }
+
+    let forged = {
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &json!({ "sub": Uuid::new_v4(), "exp": 4_102_444_800_i64 }),
+            &EncodingKey::from_secret(b"not-the-secret"),
+        )?;
+        format!("Bearer {token}")
+    };
+    let request = TestRequest::get("/users/me").with_auth(forged).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::UNAUTHORIZED)
+        .json_include(json!({ "code": "invalid_token" }))
+        .await;
+
+    let without_exp = sign(json!({ "sub": Uuid::new_v4() }))?;
+    let request = TestRequest::get("/users/me")
+        .with_auth(without_exp)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::UNAUTHORIZED)
+        .json_include(json!({ "code": "invalid_token" }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn expired_token(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    let exp = chrono::Utc::now().timestamp() - 3600;
+    let token = sign(json!({ "sub": user.id, "exp": exp }))?;
+
+    let request = TestRequest::get("/users/me").with_auth(token).build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(
+        response.headers()[WWW_AUTHENTICATE],
+        "Bearer realm=\"s4s\", error=\"invalid_token\", error_description=\"The access token has expired.\""
+    );
+    Assert(response)
+        .status(StatusCode::UNAUTHORIZED)
+        .json_include(json!({ "code": "token_expired" }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn wrong_credentials(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    let request = TestRequest::post("/auth/login")
+        .with_json(json!({ "username": user.username, "password": "wrongpassword" }))
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert!(response.headers().contains_key(WWW_AUTHENTICATE));
+    Assert(response)
+        .status(StatusCode::UNAUTHORIZED)
+        .json_include(json!({ "code": "wrong_credentials" }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn insufficient_privileges(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    let request = TestRequest::get("/admin/users")
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert!(!response.headers().contains_key(WWW_AUTHENTICATE));
+    Assert(response)
+        .status(StatusCode::FORBIDDEN)
+        .json_include(json!({ "code": "forbidden" }))
+        .await;
+
+    Ok(())
+}
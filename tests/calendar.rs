@@ -0,0 +1,102 @@
+pub mod common;
+
+use chrono::{Duration, SecondsFormat, Utc};
+use hyper::{header::CONTENT_TYPE, StatusCode};
+use serde_json::{json, Value};
+
+use crate::common::{DbPool, TestApp, TestRequest, TestResult, TestUser};
+
+async fn calendar_token(app: &mut TestApp, user: &TestUser, rotate: bool) -> TestResult<String> {
+    let request = if rotate {
+        TestRequest::post("/users/me/calendar-token")
+    } else {
+        TestRequest::get("/users/me/calendar-token")
+    }
+    .with_auth(&user.token)
+    .build()?;
+    let response = app.oneshot(request).await?;
+    let body = TestApp::body_to_json(response.into_body()).await?;
+    Ok(body["token"].as_str().unwrap().to_owned())
+}
+
+async fn feed(app: &mut TestApp, token: &str) -> TestResult<(StatusCode, String)> {
+    let request = TestRequest::get(format!("/users/me/calendar.ics?token={}", token)).build()?;
+    let response = app.oneshot(request).await?;
+    let status = response.status();
+    if status == StatusCode::OK {
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            "text/calendar; charset=utf-8"
+        );
+    }
+    Ok((status, TestApp::body_to_string(response.into_body()).await?))
+}
+
+#[sqlx::test]
+fn feed_reflects_sessions_and_deadlines(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+    let session_id = app.create_session(order_id, &student, &mentor, 48).await?;
+
+    let deadline = (Utc::now() + Duration::days(7)).to_rfc3339_opts(SecondsFormat::Secs, true);
+    let request = TestRequest::post("/orders")
+        .with_json(json!({
+            "mentor": mentor.username,
+            "title": "Essay, review; part 1",
+            "description": TestApp::fake_title(),
+            "price": 100,
+            "deadline": deadline,
+        }))
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let order: Value = TestApp::body_to_json(response.into_body()).await?;
+
+    let token = calendar_token(&mut app, &mentor, false).await?;
+    let (status, ics) = feed(&mut app, &token).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(ics.contains(&format!("UID:session-{}@s4s\r\n", session_id)));
+    assert!(ics.contains("SEQUENCE:0\r\nSTATUS:CONFIRMED"));
+    assert!(ics.contains(&format!(
+        "UID:order-{}-deadline@s4s\r\n",
+        order["id"].as_str().unwrap()
+    )));
+    assert!(ics.contains("SUMMARY:Deadline: Essay\\, review\\; part 1\r\n"));
+
+    let request = TestRequest::post(format!(
+        "/orders/{}/sessions/{}/cancel",
+        order_id, session_id
+    ))
+    .with_json(json!({ "reason": "Conference" }))
+    .with_auth(&mentor.token)
+    .build()?;
+    app.oneshot(request).await?;
+
+    let (_, ics) = feed(&mut app, &token).await?;
+    assert!(ics.contains("SEQUENCE:1\r\nSTATUS:CANCELLED"));
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn rotate_token(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    let token = calendar_token(&mut app, &user, false).await?;
+    assert_eq!(calendar_token(&mut app, &user, false).await?, token);
+
+    let rotated = calendar_token(&mut app, &user, true).await?;
+    assert_ne!(rotated, token);
+
+    let (status, _) = feed(&mut app, &token).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = feed(&mut app, &rotated).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    Ok(())
+}
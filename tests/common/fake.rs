@@ -3,10 +3,11 @@ use std::ops::Range;
 use fake::{
     faker::{
         internet::raw::{Password, SafeEmail, Username},
+        lorem::raw::Sentence,
         name::raw::{FirstName, LastName},
     },
     locales::EN,
-    Fake,
+    Fake, StringFaker,
 };
 use serde_json::{json, Value};
 
@@ -14,7 +15,13 @@ use super::TestApp;
 
 impl TestApp {
     pub fn fake_username() -> String {
-        Username(EN).fake::<String>()
+        let username = Username(EN)
+            .fake::<String>()
+            .chars()
+            .filter(|c| c.is_ascii_lowercase() || *c == '_')
+            .take(24)
+            .collect::<String>();
+        format!("{}_{}", username, Self::fake_suffix())
     }
 
     pub fn fake_first_name() -> String {
@@ -26,7 +33,12 @@ impl TestApp {
     }
 
     pub fn fake_email() -> String {
-        SafeEmail(EN).fake::<String>()
+        let email = SafeEmail(EN).fake::<String>();
+        email.replacen('@', &format!(".{}@", Self::fake_suffix()), 1)
+    }
+
+    fn fake_suffix() -> String {
+        StringFaker::with(Vec::from("abcdefghijklmnopqrstuvwxyz"), 6).fake::<String>()
     }
 
     pub fn fake_password() -> String {
@@ -41,6 +53,27 @@ impl TestApp {
         (0..512).fake()
     }
 
+    pub fn fake_title() -> String {
+        Sentence(EN, 1..8)
+            .fake::<String>()
+            .chars()
+            .take(128)
+            .collect()
+    }
+
+    pub fn fake_message_form_json() -> Value {
+        json!({
+            "body": Sentence(EN, 1..16).fake::<String>(),
+        })
+    }
+
+    pub fn fake_dispute_form_json() -> Value {
+        json!({
+            "reason": Sentence(EN, 4..16).fake::<String>(),
+            "evidence": [Sentence(EN, 1..8).fake::<String>()],
+        })
+    }
+
     pub fn fake_signup_form_json() -> Value {
         let pwd = Self::fake_password();
         json!({
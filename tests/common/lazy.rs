@@ -1,12 +1,12 @@
 use jsonschema::JSONSchema;
 use once_cell::sync::Lazy;
-use s4s::telemetry::Telemetry;
+use s4s::{config::telemetry::TelemetryConfig, telemetry::Telemetry};
 use serde_json::json;
 
 use super::TestApp;
 
 pub static TRACING: Lazy<()> = Lazy::new(|| {
-    Telemetry::initialize();
+    Telemetry::initialize(&TelemetryConfig::default());
 });
 
 pub static ACCESS_TOKEN_JSON_SCHEMA: Lazy<JSONSchema> = Lazy::new(|| {
@@ -35,8 +35,18 @@ pub static USERS_ME_JSON_SCHEMA: Lazy<JSONSchema> = Lazy::new(|| {
             "verified": { "type": "boolean" },
             "created_at": { "type": "string" },
             "updated_at": { "type": "string" },
+            "privacy": {
+                "type": "object",
+                "properties": {
+                    "name": { "enum": ["public", "members", "private"] },
+                    "email": { "enum": ["public", "members", "private"] },
+                    "age": { "enum": ["public", "members", "private"] },
+                    "about": { "enum": ["public", "members", "private"] },
+                },
+                "required": ["name", "email", "age", "about"]
+            },
         },
-        "required": ["username", "first_name", "last_name", "email", "age", "about", "verified", "created_at", "updated_at"]
+        "required": ["username", "first_name", "last_name", "email", "age", "about", "verified", "created_at", "updated_at", "privacy"]
     });
 
     JSONSchema::options().compile(&schema).unwrap()
@@ -44,22 +54,27 @@ pub static USERS_ME_JSON_SCHEMA: Lazy<JSONSchema> = Lazy::new(|| {
 
 pub static USERS_GET_ALL_JSON_SCHEMA: Lazy<JSONSchema> = Lazy::new(|| {
     let schema = json!({
-        "type": "array",
-        "items": {
-            "type": "object",
-            "properties": {
-                "username": { "type": "string" },
-                "first_name": { "type": ["string", "null"] },
-                "last_name": { "type": ["string", "null"] },
-                "email": { "type": "string" },
-                "age": { "type": ["number", "null"]},
-                "about": { "type": ["string", "null"] },
-                "verified": { "type": "boolean" },
-                "created_at": { "type": "string" },
-                "updated_at": { "type": "string" },
+        "type": "object",
+        "properties": {
+            "items": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "username": { "type": "string" },
+                        "first_name": { "type": ["string", "null"] },
+                        "last_name": { "type": ["string", "null"] },
+                        "about": { "type": ["string", "null"] },
+                        "verified": { "type": "boolean" },
+                        "created_at": { "type": "string" },
+                    },
+                    "required": ["username", "verified", "created_at"],
+                    "not": { "anyOf": [{ "required": ["email"] }, { "required": ["age"] }] }
+                },
             },
+            "next_cursor": { "type": ["string", "null"] },
         },
-        "required": ["username", "first_name", "last_name", "email", "age", "about", "verified", "created_at", "updated_at"]
+        "required": ["items", "next_cursor"]
     });
 
     JSONSchema::options().compile(&schema).unwrap()
@@ -72,14 +87,77 @@ pub static USERS_GET_BY_USERNAME_JSON_SCHEMA: Lazy<JSONSchema> = Lazy::new(|| {
             "username": { "type": "string" },
             "first_name": { "type": ["string", "null"] },
             "last_name": { "type": ["string", "null"] },
-            "email": { "type": "string" },
-            "age": { "type": ["number", "null"]},
             "about": { "type": ["string", "null"] },
             "verified": { "type": "boolean" },
             "created_at": { "type": "string" },
-            "updated_at": { "type": "string" },
+            "mentor_profile": { "type": ["object", "null"] },
+        },
+        "required": ["username", "verified", "created_at", "mentor_profile"],
+        "not": { "anyOf": [{ "required": ["email"] }, { "required": ["age"] }] }
+    });
+
+    JSONSchema::options().compile(&schema).unwrap()
+});
+
+pub static MESSAGE_JSON_SCHEMA: Lazy<JSONSchema> = Lazy::new(|| {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "id": { "type": "string" },
+            "order_id": { "type": "string" },
+            "sender_id": { "type": "string" },
+            "body": { "type": "string" },
+            "read_at": { "type": ["string", "null"] },
+            "edited_at": { "type": ["string", "null"] },
+            "deleted_at": { "type": ["string", "null"] },
+            "created_at": { "type": "string" },
+        },
+        "required": ["id", "order_id", "sender_id", "body", "read_at", "edited_at", "deleted_at", "created_at"]
+    });
+
+    JSONSchema::options().compile(&schema).unwrap()
+});
+
+pub static MESSAGE_PAGE_JSON_SCHEMA: Lazy<JSONSchema> = Lazy::new(|| {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "items": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["id", "order_id", "sender_id", "body", "read_at", "edited_at", "deleted_at", "created_at"]
+                }
+            },
+            "next_cursor": { "type": ["string", "null"] },
         },
-        "required": ["username", "first_name", "last_name", "email", "age", "about", "verified", "created_at", "updated_at"]
+        "required": ["items", "next_cursor"]
+    });
+
+    JSONSchema::options().compile(&schema).unwrap()
+});
+
+pub static NOTIFICATION_PAGE_JSON_SCHEMA: Lazy<JSONSchema> = Lazy::new(|| {
+    let schema = json!({
+        "type": "object",
+        "properties": {
+            "items": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "id": { "type": "string" },
+                        "kind": { "type": "string" },
+                        "payload": { "type": "object" },
+                        "read_at": { "type": ["string", "null"] },
+                        "created_at": { "type": "string" },
+                    },
+                    "required": ["id", "kind", "payload", "read_at", "created_at"]
+                }
+            },
+            "next_cursor": { "type": ["string", "null"] },
+        },
+        "required": ["items", "next_cursor"]
     });
 
     JSONSchema::options().compile(&schema).unwrap()
@@ -101,4 +179,16 @@ impl TestApp {
     pub fn users_get_by_username_json_schema() -> &'static JSONSchema {
         &USERS_GET_BY_USERNAME_JSON_SCHEMA
     }
+
+    pub fn message_json_schema() -> &'static JSONSchema {
+        &MESSAGE_JSON_SCHEMA
+    }
+
+    pub fn message_page_json_schema() -> &'static JSONSchema {
+        &MESSAGE_PAGE_JSON_SCHEMA
+    }
+
+    pub fn notification_page_json_schema() -> &'static JSONSchema {
+        &NOTIFICATION_PAGE_JSON_SCHEMA
+    }
 }
@@ -2,8 +2,9 @@ mod assert;
 mod fake;
 mod lazy;
 mod request;
+mod seed;
 
-use std::error::Error;
+use std::{error::Error, net::SocketAddr};
 
 use axum::{
     body::{Body, HttpBody},
@@ -16,7 +17,10 @@ use hyper::{
     Method,
 };
 use once_cell::sync::Lazy;
-use s4s::config::routes::routes;
+use s4s::{
+    config::routes::{admin, routes},
+    state::AppState,
+};
 use serde_json::Value;
 use sqlx::PgPool;
 use tower::ServiceExt;
@@ -24,21 +28,67 @@ use tower::ServiceExt;
 pub use self::assert::Assert;
 use self::lazy::TRACING;
 pub use self::request::TestRequest;
+pub use self::seed::TestUser;
 
 pub type DbPool = PgPool;
 pub type TestResult<T> = Result<T, Box<dyn Error>>;
 
 pub struct TestApp {
     app: Router,
+    pool: DbPool,
 }
 
 impl TestApp {
     pub fn spawn(pool: DbPool) -> Self {
         Lazy::force(&TRACING);
 
-        let app = routes().with_state(pool);
+        let app = routes(AppState::new(pool.clone()));
+
+        Self { app, pool }
+    }
+
+    pub fn spawn_with_uploads(pool: DbPool, uploads: Value) -> TestResult<Self> {
+        Lazy::force(&TRACING);
+
+        let state =
+            AppState::new(pool.clone()).with_upload_config(serde_json::from_value(uploads)?);
+        let app = routes(state);
+
+        Ok(Self { app, pool })
+    }
+
+    pub fn spawn_with_versioning(pool: DbPool, versioning: Value) -> TestResult<Self> {
+        Lazy::force(&TRACING);
+
+        let state =
+            AppState::new(pool.clone()).with_versioning_policy(serde_json::from_value(versioning)?);
+        let app = routes(state);
+
+        Ok(Self { app, pool })
+    }
+
+    pub fn spawn_with_state(pool: DbPool, state: AppState) -> Self {
+        Lazy::force(&TRACING);
+
+        let app = routes(state);
+
+        Self { app, pool }
+    }
 
-        Self { app }
+    pub fn serve(&self) -> TestResult<SocketAddr> {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let server = axum::Server::from_tcp(listener)?.serve(self.app.clone().into_make_service());
+        tokio::spawn(server);
+        Ok(addr)
+    }
+
+    pub async fn scrape_metrics(&self) -> TestResult<String> {
+        let request = Request::builder().uri("/metrics").body(Body::empty())?;
+        let response = admin(AppState::new(self.pool.clone()))
+            .oneshot(request)
+            .await?;
+        Self::body_to_string(response.into_body()).await
     }
 
     pub async fn oneshot(&mut self, request: Request<Body>) -> TestResult<Response> {
@@ -54,6 +104,21 @@ impl TestApp {
         Ok(serde_json::from_slice(&body)?)
     }
 
+    pub async fn body_to_bytes<T: HttpBody>(body: T) -> TestResult<Vec<u8>>
+    where
+        <T as HttpBody>::Error: std::error::Error + 'static,
+    {
+        Ok(hyper::body::to_bytes(body).await?.to_vec())
+    }
+
+    pub async fn body_to_string<T: HttpBody>(body: T) -> TestResult<String>
+    where
+        <T as HttpBody>::Error: std::error::Error + 'static,
+    {
+        let body = hyper::body::to_bytes(body).await?;
+        Ok(String::from_utf8(body.to_vec())?)
+    }
+
     pub async fn body_to_token<T: HttpBody>(body: T) -> TestResult<String>
     where
         <T as HttpBody>::Error: std::error::Error,
@@ -12,8 +12,12 @@ pub struct TestRequest {
     method: Method,
     json: Option<Value>,
     token: Option<String>,
+    headers: Vec<(String, String)>,
+    file: Option<(String, String, Vec<u8>)>,
 }
 
+const BOUNDARY: &str = "s4s-test-boundary";
+
 impl TestRequest {
     pub fn build(self) -> TestResult<Request<Body>> {
         let req = Request::builder().method(self.method).uri(self.uri);
@@ -24,9 +28,26 @@ impl TestRequest {
             req
         };
 
+        let req = self
+            .headers
+            .into_iter()
+            .fold(req, |req, (name, value)| req.header(name, value));
+
         let req = if let Some(json) = self.json {
             req.header(CONTENT_TYPE, "application/json")
                 .body(Body::from(serde_json::to_vec(&json)?))
+        } else if let Some((file_name, content_type, bytes)) = self.file {
+            let mut body = format!(
+                "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\nContent-Type: {content_type}\r\n\r\n"
+            )
+            .into_bytes();
+            body.extend(bytes);
+            body.extend(format!("\r\n--{BOUNDARY}--\r\n").into_bytes());
+            req.header(
+                CONTENT_TYPE,
+                format!("multipart/form-data; boundary={BOUNDARY}"),
+            )
+            .body(Body::from(body))
         } else {
             req.body(Body::empty())
         }?;
@@ -66,6 +87,14 @@ impl TestRequest {
         }
     }
 
+    pub fn options(uri: impl Into<String>) -> Self {
+        Self {
+            uri: uri.into(),
+            method: Method::OPTIONS,
+            ..Self::default()
+        }
+    }
+
     pub fn with_json(self, json: impl Into<Value>) -> Self {
         Self {
             json: Some(json.into()),
@@ -73,10 +102,27 @@ impl TestRequest {
         }
     }
 
+    pub fn with_file(
+        self,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        bytes: Vec<u8>,
+    ) -> Self {
+        Self {
+            file: Some((file_name.into(), content_type.into(), bytes)),
+            ..self
+        }
+    }
+
     pub fn with_auth(self, token: impl Into<String>) -> Self {
         Self {
             token: Some(token.into()),
             ..self
         }
     }
+
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
 }
@@ -0,0 +1,127 @@
+use sqlx::types::Uuid;
+
+use super::{TestApp, TestRequest, TestResult};
+
+pub struct TestUser {
+    pub id: Uuid,
+    pub username: String,
+    pub token: String,
+}
+
+impl TestApp {
+    pub async fn signup(&mut self) -> TestResult<TestUser> {
+        let signup_form = Self::fake_signup_form_json();
+        let username = signup_form["username"].as_str().unwrap().to_owned();
+
+        let request = TestRequest::post("/auth/signup")
+            .with_json(signup_form)
+            .build()?;
+        let response = self.oneshot(request).await?;
+        let token = Self::body_to_token(response.into_body()).await?;
+
+        let (id,): (Uuid,) = sqlx::query_as("SELECT id FROM users WHERE username = $1")
+            .bind(&username)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(TestUser {
+            id,
+            username,
+            token,
+        })
+    }
+
+    pub async fn count_emails(&self, user: &TestUser) -> TestResult<i64> {
+        let (count,): (i64,) = sqlx::query_as(
+            "SELECT COUNT(*) FROM emails JOIN users ON users.email = emails.address WHERE users.id = $1",
+        )
+        .bind(user.id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(count)
+    }
+
+    pub async fn last_email(&self, user: &TestUser) -> TestResult<(String, String)> {
+        let email = sqlx::query_as(
+            r#"
+                SELECT subject, body
+                FROM emails
+                JOIN users ON users.email = emails.address
+                WHERE users.id = $1
+                ORDER BY emails.created_at DESC
+                LIMIT 1;
+            "#,
+        )
+        .bind(user.id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(email)
+    }
+
+    pub async fn make_admin(&self, user: &TestUser) -> TestResult<()> {
+        sqlx::query("UPDATE users SET is_admin = TRUE WHERE id = $1")
+            .bind(user.id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn create_order(&self, student: &TestUser, mentor: &TestUser) -> TestResult<Uuid> {
+        let id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+                INSERT INTO orders (id, student_id, mentor_id, price, title, description, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, NOW(), NOW());
+            "#,
+        )
+        .bind(id)
+        .bind(student.id)
+        .bind(mentor.id)
+        .bind(100)
+        .bind(Self::fake_title())
+        .bind(Self::fake_text())
+        .execute(&self.pool)
+        .await?;
+        Ok(id)
+    }
+
+    pub async fn create_session(
+        &self,
+        order_id: Uuid,
+        student: &TestUser,
+        mentor: &TestUser,
+        starts_in_hours: i32,
+    ) -> TestResult<Uuid> {
+        let id = Uuid::new_v4();
+        sqlx::query(
+            r#"
+                INSERT INTO sessions (id, order_id, mentor_id, student_id, starts_at, ends_at, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, NOW() + MAKE_INTERVAL(hours => $5), NOW() + MAKE_INTERVAL(hours => $5 + 1), NOW(), NOW());
+            "#,
+        )
+        .bind(id)
+        .bind(order_id)
+        .bind(mentor.id)
+        .bind(student.id)
+        .bind(starts_in_hours)
+        .execute(&self.pool)
+        .await?;
+        Ok(id)
+    }
+}
+
+impl TestUser {
+    pub fn access_token(&self) -> &str {
+        self.token.trim_start_matches("Bearer ")
+    }
+}
+
+impl TestApp {
+    pub async fn subject_id(&self, slug: &str) -> TestResult<Uuid> {
+        let (id,): (Uuid,) = sqlx::query_as("SELECT id FROM subjects WHERE slug = $1")
+            .bind(slug)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(id)
+    }
+}
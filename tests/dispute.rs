@@ -0,0 +1,222 @@
+pub mod common;
+
+use hyper::StatusCode;
+use serde_json::{json, Value};
+use sqlx::types::Uuid;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult, TestUser};
+
+async fn open_dispute(app: &mut TestApp, user: &TestUser, order_id: Uuid) -> TestResult<Value> {
+    let request = TestRequest::post(format!("/orders/{}/disputes", order_id))
+        .with_json(TestApp::fake_dispute_form_json())
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    TestApp::body_to_json(response.into_body()).await
+}
+
+#[sqlx::test]
+fn open(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::post(format!("/orders/{}/disputes", order_id))
+        .with_json(TestApp::fake_dispute_form_json())
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::CREATED)
+        .json_include(json!({ "order_id": order_id, "status": "open" }))
+        .await;
+
+    let request = TestRequest::post(format!("/orders/{}/disputes", order_id))
+        .with_json(TestApp::fake_dispute_form_json())
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::CONFLICT);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn open_retries_serialization_failures(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    // Sequences ignore rollbacks, so only the first attempt fails.
+    sqlx::query("CREATE SEQUENCE dispute_attempts")
+        .execute(&pool)
+        .await?;
+    sqlx::query(
+        r#"
+            CREATE FUNCTION fail_first_dispute() RETURNS TRIGGER AS $$
+            BEGIN
+                IF nextval('dispute_attempts') = 1 THEN
+                    RAISE EXCEPTION 'conflict' USING ERRCODE = 'serialization_failure';
+                END IF;
+                RETURN NEW;
+            END;
+            $$ LANGUAGE plpgsql;
+        "#,
+    )
+    .execute(&pool)
+    .await?;
+    sqlx::query(
+        "CREATE TRIGGER fail_first_dispute BEFORE INSERT ON disputes \
+         FOR EACH ROW EXECUTE FUNCTION fail_first_dispute()",
+    )
+    .execute(&pool)
+    .await?;
+
+    let request = TestRequest::post(format!("/orders/{}/disputes", order_id))
+        .with_json(TestApp::fake_dispute_form_json())
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::CREATED)
+        .json_include(json!({ "order_id": order_id, "status": "open" }))
+        .await;
+    let (attempts,): (i64,) = sqlx::query_as("SELECT last_value FROM dispute_attempts")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(attempts, 2);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn outsider_cannot_open(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let outsider = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::post(format!("/orders/{}/disputes", order_id))
+        .with_json(TestApp::fake_dispute_form_json())
+        .with_auth(&outsider.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn admin_queue(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let admin = app.signup().await?;
+    app.make_admin(&admin).await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+    let dispute = open_dispute(&mut app, &student, order_id).await?;
+
+    let request = TestRequest::get("/admin/disputes")
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::get("/admin/disputes")
+        .with_auth(&admin.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "items": [{ "id": dispute["id"] }] }))
+        .await;
+
+    let request = TestRequest::get(format!(
+        "/admin/disputes/{}",
+        dispute["id"].as_str().unwrap()
+    ))
+    .with_auth(&admin.token)
+    .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({
+            "order": { "id": order_id, "status": "disputed" },
+            "history": [{ "status": "disputed" }],
+            "messages": [],
+        }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn resolve(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let admin = app.signup().await?;
+    app.make_admin(&admin).await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+    let dispute = open_dispute(&mut app, &student, order_id).await?;
+    let uri = format!(
+        "/admin/disputes/{}/resolve",
+        dispute["id"].as_str().unwrap()
+    );
+
+    let request = TestRequest::post(&uri)
+        .with_json(json!({ "outcome": "split", "student_amount": 1000 }))
+        .with_auth(&admin.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    let request = TestRequest::post(&uri)
+        .with_json(json!({ "outcome": "split", "student_amount": 30 }))
+        .with_auth(&admin.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({
+            "status": "resolved",
+            "outcome": "split",
+            "student_amount": 30,
+            "mentor_amount": 70,
+        }))
+        .await;
+
+    let request = TestRequest::post(&uri)
+        .with_json(json!({ "outcome": "refund" }))
+        .with_auth(&admin.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::CONFLICT);
+
+    let request = TestRequest::get(format!(
+        "/admin/disputes/{}",
+        dispute["id"].as_str().unwrap()
+    ))
+    .with_auth(&admin.token)
+    .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .json_include(json!({ "order": { "status": "completed" } }))
+        .await;
+
+    Ok(())
+}
@@ -0,0 +1,220 @@
+pub mod common;
+
+use std::{io::Cursor, time::Duration};
+
+use hyper::{header::CONTENT_TYPE, StatusCode};
+use s4s::state::AppState;
+use serde_json::{json, Value};
+use sqlx::types::Uuid;
+use zip::ZipArchive;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult, TestUser};
+
+async fn request_export(app: &mut TestApp, user: &TestUser) -> TestResult<(StatusCode, Value)> {
+    let request = TestRequest::post("/users/me/export")
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let status = response.status();
+    Ok((status, TestApp::body_to_json(response.into_body()).await?))
+}
+
+async fn wait_for_archive(app: &mut TestApp, user: &TestUser, id: &Value) -> TestResult<Vec<u8>> {
+    let path = format!("/users/me/export/{}", id.as_str().unwrap());
+    for _ in 0..50 {
+        let request = TestRequest::get(&path).with_auth(&user.token).build()?;
+        let response = app.oneshot(request).await?;
+        if response.status() == StatusCode::OK {
+            assert_eq!(response.headers()[CONTENT_TYPE], "application/zip");
+            return TestApp::body_to_bytes(response.into_body()).await;
+        }
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    Err("export was not built in time".into())
+}
+
+fn read_entry(archive: &mut ZipArchive<Cursor<Vec<u8>>>, name: &str) -> TestResult<Value> {
+    Ok(serde_json::from_reader(archive.by_name(name)?)?)
+}
+
+#[sqlx::test]
+fn export_contains_personal_data(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::post(format!("/orders/{order_id}/messages"))
+        .with_json(TestApp::fake_message_form_json())
+        .with_auth(&student.token)
+        .build()?;
+    app.oneshot(request).await?;
+
+    let (status, body) = request_export(&mut app, &student).await?;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(body["status"], "pending");
+
+    let bytes = wait_for_archive(&mut app, &student, &body["id"]).await?;
+    let mut archive = ZipArchive::new(Cursor::new(bytes))?;
+
+    let profile = read_entry(&mut archive, "profile.json")?;
+    assert_eq!(profile["username"], json!(student.username));
+    assert!(profile.get("pwd_hash").is_none());
+
+    let orders = read_entry(&mut archive, "orders.json")?;
+    assert_eq!(orders[0]["id"], json!(order_id));
+
+    let messages = read_entry(&mut archive, "messages.json")?;
+    assert_eq!(messages.as_array().unwrap().len(), 1);
+
+    for name in [
+        "sessions.json",
+        "disputes.json",
+        "events.json",
+        "uploads.json",
+    ] {
+        assert!(read_entry(&mut archive, name)?.is_array());
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn exports_are_private(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    let other = app.signup().await?;
+
+    let (_, body) = request_export(&mut app, &user).await?;
+    wait_for_archive(&mut app, &user, &body["id"]).await?;
+    let path = format!("/users/me/export/{}", body["id"].as_str().unwrap());
+
+    let request = TestRequest::get(&path).with_auth(&other.token).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn exports_are_rate_limited(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    for _ in 0..3 {
+        let (status, body) = request_export(&mut app, &user).await?;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        wait_for_archive(&mut app, &user, &body["id"]).await?;
+    }
+    let (status, _) = request_export(&mut app, &user).await?;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn exports_expire(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let user = app.signup().await?;
+
+    let (_, body) = request_export(&mut app, &user).await?;
+    wait_for_archive(&mut app, &user, &body["id"]).await?;
+
+    sqlx::query("UPDATE data_exports SET expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(&pool)
+        .await?;
+    let path = format!("/users/me/export/{}", body["id"].as_str().unwrap());
+
+    let request = TestRequest::get(&path).with_auth(&user.token).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::GONE);
+
+    let removed = AppState::new(pool.clone())
+        .remove_expired_exports()
+        .await
+        .unwrap();
+    assert_eq!(removed, 1);
+
+    let (status,): (String,) = sqlx::query_as("SELECT status FROM data_exports")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(status, "expired");
+
+    let request = TestRequest::get(&path).with_auth(&user.token).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::GONE);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn abandoned_exports_fail(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let user = app.signup().await?;
+
+    for _ in 0..3 {
+        sqlx::query(
+            "INSERT INTO data_exports (id, user_id, status, created_at) \
+             VALUES (gen_random_uuid(), $1, 'pending', NOW() - INTERVAL '1 hour')",
+        )
+        .bind(user.id)
+        .execute(&pool)
+        .await?;
+    }
+    let (status, _) = request_export(&mut app, &user).await?;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+    AppState::new(pool.clone())
+        .remove_expired_exports()
+        .await
+        .unwrap();
+
+    let (pending,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM data_exports WHERE status = 'pending'")
+            .fetch_one(&pool)
+            .await?;
+    assert_eq!(pending, 0);
+    let (status, body) = request_export(&mut app, &user).await?;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    wait_for_archive(&mut app, &user, &body["id"]).await?;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn cleanup_continues_past_failed_deletes(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let user = app.signup().await?;
+
+    let (_, body) = request_export(&mut app, &user).await?;
+    wait_for_archive(&mut app, &user, &body["id"]).await?;
+    // A directory can't be removed like an archive, so deleting this one fails.
+    sqlx::query(
+        "INSERT INTO data_exports (id, user_id, status, storage_key, created_at, expires_at) \
+         VALUES (gen_random_uuid(), $1, 'ready', 'exports', NOW(), NOW())",
+    )
+    .bind(user.id)
+    .execute(&pool)
+    .await?;
+    sqlx::query("UPDATE data_exports SET expires_at = NOW() - INTERVAL '1 minute'")
+        .execute(&pool)
+        .await?;
+
+    let removed = AppState::new(pool.clone())
+        .remove_expired_exports()
+        .await
+        .unwrap();
+    assert_eq!(removed, 1);
+
+    let (status,): (String,) = sqlx::query_as("SELECT status FROM data_exports WHERE id = $1")
+        .bind(body["id"].as_str().unwrap().parse::<Uuid>()?)
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(status, "expired");
+
+    Ok(())
+}
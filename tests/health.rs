@@ -0,0 +1,112 @@
+pub mod common;
+
+use std::time::Duration;
+
+use hyper::StatusCode;
+use s4s::state::AppState;
+use serde_json::{json, Value};
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
+
+// The event listener connects in the background, so readiness may take a moment.
+async fn wait_until_ready(app: &mut TestApp) -> TestResult<Value> {
+    for _ in 0..50 {
+        let request = TestRequest::get("/health/ready").build()?;
+        let response = app.oneshot(request).await?;
+        if response.status() == StatusCode::OK {
+            return TestApp::body_to_json(response.into_body()).await;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    Err("service never became ready".into())
+}
+
+#[sqlx::test]
+fn liveness_does_not_touch_dependencies(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    pool.close().await;
+
+    let request = TestRequest::get("/health/live").build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "status": "ok" }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn readiness_reports_each_check(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let body = wait_until_ready(&mut app).await?;
+
+    assert_eq!(body["status"], "ok");
+    for check in ["database", "migrations", "files", "events"] {
+        assert_eq!(body["checks"][check]["status"], "ok", "{check}: {body}");
+        assert!(body["checks"][check]["duration_ms"].is_f64(), "{body}");
+    }
+    assert!(
+        body["checks"]["migrations"]["detail"]
+            .as_str()
+            .unwrap()
+            .starts_with("at version "),
+        "{body}"
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn readiness_fails_without_database(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    wait_until_ready(&mut app).await?;
+    pool.close().await;
+
+    let request = TestRequest::get("/health/ready").build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::SERVICE_UNAVAILABLE)
+        .json_include(json!({
+            "status": "unavailable",
+            "checks": {
+                "database": { "status": "failed", "detail": "database is unreachable" },
+                "migrations": { "status": "failed", "detail": "migrations could not be read" },
+                "files": { "status": "ok" },
+            },
+        }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn readiness_withdrawn_while_draining(pool: DbPool) -> TestResult<()> {
+    let state = AppState::new(pool.clone())
+        .with_health_policy(serde_json::from_value(json!({ "drain_seconds": 0 }))?);
+    let mut app = TestApp::spawn_with_state(pool, state.clone());
+    wait_until_ready(&mut app).await?;
+
+    state.drain().await;
+
+    let request = TestRequest::get("/health/ready").build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::SERVICE_UNAVAILABLE)
+        .json_include(json!({
+            "status": "draining",
+            "checks": { "database": { "status": "ok" } },
+        }))
+        .await;
+
+    let request = TestRequest::get("/health/live").build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::OK);
+
+    Ok(())
+}
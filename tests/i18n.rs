@@ -0,0 +1,195 @@
+pub mod common;
+
+use std::collections::{BTreeSet, HashMap};
+
+use hyper::{header::CONTENT_LANGUAGE, StatusCode};
+use serde_json::json;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult, TestUser};
+
+async fn set_language(app: &mut TestApp, user: &TestUser, language: &str) -> TestResult<()> {
+    let request = TestRequest::put("/users/me/edit")
+        .with_json(json!({ "username": user.username, "language": language }))
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert!(response.status().is_success(), "{}", response.status());
+    Ok(())
+}
+
+#[sqlx::test]
+fn errors_follow_accept_language(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::get("/users/nobodyhere")
+        .with_header("accept-language", "de-DE,de;q=0.9,en;q=0.8")
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.headers()[CONTENT_LANGUAGE], "de");
+    Assert(response)
+        .status(StatusCode::NOT_FOUND)
+        .json_include(json!({
+            "code": "not_found",
+            "detail": "Die angeforderte Ressource wurde nicht gefunden.",
+        }))
+        .await;
+
+    let request = TestRequest::get("/users/nobodyhere")
+        .with_header("accept-language", "fr, es;q=0.7, de;q=0.3")
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.headers()[CONTENT_LANGUAGE], "es");
+
+    let request = TestRequest::get("/users/nobodyhere")
+        .with_header("accept-language", "fr")
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.headers()[CONTENT_LANGUAGE], "en");
+    Assert(response)
+        .status(StatusCode::NOT_FOUND)
+        .json_include(json!({ "detail": "The requested resource was not found." }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn validation_messages_are_localised(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::post("/auth/signup")
+        .with_json(json!({
+            "username": "ab",
+            "email": "not-an-email",
+            "password": "secret12",
+            "repeat_password": "secret12",
+        }))
+        .with_header("accept-language", "es")
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::BAD_REQUEST)
+        .json_include(json!({
+            "code": "validation_failed",
+            "detail": "La solicitud no es válida.",
+            "errors": [
+                {
+                    "field": "email",
+                    "code": "email",
+                    "message": "debe ser una dirección de correo electrónico válida",
+                },
+                {
+                    "field": "username",
+                    "code": "length",
+                    "message": "debe tener entre 4 y 32 caracteres",
+                },
+            ],
+        }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn stored_language_overrides_header(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    set_language(&mut app, &user, "de").await?;
+
+    let request = TestRequest::get("/users/me")
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "language": "de" }))
+        .await;
+
+    let request = TestRequest::get("/orders/00000000-0000-0000-0000-000000000000")
+        .with_header("accept-language", "es")
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.headers()[CONTENT_LANGUAGE], "de");
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn unsupported_language_is_rejected(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    let request = TestRequest::put("/users/me/edit")
+        .with_json(json!({ "username": user.username, "language": "xx" }))
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::BAD_REQUEST)
+        .json_include(json!({
+            "errors": [{ "field": "language", "code": "supported_language" }],
+        }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn emails_use_stored_language(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    set_language(&mut app, &mentor, "de").await?;
+
+    let request = TestRequest::post("/orders")
+        .with_json(json!({
+            "mentor": mentor.username,
+            "title": TestApp::fake_title(),
+            "description": TestApp::fake_title(),
+            "price": 100,
+        }))
+        .with_auth(&student.token)
+        .build()?;
+    let _ = app.oneshot(request).await?;
+
+    let (subject, body) = app.last_email(&mentor).await?;
+
+    assert_eq!(subject, "Neuer Auftrag");
+    assert!(
+        body.starts_with(&format!("Hallo {},", mentor.username)),
+        "{body}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn catalogs_define_the_same_keys() -> TestResult<()> {
+    let keys = |source: &str| -> TestResult<BTreeSet<String>> {
+        let catalog: HashMap<String, String> = serde_json::from_str(source)?;
+        Ok(catalog.into_keys().collect())
+    };
+    let en = keys(include_str!("../src/i18n/en.json"))?;
+
+    for (locale, source) in [
+        ("de", include_str!("../src/i18n/de.json")),
+        ("es", include_str!("../src/i18n/es.json")),
+    ] {
+        let other = keys(source)?;
+        assert_eq!(
+            en.symmetric_difference(&other).collect::<Vec<_>>(),
+            Vec::<&String>::new(),
+            "{locale}"
+        );
+    }
+
+    Ok(())
+}
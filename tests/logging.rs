@@ -0,0 +1,108 @@
+pub mod common;
+
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use hyper::StatusCode;
+use s4s::telemetry::Telemetry;
+use serde_json::{json, Value};
+
+use crate::common::{DbPool, TestApp, TestRequest, TestResult};
+
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Capture {
+    fn output(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+impl io::Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[sqlx::test]
+fn secrets_never_reach_logs(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let capture = Capture::default();
+    let writer = capture.clone();
+    let config = serde_json::from_value(json!({ "log_format": "json", "log_filter": "trace" }))?;
+    let _guard =
+        tracing::subscriber::set_default(Telemetry::logging_to(&config, move || writer.clone()));
+
+    let password = "correct-horse-battery";
+    let wrong_password = "incorrect-donkey-staple";
+    let new_password = "tr0ub4dor-and-3";
+
+    let request = TestRequest::post("/v1/auth/signup")
+        .with_json(json!({
+            "username": "loggeduser",
+            "email": "logged@example.com",
+            "password": password,
+            "repeat_password": password,
+        }))
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::post("/v1/auth/login")
+        .with_json(json!({ "username": "loggeduser", "password": wrong_password }))
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert!(response.status().is_client_error());
+
+    let request = TestRequest::post("/v1/auth/login")
+        .with_json(json!({ "username": "loggeduser", "password": password }))
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let token = TestApp::body_to_token(response.into_body()).await?;
+
+    // A validation failure carries the rejected value in its details.
+    let request = TestRequest::put("/v1/users/me/edit/password")
+        .with_json(json!({ "password": new_password, "repeat_password": wrong_password }))
+        .with_auth(&token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let request = TestRequest::put("/v1/users/me/edit/password")
+        .with_json(json!({ "password": new_password, "repeat_password": new_password }))
+        .with_auth(&token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert!(response.status().is_success(), "{}", response.status());
+
+    let (pwd_hash,): (String,) =
+        sqlx::query_as("SELECT pwd_hash FROM users WHERE username = 'loggeduser'")
+            .fetch_one(&pool)
+            .await?;
+    let output = capture.output();
+
+    assert!(output.contains("loggeduser"), "{output}");
+    assert!(output.contains("[REDACTED]"), "{output}");
+    for secret in [
+        password,
+        wrong_password,
+        new_password,
+        &pwd_hash,
+        &token[7..],
+    ] {
+        assert!(!output.contains(secret), "{secret} was logged");
+    }
+    for line in output.lines() {
+        serde_json::from_str::<Value>(line)?;
+    }
+
+    Ok(())
+}
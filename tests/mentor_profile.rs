@@ -0,0 +1,138 @@
+pub mod common;
+
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult, TestUser};
+
+fn mentor_profile_form(time_zone: &str) -> Value {
+    json!({
+        "headline": "Calculus without tears",
+        "hourly_rate": 4000,
+        "languages": ["English", "German"],
+        "education": "MSc Mathematics",
+        "time_zone": time_zone,
+        "availability": [
+            { "weekday": 0, "start_time": "09:00:00", "end_time": "12:00:00" },
+            { "weekday": 2, "start_time": "14:00:00", "end_time": "18:00:00" },
+        ],
+        "exceptions": [
+            { "starts_at": "2030-01-07T00:00:00Z", "ends_at": "2030-01-08T00:00:00Z", "reason": "Holiday" },
+        ],
+    })
+}
+
+async fn edit_profile(app: &mut TestApp, user: &TestUser, form: Value) -> TestResult<StatusCode> {
+    let request = TestRequest::put("/users/me/mentor-profile")
+        .with_json(form)
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Ok(response.status())
+}
+
+#[sqlx::test]
+fn edit_and_get(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let mentor = app.signup().await?;
+
+    let status = edit_profile(&mut app, &mentor, mentor_profile_form("Europe/Berlin")).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    let request = TestRequest::get(format!("/users/{}", mentor.username)).build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({
+            "username": mentor.username,
+            "mentor_profile": {
+                "hourly_rate": 4000,
+                "time_zone": "Europe/Berlin",
+                "availability": [{ "weekday": 0 }, { "weekday": 2 }],
+                "exceptions": [{ "reason": "Holiday" }],
+            },
+        }))
+        .await;
+
+    let status = edit_profile(&mut app, &mentor, mentor_profile_form("Mars/Olympus")).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let mut form = mentor_profile_form("Europe/Berlin");
+    form["availability"][0]["end_time"] = json!("08:00:00");
+    let status = edit_profile(&mut app, &mentor, form).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn search_by_availability(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let berlin = app.signup().await?;
+    let tokyo = app.signup().await?;
+    edit_profile(&mut app, &berlin, mentor_profile_form("Europe/Berlin")).await?;
+    edit_profile(&mut app, &tokyo, mentor_profile_form("Asia/Tokyo")).await?;
+
+    // Monday 2030-01-14, 10:00-11:00 in Berlin (UTC+1).
+    let request = TestRequest::get(
+        "/mentors?available_from=2030-01-14T09:00:00Z&available_to=2030-01-14T10:00:00Z",
+    )
+    .build()?;
+    let response = app.oneshot(request).await?;
+    let mentors = TestApp::body_to_json(response.into_body()).await?;
+    let usernames: Vec<&Value> = mentors["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|mentor| &mentor["username"])
+        .collect();
+    assert_eq!(usernames, vec![&json!(berlin.username)]);
+
+    // The same slot a week earlier falls into the holiday exception.
+    let request = TestRequest::get(
+        "/mentors?available_from=2030-01-07T09:00:00Z&available_to=2030-01-07T10:00:00Z",
+    )
+    .build()?;
+    let response = app.oneshot(request).await?;
+    let mentors = TestApp::body_to_json(response.into_body()).await?;
+    assert!(mentors["items"].as_array().unwrap().is_empty());
+
+    for query in [
+        "available_from=2030-01-14T09:00:00Z",
+        "available_to=2030-01-14T10:00:00Z",
+    ] {
+        let request = TestRequest::get(format!("/mentors?{query}")).build()?;
+        let response = app.oneshot(request).await?;
+
+        Assert(response)
+            .status(StatusCode::BAD_REQUEST)
+            .json_include(json!({ "code": "incomplete_time_range" }))
+            .await;
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn public_profile_hides_user_id(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let mentor = app.signup().await?;
+    let status = edit_profile(&mut app, &mentor, mentor_profile_form("Europe/Berlin")).await?;
+    assert_eq!(status, StatusCode::OK);
+
+    let request = TestRequest::get(format!("/users/{}", mentor.username)).build()?;
+    let response = app.oneshot(request).await?;
+    let body = TestApp::body_to_json(response.into_body()).await?;
+
+    let profile = &body["mentor_profile"];
+    assert!(profile.get("user_id").is_none(), "{profile}");
+    for slot in profile["availability"].as_array().unwrap() {
+        assert!(slot.get("user_id").is_none(), "{slot}");
+    }
+    for exception in profile["exceptions"].as_array().unwrap() {
+        assert!(exception.get("user_id").is_none(), "{exception}");
+    }
+    assert!(!body.to_string().contains(&mentor.id.to_string()), "{body}");
+
+    Ok(())
+}
@@ -0,0 +1,211 @@
+pub mod common;
+
+use hyper::{header::LINK, StatusCode};
+use serde_json::json;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
+
+#[sqlx::test]
+fn create(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::post(format!("/orders/{}/messages", order_id))
+        .with_json(TestApp::fake_message_form_json())
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::CREATED)
+        .json_schema(TestApp::message_json_schema())
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn outsider_is_forbidden(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let outsider = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::get(format!("/orders/{}/messages", order_id))
+        .with_auth(&outsider.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::post(format!("/orders/{}/messages", order_id))
+        .with_json(TestApp::fake_message_form_json())
+        .with_auth(&outsider.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn admin_can_read(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let admin = app.signup().await?;
+    app.make_admin(&admin).await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::get(format!("/orders/{}/messages", order_id))
+        .with_auth(&admin.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_schema(TestApp::message_page_json_schema())
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn get_page(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    for _ in 0..3 {
+        let request = TestRequest::post(format!("/orders/{}/messages", order_id))
+            .with_json(TestApp::fake_message_form_json())
+            .with_auth(&student.token)
+            .build()?;
+        let _ = app.oneshot(request).await?;
+    }
+
+    let request = TestRequest::get(format!("/orders/{}/messages?limit=2", order_id))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let link = response.headers()[LINK].to_str()?.to_owned();
+    let page = TestApp::body_to_json(response.into_body()).await?;
+
+    assert_eq!(page["items"].as_array().unwrap().len(), 2);
+    let cursor = page["next_cursor"].as_str().unwrap();
+    assert_eq!(
+        link,
+        format!("</orders/{order_id}/messages?limit=2&cursor={cursor}>; rel=\"next\"")
+    );
+
+    let request = TestRequest::get(format!(
+        "/orders/{}/messages?limit=2&cursor={}",
+        order_id, cursor
+    ))
+    .with_auth(&mentor.token)
+    .build()?;
+    let response = app.oneshot(request).await?;
+    let page = TestApp::body_to_json(response.into_body()).await?;
+
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    assert!(page["next_cursor"].is_null());
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn mark_read(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::post(format!("/orders/{}/messages", order_id))
+        .with_json(TestApp::fake_message_form_json())
+        .with_auth(&student.token)
+        .build()?;
+    let _ = app.oneshot(request).await?;
+
+    let request = TestRequest::get("/users/me")
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .json_include(json!({ "unread_messages": { "total": 1 } }))
+        .await;
+
+    let request = TestRequest::post(format!("/orders/{}/messages/read", order_id))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::NO_CONTENT);
+
+    let request = TestRequest::get("/users/me")
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .json_include(json!({ "unread_messages": { "total": 0 } }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn edit_and_delete(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::post(format!("/orders/{}/messages", order_id))
+        .with_json(TestApp::fake_message_form_json())
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let message = TestApp::body_to_json(response.into_body()).await?;
+    let uri = format!(
+        "/orders/{}/messages/{}",
+        order_id,
+        message["id"].as_str().unwrap()
+    );
+
+    let request = TestRequest::put(&uri)
+        .with_json(json!({ "body": "edited" }))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::put(&uri)
+        .with_json(json!({ "body": "edited" }))
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "body": "edited" }))
+        .await;
+
+    let request = TestRequest::delete(&uri)
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::NO_CONTENT)
+        .empty_body()
+        .await;
+
+    Ok(())
+}
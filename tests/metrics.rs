@@ -0,0 +1,136 @@
+pub mod common;
+
+use hyper::StatusCode;
+use serde_json::json;
+
+use crate::common::{DbPool, TestApp, TestRequest, TestResult};
+
+// Other tests in this binary run concurrently, so counters are only compared as lower bounds.
+fn sample(metrics: &str, series: &str) -> f64 {
+    metrics
+        .lines()
+        .find_map(|line| line.strip_prefix(series)?.strip_prefix(' '))
+        .map_or(0.0, |value| value.parse().unwrap())
+}
+
+#[sqlx::test]
+fn requests_are_labelled_by_route_template(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    let request = TestRequest::get(format!("/v1/users/{}", user.username)).build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let request = TestRequest::get("/health/live").build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let request = TestRequest::get("/v1/no-such-route").build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let metrics = app.scrape_metrics().await?;
+
+    assert!(
+        sample(
+            &metrics,
+            r#"http_requests_total{method="GET",route="/v1/users/:username",status="2xx"}"#
+        ) >= 1.0,
+        "{metrics}"
+    );
+    assert!(
+        sample(
+            &metrics,
+            r#"http_request_duration_seconds_count{method="GET",route="/v1/users/:username",status="2xx"}"#
+        ) >= 1.0,
+        "{metrics}"
+    );
+    assert!(
+        sample(
+            &metrics,
+            r#"http_requests_total{method="GET",route="/health/live",status="2xx"}"#
+        ) >= 1.0,
+        "{metrics}"
+    );
+    assert!(
+        sample(
+            &metrics,
+            r#"http_requests_total{method="GET",route="unmatched",status="4xx"}"#
+        ) >= 1.0,
+        "{metrics}"
+    );
+    assert!(!metrics.contains(&user.username), "{metrics}");
+    assert!(!metrics.contains("no-such-route"), "{metrics}");
+    assert!(metrics.contains("\nhttp_requests_in_flight "), "{metrics}");
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn business_events_are_counted(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let before = app.scrape_metrics().await?;
+
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let calculus = app.subject_id("calculus").await?;
+
+    let request = TestRequest::post("/v1/orders")
+        .with_json(json!({
+            "mentor": mentor.username,
+            "title": TestApp::fake_title(),
+            "description": TestApp::fake_title(),
+            "price": 100,
+            "subject_ids": [calculus],
+        }))
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert!(response.status().is_success(), "{}", response.status());
+
+    let request = TestRequest::post("/v1/auth/login")
+        .with_json(json!({ "username": student.username, "password": "wrong-password" }))
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert!(response.status().is_client_error(), "{}", response.status());
+
+    let after = app.scrape_metrics().await?;
+
+    for (series, increase) in [
+        ("signups_total", 2.0),
+        ("orders_created_total", 1.0),
+        (r#"logins_total{outcome="failure"}"#, 1.0),
+    ] {
+        assert!(
+            sample(&after, series) >= sample(&before, series) + increase,
+            "{series}: {after}"
+        );
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn pool_statistics_are_exported(pool: DbPool) -> TestResult<()> {
+    let app = TestApp::spawn(pool);
+
+    let metrics = app.scrape_metrics().await?;
+
+    assert!(
+        metrics.contains(r#"db_pool_connections{state="idle"}"#),
+        "{metrics}"
+    );
+    assert!(
+        metrics.contains(r#"db_pool_connections{state="in_use"}"#),
+        "{metrics}"
+    );
+    assert!(
+        sample(&metrics, "db_pool_probe_acquire_seconds").is_finite(),
+        "{metrics}"
+    );
+    assert!(
+        metrics.contains("# TYPE signups_total counter"),
+        "{metrics}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+pub mod common;
+
+use hyper::StatusCode;
+use serde_json::{json, Value};
+use sqlx::types::Uuid;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult, TestUser};
+
+async fn send_message(app: &mut TestApp, user: &TestUser, order_id: Uuid) -> TestResult<()> {
+    let request = TestRequest::post(format!("/orders/{}/messages", order_id))
+        .with_json(TestApp::fake_message_form_json())
+        .with_auth(&user.token)
+        .build()?;
+    let _ = app.oneshot(request).await?;
+    Ok(())
+}
+
+async fn notifications(app: &mut TestApp, user: &TestUser, query: &str) -> TestResult<Value> {
+    let request = TestRequest::get(format!("/users/me/notifications{}", query))
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    TestApp::body_to_json(response.into_body()).await
+}
+
+#[sqlx::test]
+fn get_page(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    send_message(&mut app, &student, order_id).await?;
+
+    let request = TestRequest::get("/users/me/notifications")
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_schema(TestApp::notification_page_json_schema())
+        .await;
+
+    let page = notifications(&mut app, &mentor, "").await?;
+    assert_eq!(page["items"][0]["kind"], "new_message");
+
+    let page = notifications(&mut app, &student, "").await?;
+    assert!(page["items"].as_array().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn mark_read(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    send_message(&mut app, &student, order_id).await?;
+    send_message(&mut app, &student, order_id).await?;
+
+    let page = notifications(&mut app, &mentor, "?unread=true").await?;
+    assert_eq!(page["items"].as_array().unwrap().len(), 2);
+    let id = page["items"][0]["id"].as_str().unwrap();
+
+    let request = TestRequest::post(format!("/users/me/notifications/{}/read", id))
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::NOT_FOUND);
+
+    let request = TestRequest::post(format!("/users/me/notifications/{}/read", id))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::NO_CONTENT);
+
+    let page = notifications(&mut app, &mentor, "?unread=true").await?;
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+
+    let request = TestRequest::post("/users/me/notifications/read")
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::NO_CONTENT);
+
+    let page = notifications(&mut app, &mentor, "?unread=true").await?;
+    assert!(page["items"].as_array().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn email_preferences(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::get("/users/me/notification-preferences")
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!([
+            { "kind": "order_created", "email": true },
+            { "kind": "order_status_changed", "email": false },
+            { "kind": "new_message", "email": false },
+        ]))
+        .await;
+
+    send_message(&mut app, &student, order_id).await?;
+    assert_eq!(app.count_emails(&mentor).await?, 0);
+
+    let request = TestRequest::put("/users/me/notification-preferences")
+        .with_json(json!({ "preferences": [{ "kind": "new_message", "email": true }] }))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!([
+            { "kind": "order_created", "email": true },
+            { "kind": "order_status_changed", "email": false },
+            { "kind": "new_message", "email": true },
+        ]))
+        .await;
+
+    send_message(&mut app, &student, order_id).await?;
+    assert_eq!(app.count_emails(&mentor).await?, 1);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn order_notifications(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+
+    let request = TestRequest::post("/orders")
+        .with_json(json!({
+            "mentor": mentor.username,
+            "title": TestApp::fake_title(),
+            "description": TestApp::fake_title(),
+            "price": 100,
+        }))
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let order = TestApp::body_to_json(response.into_body()).await?;
+    let order_id = order["id"].as_str().unwrap();
+
+    let page = notifications(&mut app, &mentor, "").await?;
+    assert_eq!(page["items"][0]["kind"], "order_created");
+    assert_eq!(page["items"][0]["payload"]["order_id"], order_id);
+    assert_eq!(app.count_emails(&mentor).await?, 1);
+
+    let request = TestRequest::post(format!("/orders/{}/disputes", order_id))
+        .with_json(json!({ "reason": "The work was never delivered." }))
+        .with_auth(&student.token)
+        .build()?;
+    let _ = app.oneshot(request).await?;
+
+    let page = notifications(&mut app, &mentor, "").await?;
+    let kinds: Vec<&str> = page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|notification| notification["kind"].as_str())
+        .collect();
+    assert!(kinds.contains(&"order_status_changed"));
+    let page = notifications(&mut app, &student, "").await?;
+    assert_eq!(page["items"], json!([]));
+
+    Ok(())
+}
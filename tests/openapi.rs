@@ -0,0 +1,158 @@
+pub mod common;
+
+use std::collections::BTreeSet;
+
+use hyper::{
+    header::{ALLOW, CONTENT_TYPE},
+    StatusCode,
+};
+use s4s::config::routes::paths;
+use serde_json::{json, Value};
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
+
+fn refs(value: &Value, found: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(target)) = map.get("$ref") {
+                found.insert(target.clone());
+            }
+            map.values().for_each(|value| refs(value, found));
+        }
+        Value::Array(items) => items.iter().for_each(|value| refs(value, found)),
+        _ => {}
+    }
+}
+
+#[sqlx::test]
+fn document_matches_router(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::get("/openapi.json").build()?;
+    let response = app.oneshot(request).await?;
+    let document = TestApp::body_to_json(response.into_body()).await?;
+    let documented = document["paths"].as_object().unwrap();
+
+    let routed: BTreeSet<String> = paths()
+        .iter()
+        .map(|path| {
+            path.split('/')
+                .map(|segment| match segment.strip_prefix(':') {
+                    Some(name) => format!("{{{name}}}"),
+                    None => segment.to_owned(),
+                })
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .collect();
+    let documented_paths: BTreeSet<String> = documented.keys().cloned().collect();
+    assert_eq!(routed, documented_paths);
+
+    // Nothing answers OPTIONS, so the 405 lists every method registered for the path.
+    let server = document["servers"][0]["url"].as_str().unwrap();
+    for (path, item) in documented {
+        let uri = format!("{server}{path}")
+            .replace("{username}", "someone")
+            .split('/')
+            .map(|segment| match segment.starts_with('{') {
+                true => "00000000-0000-0000-0000-000000000000",
+                false => segment,
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let request = TestRequest::options(uri).build()?;
+        let response = app.oneshot(request).await?;
+
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED, "{path}");
+        let allowed: BTreeSet<String> = response.headers()[ALLOW]
+            .to_str()?
+            .split(',')
+            .map(|method| method.trim().to_lowercase())
+            .filter(|method| method != "head")
+            .collect();
+        let methods: BTreeSet<String> = item.as_object().unwrap().keys().cloned().collect();
+        assert_eq!(allowed, methods, "{path}");
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn document_references_resolve(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::get("/openapi.json").build()?;
+    let response = app.oneshot(request).await?;
+    let document = TestApp::body_to_json(response.into_body()).await?;
+
+    let mut found = BTreeSet::new();
+    refs(&document, &mut found);
+    assert!(!found.is_empty());
+    for target in found {
+        let pointer = target.strip_prefix('#').unwrap();
+        assert!(document.pointer(pointer).is_some(), "{target}");
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn validation_constraints_are_documented(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::get("/openapi.json").build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({
+            "openapi": "3.0.3",
+            "components": {
+                "schemas": {
+                    "SignupForm": {
+                        "required": ["email", "password", "repeat_password", "username"],
+                        "properties": {
+                            "username": { "type": "string", "minLength": 4, "maxLength": 32 },
+                            "email": { "type": "string", "format": "email" },
+                            "password": { "type": "string", "minLength": 8 },
+                        },
+                    },
+                },
+            },
+            "paths": {
+                "/users/me/edit": {
+                    "put": {
+                        "security": [{ "bearer": [] }],
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": { "$ref": "#/components/schemas/EditUserForm" },
+                                },
+                            },
+                        },
+                    },
+                },
+            },
+        }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn docs_page_loads_document(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::get("/docs").build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()[CONTENT_TYPE]
+        .to_str()?
+        .starts_with("text/html"));
+    let body = TestApp::body_to_string(response.into_body()).await?;
+    assert!(body.contains("/openapi.json"));
+    assert!(!body.contains("swagger-ui-dist@5/"), "{body}");
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+pub mod common;
+
+use hyper::StatusCode;
+use serde_json::json;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
+
+#[sqlx::test]
+fn create(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let calculus = app.subject_id("calculus").await?;
+
+    let request = TestRequest::post("/orders")
+        .with_json(json!({
+            "mentor": mentor.username,
+            "title": TestApp::fake_title(),
+            "description": TestApp::fake_title(),
+            "price": 100,
+            "subject_ids": [calculus],
+        }))
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let order = TestApp::body_to_json(response.into_body()).await?;
+    assert_eq!(order["mentor_id"], json!(mentor.id));
+    assert_eq!(order["subjects"][0]["slug"], "calculus");
+
+    let request = TestRequest::get(format!("/orders/{}", order["id"].as_str().unwrap()))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "id": order["id"], "status": "in_progress" }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn create_rejects_unknown_subject(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+
+    let request = TestRequest::post("/orders")
+        .with_json(json!({
+            "mentor": mentor.username,
+            "title": TestApp::fake_title(),
+            "description": TestApp::fake_title(),
+            "price": 100,
+            "subject_ids": ["00000000-0000-0000-0000-000000000000"],
+        }))
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    let request = TestRequest::post("/orders")
+        .with_json(json!({
+            "mentor": student.username,
+            "title": TestApp::fake_title(),
+            "description": TestApp::fake_title(),
+            "price": 100,
+        }))
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn filter_by_subject(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let outsider = app.signup().await?;
+    let algebra = app.subject_id("algebra").await?;
+    let physics = app.subject_id("physics").await?;
+
+    for subject_id in [algebra, physics] {
+        let request = TestRequest::post("/orders")
+            .with_json(json!({
+                "mentor": mentor.username,
+                "title": TestApp::fake_title(),
+                "description": TestApp::fake_title(),
+                "price": 50,
+                "subject_ids": [subject_id],
+            }))
+            .with_auth(&student.token)
+            .build()?;
+        app.oneshot(request).await?;
+    }
+
+    let request = TestRequest::get("/orders?subject=mathematics")
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let orders = TestApp::body_to_json(response.into_body()).await?;
+    assert_eq!(orders["items"].as_array().unwrap().len(), 1);
+
+    let request = TestRequest::get("/orders")
+        .with_auth(&outsider.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let orders = TestApp::body_to_json(response.into_body()).await?;
+    assert!(orders["items"].as_array().unwrap().is_empty());
+
+    Ok(())
+}
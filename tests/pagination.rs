@@ -0,0 +1,205 @@
+pub mod common;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hyper::{header::LINK, StatusCode};
+use serde_json::{json, Value};
+use sqlx::types::Uuid;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
+
+#[sqlx::test]
+fn users_are_paged_by_cursor(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let mut expected = Vec::new();
+    for _ in 0..5 {
+        expected.push(app.signup().await?.username);
+    }
+    expected.sort();
+
+    let request = TestRequest::get("/users?sort=username&limit=2").build()?;
+    let response = app.oneshot(request).await?;
+    let link = response.headers()[LINK].to_str()?.to_owned();
+    let page = TestApp::body_to_json(response.into_body()).await?;
+    let cursor = page["next_cursor"].as_str().unwrap();
+    assert_eq!(
+        link,
+        format!("</users?sort=username&limit=2&cursor={cursor}>; rel=\"next\"")
+    );
+
+    let mut usernames = Vec::new();
+    let mut uri = String::from("/users?sort=username&limit=2");
+    loop {
+        let request = TestRequest::get(&uri).build()?;
+        let response = app.oneshot(request).await?;
+        let page = TestApp::body_to_json(response.into_body()).await?;
+        let items = page["items"].as_array().unwrap();
+        assert!(items.len() <= 2);
+        usernames.extend(
+            items
+                .iter()
+                .map(|user| user["username"].as_str().unwrap().to_owned()),
+        );
+        match &page["next_cursor"] {
+            Value::String(cursor) => uri = format!("/users?sort=username&limit=2&cursor={cursor}"),
+            _ => break,
+        }
+    }
+    assert_eq!(usernames, expected);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn descending_order_reverses_page(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let mut expected = Vec::new();
+    for _ in 0..3 {
+        expected.push(app.signup().await?.username);
+    }
+    expected.sort();
+    expected.reverse();
+
+    let request = TestRequest::get("/users?sort=username&order=desc").build()?;
+    let response = app.oneshot(request).await?;
+    let page = TestApp::body_to_json(response.into_body()).await?;
+    let usernames: Vec<&str> = page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|user| user["username"].as_str().unwrap())
+        .collect();
+    assert_eq!(usernames, expected);
+    assert!(page["next_cursor"].is_null());
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn invalid_cursor(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    for _ in 0..2 {
+        app.signup().await?;
+    }
+
+    let request = TestRequest::get("/users?cursor=garbage").build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    let request = TestRequest::get("/users?sort=username&limit=1").build()?;
+    let response = app.oneshot(request).await?;
+    let page = TestApp::body_to_json(response.into_body()).await?;
+    let cursor = page["next_cursor"].as_str().unwrap();
+
+    let request =
+        TestRequest::get(format!("/users?sort=created_at&limit=1&cursor={cursor}")).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn unknown_sort(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::get("/users?sort=password").build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn unknown_filter(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    for uri in [
+        "/users?verifed=true",
+        "/orders?stauts=in_progress",
+        "/mentors?max_rate=100&subjet=math",
+    ] {
+        let request = TestRequest::get(uri).with_auth(&user.token).build()?;
+        let response = app.oneshot(request).await?;
+
+        Assert(response).status(StatusCode::BAD_REQUEST);
+    }
+
+    for uri in [
+        "/users?verified=true&sort=username&order=desc&limit=1",
+        "/orders?status=in_progress&sort=price&limit=1",
+        "/mentors?max_rate=100&sort=username&limit=1",
+    ] {
+        let request = TestRequest::get(uri).with_auth(&user.token).build()?;
+        let response = app.oneshot(request).await?;
+
+        Assert(response).status(StatusCode::OK);
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn orders_are_paged_by_price(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let mut expected = Vec::new();
+    for price in [300, 100, 200, 200] {
+        let id = app.create_order(&student, &mentor).await?;
+        sqlx::query("UPDATE orders SET price = $2 WHERE id = $1")
+            .bind(id)
+            .bind(price)
+            .execute(&pool)
+            .await?;
+        expected.push((price, id.to_string()));
+    }
+    expected.sort();
+    expected.reverse();
+
+    let mut seen = Vec::new();
+    let mut uri = String::from("/orders?sort=price&order=desc&limit=1");
+    loop {
+        let request = TestRequest::get(&uri).with_auth(&student.token).build()?;
+        let response = app.oneshot(request).await?;
+        let page = TestApp::body_to_json(response.into_body()).await?;
+        for order in page["items"].as_array().unwrap() {
+            seen.push((
+                order["price"].as_i64().unwrap(),
+                order["id"].as_str().unwrap().to_owned(),
+            ));
+        }
+        match &page["next_cursor"] {
+            Value::String(cursor) => {
+                uri = format!("/orders?sort=price&order=desc&limit=1&cursor={cursor}")
+            }
+            _ => break,
+        }
+    }
+    assert_eq!(seen, expected);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn cursor_key_must_match_sort(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    app.signup().await?;
+
+    let cursor = json!({
+        "sort": "created_at",
+        "order": "asc",
+        "key": "yesterday",
+        "id": Uuid::nil(),
+    });
+    let cursor = URL_SAFE_NO_PAD.encode(cursor.to_string());
+    let request = TestRequest::get(format!("/users?cursor={cursor}")).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+pub mod common;
+
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult, TestUser};
+
+async fn fill_profile(app: &mut TestApp, user: &TestUser) -> TestResult<()> {
+    let request = TestRequest::put("/users/me/edit")
+        .with_json(json!({
+            "username": user.username,
+            "first_name": "Ada",
+            "last_name": "Lovelace",
+            "age": 36,
+            "about": "Analytical engines",
+        }))
+        .with_auth(&user.token)
+        .build()?;
+    app.oneshot(request).await?;
+    Ok(())
+}
+
+async fn profile(
+    app: &mut TestApp,
+    username: &str,
+    viewer: Option<&TestUser>,
+) -> TestResult<Value> {
+    let mut request = TestRequest::get(format!("/users/{}", username));
+    if let Some(viewer) = viewer {
+        request = request.with_auth(&viewer.token);
+    }
+    let response = app.oneshot(request.build()?).await?;
+    TestApp::body_to_json(response.into_body()).await
+}
+
+#[sqlx::test]
+fn defaults_hide_contact_details(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    fill_profile(&mut app, &user).await?;
+
+    let body = profile(&mut app, &user.username, None).await?;
+    assert_eq!(body["first_name"], "Ada");
+    assert_eq!(body["about"], "Analytical engines");
+    assert!(body.get("email").is_none());
+    assert!(body.get("age").is_none());
+
+    let request = TestRequest::get("/users/me")
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .json_include(json!({
+            "age": 36,
+            "privacy": { "name": "public", "email": "private", "age": "private", "about": "public" },
+        }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn visibility_depends_on_viewer(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    let member = app.signup().await?;
+    fill_profile(&mut app, &user).await?;
+
+    let request = TestRequest::put("/users/me/privacy")
+        .with_json(json!({
+            "name": "members",
+            "email": "public",
+            "age": "members",
+            "about": "private",
+        }))
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "name": "members", "email": "public" }))
+        .await;
+
+    let body = profile(&mut app, &user.username, None).await?;
+    assert!(body.get("email").is_some());
+    assert!(body.get("first_name").is_none());
+    assert!(body.get("age").is_none());
+    assert!(body.get("about").is_none());
+
+    let body = profile(&mut app, &user.username, Some(&member)).await?;
+    assert_eq!(body["first_name"], "Ada");
+    assert_eq!(body["age"], 36);
+    assert!(body.get("about").is_none());
+
+    let body = profile(&mut app, &user.username, Some(&user)).await?;
+    assert_eq!(body["about"], "Analytical engines");
+
+    let request = TestRequest::get("/users?sort=username").build()?;
+    let response = app.oneshot(request).await?;
+    let page = TestApp::body_to_json(response.into_body()).await?;
+    let listed = page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["username"] == json!(user.username))
+        .unwrap();
+    assert!(listed.get("email").is_some());
+    assert!(listed.get("first_name").is_none());
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn rejects_unknown_visibility(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    let request = TestRequest::put("/users/me/privacy")
+        .with_json(json!({
+            "name": "friends",
+            "email": "public",
+            "age": "members",
+            "about": "private",
+        }))
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn admin_sees_everything(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    let admin = app.signup().await?;
+    app.make_admin(&admin).await?;
+    fill_profile(&mut app, &user).await?;
+
+    let path = format!("/admin/users/{}", user.username);
+    let request = TestRequest::get(&path).with_auth(&user.token).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::get(&path).with_auth(&admin.token).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({
+            "id": user.id,
+            "age": 36,
+            "is_admin": false,
+        }))
+        .await;
+
+    let request = TestRequest::get("/admin/users")
+        .with_auth(&admin.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let page = TestApp::body_to_json(response.into_body()).await?;
+    assert!(page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|item| item.get("email").is_some()));
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+pub mod common;
+
+use hyper::{header::CONTENT_TYPE, StatusCode};
+use serde_json::json;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
+
+#[sqlx::test]
+fn validation_errors_are_listed_per_field(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::post("/auth/signup")
+        .with_json(json!({
+            "username": "ab",
+            "email": "not-an-email",
+            "password": "secret12",
+            "repeat_password": "secret12",
+        }))
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()[CONTENT_TYPE], "application/problem+json");
+    let body = TestApp::body_to_json(response.into_body()).await?;
+    assert_eq!(body["code"], "validation_failed");
+    assert_eq!(body["status"], 400);
+    assert_eq!(body["title"], "Bad Request");
+
+    let errors = body["errors"].as_array().unwrap();
+    let fields: Vec<&str> = errors
+        .iter()
+        .map(|error| error["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, ["email", "username"]);
+    assert_eq!(errors[1]["code"], "length");
+    assert_eq!(errors[1]["params"], json!({ "min": 4, "max": 32 }));
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn rejected_values_are_not_echoed(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::post("/auth/signup")
+        .with_json(json!({
+            "username": "someone",
+            "email": "someone@example.com",
+            "password": "hunter2",
+            "repeat_password": "hunter2",
+        }))
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let body = TestApp::body_to_string(response.into_body()).await?;
+
+    assert!(body.contains("\"field\":\"password\""));
+    assert!(!body.contains("hunter2"), "{body}");
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn correlation_id_is_echoed(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::get("/users/nobodyhere")
+        .with_header("x-request-id", "trace-1234")
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.headers()["x-request-id"], "trace-1234");
+    Assert(response)
+        .status(StatusCode::NOT_FOUND)
+        .json_include(json!({
+            "type": "about:blank",
+            "code": "not_found",
+            "detail": "The requested resource was not found.",
+            "correlation_id": "trace-1234",
+        }))
+        .await;
+
+    let request = TestRequest::get("/users/nobodyhere").build()?;
+    let response = app.oneshot(request).await?;
+    let header = response.headers()["x-request-id"].to_str()?.to_owned();
+    let body = TestApp::body_to_json(response.into_body()).await?;
+
+    assert_eq!(body["correlation_id"], header);
+
+    Ok(())
+}
@@ -0,0 +1,199 @@
+pub mod common;
+
+use std::{net::SocketAddr, time::Duration};
+
+use axum::body::{BoxBody, HttpBody};
+use futures_util::StreamExt;
+use hyper::StatusCode;
+use serde_json::Value;
+use sqlx::types::Uuid;
+use tokio::net::TcpStream;
+use tokio_tungstenite::{
+    connect_async, tungstenite::Error as WsError, MaybeTlsStream, WebSocketStream,
+};
+
+use crate::common::{DbPool, TestApp, TestRequest, TestResult, TestUser};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+async fn connect(addr: SocketAddr, user: &TestUser, last_event_id: i64) -> TestResult<Socket> {
+    let url = format!(
+        "ws://{}/ws?token={}&last_event_id={}",
+        addr,
+        user.access_token(),
+        last_event_id
+    );
+    let (socket, _) = connect_async(url).await?;
+    Ok(socket)
+}
+
+async fn next_event(socket: &mut Socket) -> TestResult<Value> {
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+            .await?
+            .ok_or("socket closed")??;
+        if message.is_text() {
+            return Ok(serde_json::from_str(message.to_text()?)?);
+        }
+    }
+}
+
+async fn next_sse_event(body: &mut BoxBody) -> TestResult<Value> {
+    loop {
+        let chunk = tokio::time::timeout(Duration::from_secs(5), body.data())
+            .await?
+            .ok_or("stream closed")??;
+        let chunk = String::from_utf8(chunk.to_vec())?;
+        if let Some(data) = chunk.lines().find_map(|line| line.strip_prefix("data:")) {
+            return Ok(serde_json::from_str(data)?);
+        }
+    }
+}
+
+async fn send_message(app: &mut TestApp, user: &TestUser, order_id: Uuid) -> TestResult<()> {
+    let request = TestRequest::post(format!("/orders/{}/messages", order_id))
+        .with_json(TestApp::fake_message_form_json())
+        .with_auth(&user.token)
+        .build()?;
+    let _ = app.oneshot(request).await?;
+    Ok(())
+}
+
+#[sqlx::test]
+fn receives_events(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let addr = app.serve()?;
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let mut socket = connect(addr, &mentor, 0).await?;
+    send_message(&mut app, &student, order_id).await?;
+    let event = next_event(&mut socket).await?;
+
+    assert_eq!(event["kind"], "message.created");
+    assert_eq!(event["payload"]["order_id"], order_id.to_string());
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn resumes_after_last_event_id(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let addr = app.serve()?;
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    send_message(&mut app, &student, order_id).await?;
+    send_message(&mut app, &student, order_id).await?;
+
+    let mut socket = connect(addr, &mentor, 0).await?;
+    let first = next_event(&mut socket).await?;
+    let second = next_event(&mut socket).await?;
+    drop(socket);
+
+    let mut socket = connect(addr, &mentor, first["id"].as_i64().unwrap()).await?;
+    let resumed = next_event(&mut socket).await?;
+
+    assert_eq!(resumed["id"], second["id"]);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn rejects_missing_token(pool: DbPool) -> TestResult<()> {
+    let app = TestApp::spawn(pool);
+    let addr = app.serve()?;
+
+    match connect_async(format!("ws://{}/ws", addr)).await {
+        Err(WsError::Http(response)) => assert_eq!(response.status(), StatusCode::UNAUTHORIZED),
+        _ => panic!("handshake without a token must be rejected"),
+    }
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn rejects_deleted_account(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    let request = TestRequest::delete("/users/me")
+        .with_auth(&user.token)
+        .build()?;
+    let _ = app.oneshot(request).await?;
+    let addr = app.serve()?;
+
+    match connect(addr, &user, 0).await {
+        Err(error) => match error.downcast_ref::<WsError>() {
+            Some(WsError::Http(response)) => assert_eq!(response.status(), StatusCode::FORBIDDEN),
+            _ => panic!("unexpected error: {error}"),
+        },
+        Ok(_) => panic!("handshake for a deleted account must be rejected"),
+    }
+
+    let request = TestRequest::get("/events").with_auth(&user.token).build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn streams_server_sent_events(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    send_message(&mut app, &student, order_id).await?;
+    send_message(&mut app, &student, order_id).await?;
+
+    let request = TestRequest::get("/events")
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let mut body = response.into_body();
+    let first = next_sse_event(&mut body).await?;
+    let second = next_sse_event(&mut body).await?;
+
+    assert_eq!(first["kind"], "message.created");
+
+    let request = TestRequest::get("/events")
+        .with_auth(&mentor.token)
+        .with_header("Last-Event-ID", first["id"].to_string())
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let resumed = next_sse_event(&mut response.into_body()).await?;
+
+    assert_eq!(resumed["id"], second["id"]);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn event_ids_follow_commit_order(pool: DbPool) -> TestResult<()> {
+    let insert = "INSERT INTO events (user_id, kind, payload, created_at) \
+                  VALUES (gen_random_uuid(), 'test', '{}', NOW()) RETURNING id";
+
+    let mut first = pool.begin().await?;
+    let (first_id,): (i64,) = sqlx::query_as(insert).fetch_one(&mut first).await?;
+
+    let later = tokio::spawn({
+        let pool = pool.clone();
+        async move { sqlx::query_as::<_, (i64,)>(insert).fetch_one(&pool).await }
+    });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(
+        !later.is_finished(),
+        "second insert ran while the first was open"
+    );
+
+    first.commit().await?;
+    let (later_id,) = later.await??;
+    assert!(later_id > first_id);
+
+    Ok(())
+}
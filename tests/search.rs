@@ -0,0 +1,232 @@
+pub mod common;
+
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{DbPool, TestApp, TestRequest, TestResult, TestUser};
+
+async fn create_order(
+    app: &mut TestApp,
+    student: &TestUser,
+    mentor: &TestUser,
+    title: &str,
+    description: &str,
+) -> TestResult<()> {
+    let request = TestRequest::post("/orders")
+        .with_json(json!({
+            "mentor": mentor.username,
+            "title": title,
+            "description": description,
+            "price": 100,
+        }))
+        .with_auth(&student.token)
+        .build()?;
+    app.oneshot(request).await?;
+    Ok(())
+}
+
+async fn search(app: &mut TestApp, user: Option<&TestUser>, query: &str) -> TestResult<Value> {
+    let mut request = TestRequest::get(format!("/search?{}", query));
+    if let Some(user) = user {
+        request = request.with_auth(&user.token);
+    }
+    let response = app.oneshot(request.build()?).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    TestApp::body_to_json(response.into_body()).await
+}
+
+#[sqlx::test]
+fn orders_are_ranked_and_private(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let outsider = app.signup().await?;
+    create_order(
+        &mut app,
+        &student,
+        &mentor,
+        "Linear algebra homework",
+        "Need help running numerical simulations.",
+    )
+    .await?;
+    create_order(
+        &mut app,
+        &student,
+        &mentor,
+        "Organic chemistry",
+        "Reaction mechanisms and some algebra.",
+    )
+    .await?;
+
+    let page = search(&mut app, Some(&student), "q=algebra&kind=order").await?;
+    let results = page["items"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["title"], "Linear <mark>algebra</mark> homework");
+
+    let page = search(&mut app, Some(&student), "q=run").await?;
+    assert!(page["items"][0]["snippet"]
+        .as_str()
+        .unwrap()
+        .contains("<mark>running</mark>"));
+
+    let page = search(&mut app, Some(&outsider), "q=algebra").await?;
+    assert!(page["items"].as_array().unwrap().is_empty());
+
+    let page = search(&mut app, None, "q=algebra").await?;
+    assert!(page["items"].as_array().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn mentors_are_paginated(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    for headline in ["Experienced calculus tutor", "Friendly physics tutor"] {
+        let mentor = app.signup().await?;
+        let request = TestRequest::put("/users/me/mentor-profile")
+            .with_json(json!({
+                "headline": headline,
+                "hourly_rate": 2500,
+                "languages": ["English"],
+                "time_zone": "UTC",
+            }))
+            .with_auth(&mentor.token)
+            .build()?;
+        app.oneshot(request).await?;
+    }
+
+    let page = search(&mut app, None, "q=tutoring&limit=1").await?;
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    assert_eq!(page["items"][0]["kind"], "mentor");
+    let first = page["items"][0]["username"].clone();
+    let cursor = page["next_cursor"].as_str().unwrap();
+
+    let page = search(
+        &mut app,
+        None,
+        &format!("q=tutoring&limit=1&cursor={cursor}"),
+    )
+    .await?;
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    assert_ne!(page["items"][0]["username"], first);
+    assert_eq!(page["next_cursor"], Value::Null);
+
+    let page = search(&mut app, None, "q=calculus").await?;
+    assert_eq!(
+        page["items"][0]["title"],
+        "Experienced <mark>calculus</mark> tutor"
+    );
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn fuzzy_usernames(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    app.signup().await?;
+
+    let typo = &user.username[..user.username.len() - 1];
+    let request = TestRequest::get(format!("/search/users?q={}", typo)).build()?;
+    let response = app.oneshot(request).await?;
+    let users = TestApp::body_to_json(response.into_body()).await?;
+    assert_eq!(users[0]["username"], json!(user.username));
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn highlights_are_escaped(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    create_order(
+        &mut app,
+        &student,
+        &mentor,
+        "Geometry <b>proofs</b>",
+        "Triangles <script>alert('geometry')</script> & circles.",
+    )
+    .await?;
+
+    let page = search(&mut app, Some(&student), "q=geometry").await?;
+    let hit = &page["items"][0];
+    assert_eq!(
+        hit["title"],
+        "<mark>Geometry</mark> &lt;b&gt;proofs&lt;/b&gt;"
+    );
+    let snippet = hit["snippet"].as_str().unwrap();
+    assert!(!snippet.contains("<script>"), "{snippet}");
+    assert!(snippet.contains("&lt;script&gt;"), "{snippet}");
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn mentors_hide_private_fields(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let mentor = app.signup().await?;
+    let request = TestRequest::put("/users/me/edit")
+        .with_json(json!({
+            "username": mentor.username,
+            "first_name": "Grace",
+            "last_name": "Hopper",
+            "about": "Compilers and cobol",
+        }))
+        .with_auth(&mentor.token)
+        .build()?;
+    app.oneshot(request).await?;
+    let request = TestRequest::put("/users/me/mentor-profile")
+        .with_json(json!({
+            "headline": "Programming tutor",
+            "hourly_rate": 2500,
+            "languages": ["English"],
+            "time_zone": "UTC",
+        }))
+        .with_auth(&mentor.token)
+        .build()?;
+    app.oneshot(request).await?;
+
+    let page = search(&mut app, None, "q=cobol").await?;
+    assert!(page["items"][0]["snippet"]
+        .as_str()
+        .unwrap()
+        .contains("<mark>cobol</mark>"));
+
+    let request = TestRequest::put("/users/me/privacy")
+        .with_json(json!({
+            "name": "members",
+            "email": "private",
+            "age": "private",
+            "about": "private",
+        }))
+        .with_auth(&mentor.token)
+        .build()?;
+    app.oneshot(request).await?;
+
+    for query in ["q=cobol", "q=hopper"] {
+        let page = search(&mut app, None, query).await?;
+        assert_eq!(page["items"], json!([]), "{query}");
+    }
+    let page = search(&mut app, None, "q=programming").await?;
+    let snippet = page["items"][0]["snippet"].as_str().unwrap();
+    assert!(!snippet.contains("obol"), "{snippet}");
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn username_wildcards_are_literal(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    app.signup().await?;
+    app.signup().await?;
+
+    for q in ["%25", "_"] {
+        let request = TestRequest::get(format!("/search/users?q={}", q)).build()?;
+        let response = app.oneshot(request).await?;
+        let users = TestApp::body_to_json(response.into_body()).await?;
+        assert_eq!(users, json!([]), "{q}");
+    }
+
+    Ok(())
+}
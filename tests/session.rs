@@ -0,0 +1,233 @@
+pub mod common;
+
+use chrono::{Duration, DurationRound, SecondsFormat, Utc};
+use hyper::StatusCode;
+use serde_json::{json, Value};
+use sqlx::types::Uuid;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult, TestUser};
+
+async fn make_mentor(app: &mut TestApp, mentor: &TestUser) -> TestResult<()> {
+    let availability: Vec<Value> = (0..7)
+        .map(|weekday| json!({ "weekday": weekday, "start_time": "08:00:00", "end_time": "20:00:00" }))
+        .collect();
+    let request = TestRequest::put("/users/me/mentor-profile")
+        .with_json(json!({
+            "headline": TestApp::fake_title(),
+            "hourly_rate": 3000,
+            "time_zone": "UTC",
+            "availability": availability,
+        }))
+        .with_auth(&mentor.token)
+        .build()?;
+    app.oneshot(request).await?;
+    Ok(())
+}
+
+fn slot(days: i64, hour: i64, minutes: i64) -> Value {
+    let day = Utc::now().duration_trunc(Duration::days(1)).unwrap() + Duration::days(days);
+    let starts_at = day + Duration::hours(hour);
+    json!({
+        "starts_at": starts_at.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        "ends_at": (starts_at + Duration::minutes(minutes)).to_rfc3339_opts(SecondsFormat::AutoSi, true),
+    })
+}
+
+async fn book(
+    app: &mut TestApp,
+    student: &TestUser,
+    order_id: Uuid,
+    form: Value,
+) -> TestResult<(StatusCode, Value)> {
+    let request = TestRequest::post(format!("/orders/{}/sessions", order_id))
+        .with_json(form)
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let status = response.status();
+    Ok((status, TestApp::body_to_json(response.into_body()).await?))
+}
+
+#[sqlx::test]
+fn book_rejects_conflicts(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let other_student = app.signup().await?;
+    let mentor = app.signup().await?;
+    make_mentor(&mut app, &mentor).await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+    let other_order_id = app.create_order(&other_student, &mentor).await?;
+
+    let (status, session) = book(&mut app, &student, order_id, slot(3, 10, 60)).await?;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(session["status"], "booked");
+
+    let (status, _) = book(&mut app, &other_student, other_order_id, slot(3, 10, 90)).await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = book(&mut app, &other_student, other_order_id, slot(3, 11, 60)).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, _) = book(&mut app, &other_student, other_order_id, slot(4, 19, 120)).await?;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = book(&mut app, &mentor, order_id, slot(5, 10, 60)).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, _) = book(&mut app, &student, order_id, slot(5, 10, 10)).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn reschedule_and_cancel(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    make_mentor(&mut app, &mentor).await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let (_, session) = book(&mut app, &student, order_id, slot(3, 10, 60)).await?;
+    let session_id = session["id"].as_str().unwrap();
+
+    let request = TestRequest::put(format!("/orders/{}/sessions/{}", order_id, session_id))
+        .with_json(slot(4, 14, 60))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "starts_at": slot(4, 14, 60)["starts_at"] }))
+        .await;
+
+    let request = TestRequest::post(format!(
+        "/orders/{}/sessions/{}/cancel",
+        order_id, session_id
+    ))
+    .with_json(json!({ "reason": "Sick" }))
+    .with_auth(&student.token)
+    .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "status": "cancelled", "cancelled_by": student.id }))
+        .await;
+
+    let (status, _) = book(&mut app, &student, order_id, slot(4, 14, 60)).await?;
+    assert_eq!(status, StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn notice_period(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+    let session_id = app.create_session(order_id, &student, &mentor, 2).await?;
+
+    let request = TestRequest::post(format!(
+        "/orders/{}/sessions/{}/cancel",
+        order_id, session_id
+    ))
+    .with_json(json!({}))
+    .with_auth(&student.token)
+    .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::CONFLICT);
+
+    let request = TestRequest::get("/users/me/sessions?time_zone=Asia/Kolkata")
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let sessions = TestApp::body_to_json(response.into_body()).await?;
+    assert!(sessions[0]["starts_at_local"]
+        .as_str()
+        .unwrap()
+        .ends_with("+05:30"));
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn disputed_orders_are_frozen(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    make_mentor(&mut app, &mentor).await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let (_, session) = book(&mut app, &student, order_id, slot(3, 10, 60)).await?;
+    let session_id = session["id"].as_str().unwrap();
+
+    let request = TestRequest::post(format!("/orders/{}/disputes", order_id))
+        .with_json(json!({ "reason": "The mentor stopped replying." }))
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::CREATED);
+
+    let request = TestRequest::put(format!("/orders/{}/sessions/{}", order_id, session_id))
+        .with_json(slot(4, 14, 60))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response)
+        .status(StatusCode::CONFLICT)
+        .json_include(json!({ "code": "order_disputed" }))
+        .await;
+
+    let request = TestRequest::post(format!(
+        "/orders/{}/sessions/{}/cancel",
+        order_id, session_id
+    ))
+    .with_json(json!({ "reason": "Sick" }))
+    .with_auth(&student.token)
+    .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::CONFLICT);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn changes_wait_for_a_dispute_being_opened(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    make_mentor(&mut app, &mentor).await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let (_, session) = book(&mut app, &student, order_id, slot(3, 10, 60)).await?;
+    let session_id = session["id"].as_str().unwrap();
+
+    // Stands in for a dispute that locked the order but hasn't committed yet.
+    let mut tx = pool.begin().await?;
+    sqlx::query("UPDATE orders SET status = 'disputed' WHERE id = $1")
+        .bind(order_id)
+        .execute(&mut tx)
+        .await?;
+    let dispute = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        tx.commit().await
+    });
+
+    let request = TestRequest::post(format!(
+        "/orders/{}/sessions/{}/cancel",
+        order_id, session_id
+    ))
+    .with_json(json!({ "reason": "Sick" }))
+    .with_auth(&student.token)
+    .build()?;
+    let response = app.oneshot(request).await?;
+    dispute.await??;
+
+    Assert(response)
+        .status(StatusCode::CONFLICT)
+        .json_include(json!({ "code": "order_disputed" }))
+        .await;
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+pub mod common;
+
+use hyper::StatusCode;
+use serde_json::{json, Value};
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
+
+#[sqlx::test]
+fn get_tree(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+
+    let request = TestRequest::get("/subjects").build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let tree = TestApp::body_to_json(response.into_body()).await?;
+    let mathematics = tree
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|node| node["slug"] == "mathematics")
+        .unwrap();
+    assert!(mathematics["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|node| node["slug"] == "calculus"));
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn admin_manages_subjects(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    let admin = app.signup().await?;
+    app.make_admin(&admin).await?;
+    let mathematics = app.subject_id("mathematics").await?;
+
+    let form = json!({ "parent_id": mathematics, "slug": "geometry", "name": "Geometry" });
+    let request = TestRequest::post("/admin/subjects")
+        .with_json(form.clone())
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::post("/admin/subjects")
+        .with_json(form)
+        .with_auth(&admin.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let geometry = TestApp::body_to_json(response.into_body()).await?;
+    assert_eq!(geometry["parent_id"], json!(mathematics));
+
+    let request = TestRequest::put(format!("/admin/subjects/{}", mathematics))
+        .with_json(json!({ "parent_id": geometry["id"], "slug": "mathematics", "name": "Maths" }))
+        .with_auth(&admin.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    let request = TestRequest::delete(format!("/admin/subjects/{}", mathematics))
+        .with_auth(&admin.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::CONFLICT);
+
+    let request = TestRequest::delete(format!(
+        "/admin/subjects/{}",
+        geometry["id"].as_str().unwrap()
+    ))
+    .with_auth(&admin.token)
+    .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::NO_CONTENT);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn filter_mentors_by_subject(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let mentor = app.signup().await?;
+    let other = app.signup().await?;
+    let calculus = app.subject_id("calculus").await?;
+
+    let request = TestRequest::put("/users/me/subjects")
+        .with_json(json!({ "subject_ids": [calculus] }))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::OK);
+
+    let request = TestRequest::get(format!("/users/{}/subjects", mentor.username)).build()?;
+    let response = app.oneshot(request).await?;
+    let subjects = TestApp::body_to_json(response.into_body()).await?;
+    assert_eq!(subjects[0]["slug"], "calculus");
+
+    let request = TestRequest::get("/users?subject=mathematics").build()?;
+    let response = app.oneshot(request).await?;
+    let users = TestApp::body_to_json(response.into_body()).await?;
+    let usernames: Vec<&Value> = users["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|user| &user["username"])
+        .collect();
+    assert!(usernames.contains(&&json!(mentor.username)));
+    assert!(!usernames.contains(&&json!(other.username)));
+
+    let request = TestRequest::put("/users/me/subjects")
+        .with_json(json!({ "subject_ids": ["00000000-0000-0000-0000-000000000000"] }))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
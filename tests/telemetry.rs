@@ -0,0 +1,130 @@
+pub mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures_util::future::BoxFuture;
+use hyper::StatusCode;
+use opentelemetry::trace::{SpanId, TraceId};
+use opentelemetry_sdk::export::trace::{ExportResult, SpanData, SpanExporter};
+use s4s::telemetry::Telemetry;
+
+use crate::common::{DbPool, TestApp, TestRequest, TestResult};
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const PARENT_ID: &str = "00f067aa0ba902b7";
+
+#[derive(Debug, Clone, Default)]
+struct InMemoryExporter(Arc<Mutex<Vec<SpanData>>>);
+
+impl InMemoryExporter {
+    // Spans are exported from a background thread, and the request span closes last.
+    async fn spans(&self) -> Vec<SpanData> {
+        for _ in 0..50 {
+            let spans = self.0.lock().unwrap().clone();
+            if spans.iter().any(|span| span.name == "request") {
+                return spans;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl SpanExporter for InMemoryExporter {
+    fn export(&mut self, batch: Vec<SpanData>) -> BoxFuture<'static, ExportResult> {
+        self.0.lock().unwrap().extend(batch);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+fn find<'a>(spans: &'a [SpanData], name: &str) -> &'a SpanData {
+    spans
+        .iter()
+        .find(|span| span.name == name)
+        .unwrap_or_else(|| panic!("no {name} span in {:?}", names(spans)))
+}
+
+fn names(spans: &[SpanData]) -> Vec<&str> {
+    spans.iter().map(|span| span.name.as_ref()).collect()
+}
+
+fn parent<'a>(spans: &'a [SpanData], span: &SpanData) -> &'a SpanData {
+    spans
+        .iter()
+        .find(|candidate| candidate.span_context.span_id() == span.parent_span_id)
+        .unwrap_or_else(|| panic!("{} has no exported parent", span.name))
+}
+
+fn attribute(span: &SpanData, key: &str) -> Option<String> {
+    span.attributes
+        .iter()
+        .find(|attribute| attribute.key.as_str() == key)
+        .map(|attribute| attribute.value.as_str().into_owned())
+}
+
+#[sqlx::test]
+fn incoming_trace_is_continued(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    let exporter = InMemoryExporter::default();
+    let _guard = tracing::subscriber::set_default(Telemetry::exporting_to(exporter.clone()));
+
+    let request = TestRequest::get(format!("/v1/users/{}", user.username))
+        .with_header("traceparent", format!("00-{TRACE_ID}-{PARENT_ID}-01"))
+        .build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let spans = exporter.spans().await;
+    let request = find(&spans, "request");
+    assert_eq!(
+        request.span_context.trace_id(),
+        TraceId::from_hex(TRACE_ID)?
+    );
+    assert_eq!(request.parent_span_id, SpanId::from_hex(PARENT_ID)?);
+    assert!(spans
+        .iter()
+        .all(|span| span.span_context.trace_id() == request.span_context.trace_id()));
+
+    let query = spans
+        .iter()
+        .find(|span| {
+            attribute(span, "db.statement").is_some_and(|statement| statement.contains("users"))
+        })
+        .unwrap_or_else(|| panic!("no query span in {:?}", names(&spans)));
+    assert_eq!(query.name, "SELECT");
+    assert_eq!(attribute(query, "db.system").as_deref(), Some("postgresql"));
+    assert!(query.start_time <= query.end_time);
+
+    // SQL -> storage -> service -> route handler -> request
+    let storage = parent(&spans, query);
+    assert_eq!(storage.name, "get_active_by_username");
+    let service = parent(&spans, storage);
+    assert_eq!(service.name, "get");
+    let handler = parent(&spans, service);
+    assert_eq!(handler.name, "get_by_username");
+    assert_eq!(parent(&spans, handler).name, "request");
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn requests_without_context_start_a_trace(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let exporter = InMemoryExporter::default();
+    let _guard = tracing::subscriber::set_default(Telemetry::exporting_to(exporter.clone()));
+
+    let request = TestRequest::get("/v1/subjects").build()?;
+    let response = app.oneshot(request).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let spans = exporter.spans().await;
+    let request = find(&spans, "request");
+    assert_eq!(request.parent_span_id, SpanId::INVALID);
+    assert_ne!(request.span_context.trace_id(), TraceId::INVALID);
+
+    Ok(())
+}
@@ -0,0 +1,403 @@
+pub mod common;
+
+use std::{
+    collections::HashMap,
+    io::Cursor,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    routing::put,
+    Router,
+};
+use hmac::{Hmac, Mac};
+use hyper::header::CONTENT_TYPE;
+use image::{ImageOutputFormat, RgbImage};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
+
+type Objects = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+fn png(width: u32, height: u32) -> TestResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    RgbImage::new(width, height).write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)?;
+    Ok(bytes)
+}
+
+async fn fetch(app: &mut TestApp, url: &Value) -> TestResult<(StatusCode, String, Vec<u8>)> {
+    let request = TestRequest::get(url.as_str().unwrap()).build()?;
+    let response = app.oneshot(request).await?;
+    let status = response.status();
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap().to_owned())
+        .unwrap_or_default();
+    let bytes = TestApp::body_to_bytes(response.into_body()).await?;
+    Ok((status, content_type, bytes))
+}
+
+// A stand-in for an S3-compatible store which keeps objects in memory and checks that every
+// request is signed and carries the hash of its payload.
+async fn object_store(objects: Objects) -> TestResult<String> {
+    async fn store(
+        State(objects): State<Objects>,
+        Path(key): Path<String>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        let signed = headers["authorization"]
+            .to_str()
+            .unwrap()
+            .starts_with("AWS4-HMAC-SHA256 Credential=access/");
+        let hash = hex::encode(Sha256::digest(&body));
+        if !signed || headers["x-amz-content-sha256"] != hash.as_str() {
+            return StatusCode::FORBIDDEN;
+        }
+        objects.lock().unwrap().insert(key, body.to_vec());
+        StatusCode::OK
+    }
+
+    async fn load(
+        State(objects): State<Objects>,
+        Path(key): Path<String>,
+    ) -> Result<Vec<u8>, StatusCode> {
+        objects
+            .lock()
+            .unwrap()
+            .get(&key)
+            .cloned()
+            .ok_or(StatusCode::NOT_FOUND)
+    }
+
+    async fn remove(State(objects): State<Objects>, Path(key): Path<String>) -> StatusCode {
+        objects.lock().unwrap().remove(&key);
+        StatusCode::NO_CONTENT
+    }
+
+    let app = Router::new()
+        .route("/*key", put(store).get(load).delete(remove))
+        .with_state(objects);
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(axum::Server::from_tcp(listener)?.serve(app.into_make_service()));
+    Ok(format!("http://{addr}"))
+}
+
+#[sqlx::test]
+fn avatar_with_thumbnail(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+    let original = png(300, 200)?;
+
+    let request = TestRequest::put("/users/me/avatar")
+        .with_file("me.png", "image/png", original.clone())
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "file_name": "me.png", "content_type": "image/png" }))
+        .await;
+
+    let request = TestRequest::get(format!("/users/{}/avatar", user.username)).build()?;
+    let response = app.oneshot(request).await?;
+    let avatar = TestApp::body_to_json(response.into_body()).await?;
+
+    let (status, content_type, bytes) = fetch(&mut app, &avatar["url"]).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/png");
+    assert_eq!(bytes, original);
+
+    let (status, content_type, bytes) = fetch(&mut app, &avatar["thumbnail_url"]).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/png");
+    let thumbnail = image::load_from_memory(&bytes)?;
+    assert_eq!((thumbnail.width(), thumbnail.height()), (128, 85));
+
+    let request = TestRequest::delete("/users/me/avatar")
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::NO_CONTENT);
+
+    let (status, _, _) = fetch(&mut app, &avatar["url"]).await?;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn avatar_must_be_an_image(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    let request = TestRequest::put("/users/me/avatar")
+        .with_file("me.txt", "text/plain", b"hello".to_vec())
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let request = TestRequest::put("/users/me/avatar")
+        .with_file("me.png", "image/png", b"not a png".to_vec())
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn attachments_are_limited_to_participants(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let outsider = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+    let path = format!("/orders/{order_id}/attachments");
+
+    let request = TestRequest::post(&path)
+        .with_file("assignment.pdf", "application/pdf", b"%PDF-1.4".to_vec())
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::CREATED)
+        .json_include(json!({ "file_name": "assignment.pdf", "size": 8, "thumbnail_url": null }))
+        .await;
+
+    let request = TestRequest::post(&path)
+        .with_file("spam.pdf", "application/pdf", b"%PDF-1.4".to_vec())
+        .with_auth(&outsider.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::get(&path).with_auth(&outsider.token).build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::get(&path).with_auth(&mentor.token).build()?;
+    let response = app.oneshot(request).await?;
+    let attachments = TestApp::body_to_json(response.into_body()).await?;
+    let attachment = &attachments[0];
+
+    let (status, content_type, bytes) = fetch(&mut app, &attachment["url"]).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "application/pdf");
+    assert_eq!(bytes, b"%PDF-1.4");
+
+    let tampered = attachment["url"]
+        .as_str()
+        .unwrap()
+        .replace("signature=", "signature=0");
+    let (status, _, _) = fetch(&mut app, &json!(tampered)).await?;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let forged = attachment["url"]
+        .as_str()
+        .unwrap()
+        .replace("variant=original", "variant=thumbnail");
+    let (status, _, _) = fetch(&mut app, &json!(forged)).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let request = TestRequest::delete(format!("{path}/{}", attachment["id"].as_str().unwrap()))
+        .with_auth(&mentor.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::FORBIDDEN);
+
+    let request = TestRequest::delete(format!("{path}/{}", attachment["id"].as_str().unwrap()))
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::NO_CONTENT);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn links_are_not_signed_with_the_jwt_secret(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::post(format!("/orders/{order_id}/attachments"))
+        .with_file("assignment.pdf", "application/pdf", b"%PDF-1.4".to_vec())
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let attachment = TestApp::body_to_json(response.into_body()).await?;
+    let url = attachment["url"].as_str().unwrap();
+    let (path, _) = url.split_once("&signature=").unwrap();
+    let expires = path.rsplit_once("expires=").unwrap().1;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(std::env::var("JWT_SECRET")?.as_bytes())?;
+    mac.update(
+        format!(
+            "download:{}:original:{expires}",
+            attachment["id"].as_str().unwrap()
+        )
+        .as_bytes(),
+    );
+    let signature = hex::encode(mac.finalize().into_bytes());
+    let (status, _, _) = fetch(&mut app, &json!(format!("{path}&signature={signature}"))).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn attachment_limits(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn_with_uploads(
+        pool,
+        json!({ "max_attachment_bytes": 1024, "link_ttl_seconds": -1 }),
+    )?;
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+    let path = format!("/orders/{order_id}/attachments");
+
+    let request = TestRequest::post(&path)
+        .with_file("big.txt", "text/plain", vec![b'a'; 2048])
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::PAYLOAD_TOO_LARGE);
+
+    let request = TestRequest::post(&path)
+        .with_file("run.sh", "application/x-sh", b"rm -rf /".to_vec())
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    for (file_name, content_type) in [("run.pdf", "application/pdf"), ("run.png", "image/png")] {
+        let request = TestRequest::post(&path)
+            .with_file(file_name, content_type, b"rm -rf /".to_vec())
+            .with_auth(&student.token)
+            .build()?;
+        let response = app.oneshot(request).await?;
+
+        Assert(response)
+            .status(StatusCode::BAD_REQUEST)
+            .json_include(json!({ "code": "content_mismatch" }))
+            .await;
+    }
+
+    let request = TestRequest::post(&path)
+        .with_file("notes.txt", "text/plain", b"notes".to_vec())
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let attachment = TestApp::body_to_json(response.into_body()).await?;
+
+    let (status, _, _) = fetch(&mut app, &attachment["url"]).await?;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn infected_files_are_rejected(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn_with_uploads(
+        pool,
+        json!({
+            "scanner": { "kind": "command", "program": "sh", "args": ["-c", "! grep -q EICAR"] },
+        }),
+    )?;
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+    let path = format!("/orders/{order_id}/attachments");
+
+    let request = TestRequest::post(&path)
+        .with_file(
+            "virus.txt",
+            "text/plain",
+            b"X5O EICAR-STANDARD-ANTIVIRUS-TEST-FILE".to_vec(),
+        )
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::UNPROCESSABLE_ENTITY);
+
+    let request = TestRequest::post(&path)
+        .with_file("notes.txt", "text/plain", b"clean notes".to_vec())
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response).status(StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn s3_backend(pool: DbPool) -> TestResult<()> {
+    let objects = Objects::default();
+    let endpoint = object_store(objects.clone()).await?;
+    let mut app = TestApp::spawn_with_uploads(
+        pool,
+        json!({
+            "backend": {
+                "kind": "s3",
+                "endpoint": endpoint,
+                "bucket": "uploads",
+                "region": "us-east-1",
+                "access_key": "access",
+                "secret_key": "secret",
+            },
+        }),
+    )?;
+    let student = app.signup().await?;
+    let mentor = app.signup().await?;
+    let order_id = app.create_order(&student, &mentor).await?;
+
+    let request = TestRequest::post(format!("/orders/{order_id}/attachments"))
+        .with_file("essay.txt", "text/plain", b"an essay".to_vec())
+        .with_auth(&student.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+    let attachment = TestApp::body_to_json(response.into_body()).await?;
+    let key = format!(
+        "uploads/attachments/{order_id}/{}",
+        attachment["id"].as_str().unwrap()
+    );
+    assert_eq!(objects.lock().unwrap()[&key], b"an essay");
+
+    let (status, _, bytes) = fetch(&mut app, &attachment["url"]).await?;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(bytes, b"an essay");
+
+    let request = TestRequest::delete(format!(
+        "/orders/{order_id}/attachments/{}",
+        attachment["id"].as_str().unwrap()
+    ))
+    .with_auth(&student.token)
+    .build()?;
+    app.oneshot(request).await?;
+    assert!(objects.lock().unwrap().is_empty());
+
+    Ok(())
+}
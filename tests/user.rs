@@ -1,6 +1,7 @@
 pub mod common;
 
 use hyper::StatusCode;
+use serde_json::json;
 
 use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
 
@@ -150,3 +151,41 @@ fn edit_email(pool: DbPool) -> TestResult<()> {
 
     Ok(())
 }
+
+#[sqlx::test]
+fn edit_to_taken_username_or_email(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool.clone());
+    let user = app.signup().await?;
+    let other = app.signup().await?;
+    let (other_email,): (String,) = sqlx::query_as("SELECT email FROM users WHERE id = $1")
+        .bind(other.id)
+        .fetch_one(&pool)
+        .await?;
+
+    let mut edit_form = TestApp::fake_edit_form_json();
+    edit_form["username"] = json!(other.username);
+
+    let request = TestRequest::put("/users/me/edit")
+        .with_json(edit_form)
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::CONFLICT)
+        .json_include(json!({ "errors": [{ "field": "username" }] }))
+        .await;
+
+    let request = TestRequest::put("/users/me/edit/email")
+        .with_json(json!({ "email": other_email }))
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    Assert(response)
+        .status(StatusCode::CONFLICT)
+        .json_include(json!({ "errors": [{ "field": "email" }] }))
+        .await;
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+pub mod common;
+
+use hyper::{header::LINK, StatusCode};
+use serde_json::json;
+
+use crate::common::{Assert, DbPool, TestApp, TestRequest, TestResult};
+
+#[sqlx::test]
+fn versioned_paths_are_not_deprecated(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    let request = TestRequest::get("/v1/users/me")
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert!(response.headers().get("deprecation").is_none());
+    assert!(response.headers().get("sunset").is_none());
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "username": user.username }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn unversioned_paths_are_deprecated_aliases(pool: DbPool) -> TestResult<()> {
+    let mut app =
+        TestApp::spawn_with_versioning(pool, json!({ "legacy_sunset": "2027-04-01T00:00:00Z" }))?;
+    let user = app.signup().await?;
+
+    let request = TestRequest::get("/users/me")
+        .with_auth(&user.token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.headers()["deprecation"], "true");
+    assert_eq!(
+        response.headers()["sunset"],
+        "Thu, 01 Apr 2027 00:00:00 GMT"
+    );
+    assert_eq!(
+        response.headers()[LINK],
+        "</v1/users/me>; rel=\"successor-version\""
+    );
+    Assert(response)
+        .status(StatusCode::OK)
+        .json_include(json!({ "username": user.username }))
+        .await;
+
+    let request = TestRequest::get("/users/nobodyhere").build()?;
+    let response = app.oneshot(request).await?;
+
+    assert_eq!(response.headers()["deprecation"], "true");
+    Assert(response)
+        .status(StatusCode::NOT_FOUND)
+        .json_include(json!({ "code": "not_found" }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn successor_link_keeps_pagination_links(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    for _ in 0..3 {
+        app.signup().await?;
+    }
+
+    let request = TestRequest::get("/users?limit=2").build()?;
+    let response = app.oneshot(request).await?;
+
+    assert!(response.headers().get("sunset").is_none());
+    let links: Vec<&str> = response
+        .headers()
+        .get_all(LINK)
+        .iter()
+        .map(|link| link.to_str().unwrap())
+        .collect();
+    assert_eq!(links.len(), 2, "{links:?}");
+    assert!(links[0].starts_with("</users?limit=2&cursor="), "{links:?}");
+    assert!(links[0].ends_with("rel=\"next\""), "{links:?}");
+    assert_eq!(links[1], "</v1/users>; rel=\"successor-version\"");
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn successor_link_drops_query(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let user = app.signup().await?;
+
+    let request = TestRequest::get(format!("/ws?token={}", user.access_token())).build()?;
+    let response = app.oneshot(request).await?;
+
+    let links: Vec<&str> = response
+        .headers()
+        .get_all(LINK)
+        .iter()
+        .map(|link| link.to_str().unwrap())
+        .collect();
+    assert_eq!(links, ["</v1/ws>; rel=\"successor-version\""]);
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn versioned_writes_are_not_deprecated(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    let mentor = app.signup().await?;
+
+    let request = TestRequest::post("/v1/auth/signup")
+        .with_json(TestApp::fake_signup_form_json())
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert!(response.headers().get("deprecation").is_none());
+    let token = TestApp::body_to_token(response.into_body()).await?;
+
+    let request = TestRequest::post("/v1/orders")
+        .with_json(json!({
+            "mentor": mentor.username,
+            "title": TestApp::fake_title(),
+            "description": TestApp::fake_title(),
+            "price": 100,
+        }))
+        .with_auth(token)
+        .build()?;
+    let response = app.oneshot(request).await?;
+
+    assert!(response.headers().get("deprecation").is_none());
+    Assert(response)
+        .status(StatusCode::CREATED)
+        .json_include(json!({ "mentor_id": mentor.id }))
+        .await;
+
+    Ok(())
+}
+
+#[sqlx::test]
+fn versioned_pagination_links_keep_prefix(pool: DbPool) -> TestResult<()> {
+    let mut app = TestApp::spawn(pool);
+    for _ in 0..3 {
+        app.signup().await?;
+    }
+
+    let request = TestRequest::get("/v1/users?sort=username&limit=2").build()?;
+    let response = app.oneshot(request).await?;
+
+    let links: Vec<&str> = response
+        .headers()
+        .get_all(LINK)
+        .iter()
+        .map(|link| link.to_str().unwrap())
+        .collect();
+    assert_eq!(links.len(), 1, "{links:?}");
+    assert!(
+        links[0].starts_with("</v1/users?sort=username&limit=2&cursor="),
+        "{links:?}"
+    );
+
+    Ok(())
+}